@@ -1,5 +1,5 @@
-use easy_cast::Conv;
 use itertools::Itertools;
+use shared::vm::{self, Flow, Machine, Termination};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Instruction {
@@ -21,25 +21,27 @@ pub fn second_answer(input: &str) -> String {
     accumulator_after_termination_of_repaired_program(&mut instructions).to_string()
 }
 
-fn run(instructions: &[Instruction]) -> (bool, Number) {
-    let mut accumulator = 0;
-    let mut program_counter = 0;
-    let mut executed_instructions = vec![false; instructions.len()];
-    while executed_instructions.get(program_counter) == Some(&false) {
-        executed_instructions[program_counter] = true;
-        match instructions[program_counter] {
+impl vm::Instruction for Instruction {
+    type State = Number;
+
+    fn execute(&self, accumulator: &mut Number) -> Flow {
+        match *self {
             Instruction::Acc(number) => {
-                accumulator += number;
-                program_counter += 1;
-            }
-            Instruction::Jmp(offset) => {
-                program_counter = usize::conv(isize::conv(program_counter) + offset);
+                *accumulator += number;
+                Flow::Next
             }
-            Instruction::Nop(_) => program_counter += 1,
+            Instruction::Jmp(offset) => Flow::Jump(offset),
+            Instruction::Nop(_) => Flow::Next,
         }
     }
-    let normal_termination = program_counter == instructions.len();
-    (normal_termination, accumulator)
+}
+
+fn run(instructions: &[Instruction]) -> (bool, Number) {
+    let mut machine = Machine::new(instructions, 0);
+    let termination = machine.run_until_loop();
+    let normal_termination =
+        termination == Termination::Halted && machine.program_counter() == instructions.len();
+    (normal_termination, machine.into_state())
 }
 
 fn accumulator_after_termination_of_repaired_program(instructions: &mut [Instruction]) -> Number {
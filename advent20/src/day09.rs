@@ -1,15 +1,19 @@
+use infrastructure::parameters::Parameter;
 use itertools::Itertools;
 
 type Number = usize;
 
+const PREAMBLE_LENGTH: Parameter<usize> = Parameter::new("preamble_length", 25, 5);
+pub const PARAMETERS: &[&str] = &[PREAMBLE_LENGTH.key()];
+
 pub fn first_answer(input: &str) -> String {
     let numbers = numbers(input);
-    first_number_not_sum_of_two_previous(&numbers, 25).to_string()
+    first_number_not_sum_of_two_previous(&numbers, PREAMBLE_LENGTH.get()).to_string()
 }
 
 pub fn second_answer(input: &str) -> String {
     let numbers = numbers(input);
-    encryption_weakness(&numbers, 25).to_string()
+    encryption_weakness(&numbers, PREAMBLE_LENGTH.get()).to_string()
 }
 
 fn first_number_not_sum_of_two_previous(numbers: &[Number], number_of_previous: usize) -> Number {
@@ -65,16 +69,13 @@ mod tests {
     use infrastructure::{Input, Puzzle, test};
 
     use super::*;
-    use crate::tests::{input, test_on_input};
+    use crate::tests::test_on_input;
 
     const DAY: usize = 9;
 
     #[test]
     fn first_answer_example() {
-        let numbers = numbers(&input(DAY, Input::Example(0)));
-        let actual = first_number_not_sum_of_two_previous(&numbers, 5);
-        let expected = 127;
-        assert_eq!(actual, expected);
+        test_on_input(DAY, Puzzle::First, Input::Example(0), 127);
     }
 
     #[test]
@@ -84,10 +85,7 @@ mod tests {
 
     #[test]
     fn second_answer_example() {
-        let numbers = numbers(&input(DAY, Input::Example(0)));
-        let actual = encryption_weakness(&numbers, 5);
-        let expected = 62;
-        assert_eq!(actual, expected);
+        test_on_input(DAY, Puzzle::Second, Input::Example(0), 62);
     }
 
     #[test]
@@ -1,5 +1,6 @@
 use itertools::Itertools;
 use shared::{
+    automaton::Automaton,
     grid::{self, Direction, Grid, Position},
     vector::Vector,
 };
@@ -7,42 +8,38 @@ use shared::{
 type SeatLayout = Grid<char>;
 
 pub fn first_answer(input: &str) -> String {
-    let mut seat_layout = SeatLayout::from(input);
-    loop {
-        let next = next_considering_adjacent_seats(&seat_layout);
-        if next == seat_layout {
-            return number_of_occupied_seats(&next).to_string();
-        }
-        seat_layout = next;
-    }
+    let seat_layout = SeatLayout::from(input);
+    let mut automaton = Automaton::new(
+        seat_layout,
+        grid::orthogonal_and_diagonal_neighbors,
+        |&seat: &char, adjacent_seats: &[char]| next_seat(seat, adjacent_seats, 4),
+    );
+    automaton.run_until_fixed_point();
+    number_of_occupied_seats(automaton.space()).to_string()
 }
 
 pub fn second_answer(input: &str) -> String {
-    let mut seat_layout = SeatLayout::from(input);
+    let seat_layout = SeatLayout::from(input);
     let visible_seats = visible_seats_from_all(&seat_layout);
-    loop {
-        let next = next_considering_visible_seats(&seat_layout, &visible_seats);
-        if next == seat_layout {
-            return number_of_occupied_seats(&next).to_string();
-        }
-        seat_layout = next;
-    }
+    let mut automaton = Automaton::new(
+        seat_layout,
+        |position| visible_seats[position].iter().copied(),
+        |&seat: &char, visible_seats: &[char]| next_seat(seat, visible_seats, 5),
+    );
+    automaton.run_until_fixed_point();
+    number_of_occupied_seats(automaton.space()).to_string()
 }
 
-fn next_considering_adjacent_seats(seat_layout: &SeatLayout) -> SeatLayout {
-    seat_layout.map(|position, &seat| {
-        let number_of_occupied_adjacent_seats = grid::orthogonal_and_diagonal_neighbors(position)
-            .into_iter()
-            .filter(|&neighbor| seat_layout.get(neighbor).is_some_and(|&seat| seat == '#'))
-            .count();
-        if seat == 'L' && number_of_occupied_adjacent_seats == 0 {
-            '#'
-        } else if seat == '#' && number_of_occupied_adjacent_seats >= 4 {
-            'L'
-        } else {
-            seat
-        }
-    })
+fn next_seat(seat: char, considered_seats: &[char], tolerance: usize) -> char {
+    let number_of_occupied_considered_seats =
+        considered_seats.iter().filter(|&&seat| seat == '#').count();
+    if seat == 'L' && number_of_occupied_considered_seats == 0 {
+        '#'
+    } else if seat == '#' && number_of_occupied_considered_seats >= tolerance {
+        'L'
+    } else {
+        seat
+    }
 }
 
 fn visible_seats_from_all(seat_layout: &SeatLayout) -> Grid<Vec<Position>> {
@@ -71,25 +68,6 @@ fn visible_seat_in_direction(
     None
 }
 
-fn next_considering_visible_seats(
-    seat_layout: &SeatLayout,
-    visible_seats: &Grid<Vec<Position>>,
-) -> SeatLayout {
-    seat_layout.map(|position, &seat| {
-        let number_of_occupied_visible_seats = visible_seats[position]
-            .iter()
-            .filter(|&&visible| seat_layout[visible] == '#')
-            .count();
-        if seat == 'L' && number_of_occupied_visible_seats == 0 {
-            '#'
-        } else if seat == '#' && number_of_occupied_visible_seats >= 5 {
-            'L'
-        } else {
-            seat
-        }
-    })
-}
-
 fn number_of_occupied_seats(seat_layout: &SeatLayout) -> usize {
     seat_layout
         .iter_row_major()
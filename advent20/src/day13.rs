@@ -17,7 +17,9 @@ pub fn second_answer(input: &str) -> String {
     let congruences = buses.into_iter().enumerate().filter_map(|(index, bus)| {
         bus.map(|bus| (isize::conv(bus) - isize::conv(index), bus.cast()))
     });
-    chinese_remainder_theorem(congruences).to_string()
+    chinese_remainder_theorem(congruences)
+        .expect("buses should have a common departure pattern")
+        .to_string()
 }
 
 fn wait_and_earliest_bus(timestamp: Timestamp, buses: &[Option<Bus>]) -> (Time, Bus) {
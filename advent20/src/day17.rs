@@ -1,77 +1,48 @@
 use std::array;
 
-use ahash::AHashSet;
 use easy_cast::Cast;
-use itertools::Itertools;
+use shared::{
+    automaton::{self, Automaton, Infinite},
+    grid::Coordinate,
+};
 
-type Grid<const DIMENSIONS: usize> = AHashSet<Position<DIMENSIONS>>;
+type Grid<const DIMENSIONS: usize> = Infinite<Position<DIMENSIONS>, bool>;
 type Position<const DIMENSIONS: usize> = [Coordinate; DIMENSIONS];
-type Coordinate = i8;
 
 pub fn first_answer(input: &str) -> String {
     let initial_grid = grid::<3>(input);
     let final_grid = grid_after(initial_grid, 6);
-    final_grid.into_iter().count().to_string()
+    final_grid.number_of_cells().to_string()
 }
 
 pub fn second_answer(input: &str) -> String {
     let initial_grid = grid::<4>(input);
     let final_grid = grid_after(initial_grid, 6);
-    final_grid.into_iter().count().to_string()
+    final_grid.number_of_cells().to_string()
 }
 
 fn grid_after<const DIMENSIONS: usize>(
-    mut grid: Grid<DIMENSIONS>,
+    grid: Grid<DIMENSIONS>,
     number_of_cycles: usize,
-) -> Grid<DIMENSIONS> {
-    for _ in 0..number_of_cycles {
-        grid = grid_after_cycle(&grid);
-    }
-    grid
-}
-
-fn grid_after_cycle<const DIMENSIONS: usize>(grid: &Grid<DIMENSIONS>) -> Grid<DIMENSIONS> {
-    let all_neighborhoods: AHashSet<Position<DIMENSIONS>> = grid
-        .iter()
-        .flat_map(|&active| neighborhood(active))
-        .collect();
-    let mut next_grid = Grid::new();
-    for position in all_neighborhoods {
-        let active = grid.contains(&position);
-        let number_of_active_neighbors = number_of_active_neighbors(grid, position);
-        let active_with_two_or_three_neighbors =
-            active && (2..=3).contains(&number_of_active_neighbors);
-        let inactive_with_three_neighbors = !active && number_of_active_neighbors == 3;
-        if active_with_two_or_three_neighbors || inactive_with_three_neighbors {
-            next_grid.insert(position);
-        }
-    }
-    next_grid
-}
-
-fn number_of_active_neighbors<const DIMENSIONS: usize>(
-    grid: &Grid<DIMENSIONS>,
-    position: Position<DIMENSIONS>,
-) -> usize {
-    let all = neighborhood(position)
-        .filter(|position| grid.contains(position))
-        .count();
-    let this: usize = grid.contains(&position).into();
-    all - this
+) -> Grid<DIMENSIONS>
+where
+    Position<DIMENSIONS>: Default,
+{
+    let mut automaton = Automaton::new(grid, automaton::moore_neighbors, is_active_after_cycle);
+    automaton.run(number_of_cycles);
+    automaton.into_space()
 }
 
-fn neighborhood<const DIMENSIONS: usize>(
-    position: Position<DIMENSIONS>,
-) -> impl Iterator<Item = Position<DIMENSIONS>> {
-    position
-        .into_iter()
-        .map(|coordinate| coordinate - 1..=coordinate + 1)
-        .multi_cartesian_product()
-        .map(|position| {
-            position
-                .try_into()
-                .expect("position should have the correct number of coordinates")
-        })
+#[expect(
+    clippy::trivially_copy_pass_by_ref,
+    reason = "automaton rules receive states by reference"
+)]
+fn is_active_after_cycle(&active: &bool, neighbors: &[bool]) -> bool {
+    let number_of_active_neighbors = neighbors.iter().filter(|&&active| active).count();
+    let active_with_two_or_three_neighbors =
+        active && (2..=3).contains(&number_of_active_neighbors);
+    let inactive_with_three_neighbors = !active && number_of_active_neighbors == 3;
+    active_with_two_or_three_neighbors || inactive_with_three_neighbors
 }
 
 fn grid<const DIMENSIONS: usize>(input: &str) -> Grid<DIMENSIONS> {
@@ -85,7 +56,7 @@ fn grid<const DIMENSIONS: usize>(input: &str) -> Grid<DIMENSIONS> {
     let actives = actives.map(|active: [Coordinate; 2]| {
         array::from_fn(|index| active.get(index).copied().unwrap_or(0))
     });
-    actives.collect()
+    Infinite::from_live(actives)
 }
 
 #[cfg(test)]
@@ -121,7 +92,10 @@ mod tests {
     #[test]
     fn grid_after_cycle() {
         let grid = grid(&input(DAY, Input::Example(0)));
-        let actual = super::grid_after_cycle(&grid);
+        let actual: AHashSet<Position<3>> = grid_after(grid, 1)
+            .cells()
+            .map(|(position, _)| position)
+            .collect();
         let expected = AHashSet::from([
             [1, 0, -1],
             [2, 2, -1],
@@ -137,11 +111,4 @@ mod tests {
         ]);
         assert_eq!(actual, expected);
     }
-
-    #[test]
-    fn neighborhood() {
-        let actual = super::neighborhood([0, 0, 0]).count();
-        let expected = 27;
-        assert_eq!(actual, expected);
-    }
 }
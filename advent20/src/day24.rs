@@ -1,18 +1,12 @@
 use ahash::AHashSet;
-use shared::{grid::Position, vector::Vector};
+use shared::{
+    automaton::{Automaton, Infinite},
+    hex::{self, Direction, Position},
+    vector::Vector,
+};
 
 type Floor = AHashSet<Position>;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Direction {
-    East,
-    SouthEast,
-    SouthWest,
-    West,
-    NorthWest,
-    NorthEast,
-}
-
 pub fn first_answer(input: &str) -> String {
     let walks = walks(input);
     floor(walks).len().to_string()
@@ -38,87 +32,35 @@ fn floor(
 }
 
 fn destination(walk: impl IntoIterator<Item = Direction>) -> Position {
-    let mut position = [0, 0];
-    for direction in walk {
-        let step = match direction {
-            Direction::East => [2, 0],
-            Direction::SouthEast => [1, 1],
-            Direction::SouthWest => [-1, 1],
-            Direction::West => [-2, 0],
-            Direction::NorthWest => [-1, -1],
-            Direction::NorthEast => [1, -1],
-        };
-        position = position.add(step);
-    }
-    position
+    walk.into_iter().fold([0, 0], Vector::add)
 }
 
-fn floor_after(days: usize, mut floor: Floor) -> Floor {
-    for _ in 1..=days {
-        floor = next_floor(&floor);
-    }
-    floor
-}
-
-fn next_floor(floor: &Floor) -> Floor {
-    let neighborhood: AHashSet<Position> = floor
-        .iter()
-        .flat_map(|&position| neighbors(position))
-        .chain(floor.iter().copied())
-        .collect();
-    neighborhood
-        .into_iter()
-        .filter(|&position| should_be_black(floor, position))
+fn floor_after(days: usize, floor: Floor) -> Floor {
+    let mut automaton = Automaton::new(Infinite::from_live(floor), hex::neighbors, should_be_black);
+    automaton.run(days);
+    automaton
+        .space()
+        .cells()
+        .map(|(position, _)| position)
         .collect()
 }
 
-fn should_be_black(floor: &Floor, position: Position) -> bool {
-    let is_black = floor.contains(&position);
-    let number_of_black_neighbors = number_of_black_neighbors(floor, position);
+#[expect(
+    clippy::trivially_copy_pass_by_ref,
+    reason = "automaton rules receive states by reference"
+)]
+fn should_be_black(&is_black: &bool, neighbors: &[bool]) -> bool {
+    let number_of_black_neighbors = neighbors.iter().filter(|&&is_black| is_black).count();
     let is_black_with_one_or_two_black_neighbors =
         is_black && [1, 2].contains(&number_of_black_neighbors);
     let is_white_with_two_black_neighbors = !is_black && number_of_black_neighbors == 2;
     is_black_with_one_or_two_black_neighbors || is_white_with_two_black_neighbors
 }
 
-fn number_of_black_neighbors(floor: &Floor, position: Position) -> usize {
-    neighbors(position)
-        .into_iter()
-        .filter(|neighbor| floor.contains(neighbor))
-        .count()
-}
-
-fn neighbors(position: Position) -> [Position; 6] {
-    let steps = [[2, 0], [1, 1], [-1, 1], [-2, 0], [-1, -1], [1, -1]];
-    steps.map(|step| position.add(step))
-}
-
 fn walks(input: &str) -> impl Iterator<Item = Vec<Direction>> + '_ {
-    input.lines().map(walk)
-}
-
-fn walk(mut str: &str) -> Vec<Direction> {
-    let mut walk = vec![];
-    while !str.is_empty() {
-        let (remaining, direction) = direction(str);
-        str = remaining;
-        walk.push(direction);
-    }
-    walk
-}
-
-fn direction(str: &str) -> (&str, Direction) {
-    match &str[0..1] {
-        "e" => (&str[1..], Direction::East),
-        "w" => (&str[1..], Direction::West),
-        _ => match &str[0..2] {
-            "se" => (&str[2..], Direction::SouthEast),
-            "sw" => (&str[2..], Direction::SouthWest),
-            "nw" => (&str[2..], Direction::NorthWest),
-            "ne" => (&str[2..], Direction::NorthEast),
-            _ => panic!("direction should be 'e', 'se', 'sw', 'w', 'nw', or 'ne'"),
-        },
-    }
+    input.lines().map(|line| {
+        hex::walk(line).expect("direction should be 'e', 'se', 'sw', 'w', 'nw', or 'ne'")
+    })
 }
 
 #[cfg(test)]
@@ -144,7 +86,12 @@ mod tests {
     #[test]
     fn second_answer_example() {
         let input = input(DAY, Input::Example(0));
-        let mut floor = floor(walks(&input));
+        let floor = floor(walks(&input));
+        let mut automaton = Automaton::new(
+            Infinite::from_live(floor),
+            hex::neighbors,
+            super::should_be_black,
+        );
 
         let expected = AHashMap::from([
             (1, 15),
@@ -169,11 +116,11 @@ mod tests {
         ]);
 
         for day in 1..=100 {
-            floor = next_floor(&floor);
+            automaton.step();
 
             if let Some(&expected) = expected.get(&day) {
                 assert_eq!(
-                    floor.len(),
+                    automaton.space().number_of_cells(),
                     expected,
                     "number of black tiles should match expected for day {day}"
                 );
@@ -188,47 +135,24 @@ mod tests {
 
     #[test]
     fn should_be_black() {
-        let function =
-            |(floor, position)| super::should_be_black(&AHashSet::from_iter(floor), position);
+        let function = |(floor, position): (Vec<Position>, Position)| {
+            let floor = AHashSet::<Position>::from_iter(floor);
+            let neighbors = hex::neighbors(position).map(|neighbor| floor.contains(&neighbor));
+            super::should_be_black(&floor.contains(&position), &neighbors)
+        };
         let cases = [
             (
-                (vec![[0, 2], [0, -2], [4, 0], [3, 1], [0, 0]], [0, 0]),
+                (vec![[-1, 2], [1, -2], [2, 0], [1, 1], [0, 0]], [0, 0]),
                 false,
             ),
-            ((vec![[5, 3], [7, 3], [6, 0], [6, 4], [6, 2]], [6, 2]), true),
+            ((vec![[1, 3], [2, 3], [3, 0], [1, 4], [2, 2]], [2, 2]), true),
             (
-                (vec![[4, 4], [5, 5], [2, 6], [1, 5], [2, 4]], [3, 5]),
+                (vec![[0, 4], [0, 5], [-2, 6], [-2, 5], [-1, 4]], [-1, 5]),
                 false,
             ),
-            ((vec![[4, 4], [5, 4], [2, 7], [0, 5], [2, 4]], [3, 5]), true),
-        ];
-        test::cases(function, cases);
-    }
-
-    #[test]
-    fn number_of_black_neighbors() {
-        let function = |(floor, position)| {
-            super::number_of_black_neighbors(&AHashSet::from_iter(floor), position)
-        };
-        let cases = [
-            ((vec![[0, 2], [0, -2], [4, 0], [3, 1]], [0, 0]), 0),
-            ((vec![[5, 3], [7, 3], [6, 0], [6, 4]], [6, 2]), 2),
-            ((vec![[4, 4], [5, 5], [2, 6], [1, 5], [2, 4]], [3, 5]), 5),
-        ];
-        test::cases(function, cases);
-    }
-
-    #[test]
-    fn neighbors() {
-        let function = |position| AHashSet::from(super::neighbors(position));
-        let cases = [
-            (
-                [0, 0],
-                AHashSet::from([[2, 0], [1, 1], [-1, 1], [-2, 0], [-1, -1], [1, -1]]),
-            ),
             (
-                [3, 4],
-                AHashSet::from([[5, 4], [4, 5], [2, 5], [1, 4], [2, 3], [4, 3]]),
+                (vec![[0, 4], [1, 4], [-3, 7], [-3, 5], [-1, 4]], [-1, 5]),
+                true,
             ),
         ];
         test::cases(function, cases);
@@ -1,8 +1,14 @@
 use itertools::Itertools;
+use shared::modular::{ModInt, discrete_logarithm};
+
+const SUBJECT_NUMBER: Key = 7;
+const MODULUS: Key = 20_201_227;
+
+type Key = u64;
 
 pub fn first_answer(input: &str) -> String {
     let [card_public_key, door_public_key] = public_keys(input);
-    let card_loop_size = loop_size(7, card_public_key);
+    let card_loop_size = loop_size(SUBJECT_NUMBER, card_public_key);
     let encryption_key = encryption_key(door_public_key, card_loop_size);
     encryption_key.to_string()
 }
@@ -11,30 +17,20 @@ pub fn second_answer(_input: &str) -> String {
     "There is no second puzzle on the 25th. Merry Christmas!".to_owned()
 }
 
-fn loop_size(subject_number: usize, public_key: usize) -> usize {
-    let mut value = 1;
-    for loop_size in 0.. {
-        if value == public_key {
-            return loop_size;
-        }
-        value = transform_once(subject_number, value);
-    }
-    unreachable!("loop should only break on value matching public key");
-}
-
-fn encryption_key(subject_number: usize, loop_size: usize) -> usize {
-    let mut value = 1;
-    for _ in 0..loop_size {
-        value = transform_once(subject_number, value);
-    }
-    value
+/// The number of times the subject number is transformed into the public key, which is the
+/// discrete logarithm of the public key.
+fn loop_size(subject_number: Key, public_key: Key) -> u64 {
+    let [subject_number, public_key] =
+        [subject_number, public_key].map(|number| ModInt::new(number, MODULUS));
+    discrete_logarithm(subject_number, public_key)
+        .expect("public key should be a transformation of the subject number")
 }
 
-fn transform_once(subject_number: usize, value: usize) -> usize {
-    (value * subject_number) % 20_201_227
+fn encryption_key(subject_number: Key, loop_size: u64) -> Key {
+    ModInt::new(subject_number, MODULUS).pow(loop_size).value()
 }
 
-fn public_keys(input: &str) -> [usize; 2] {
+fn public_keys(input: &str) -> [Key; 2] {
     input
         .lines()
         .map(|public_key| public_key.parse().expect("public key should be numeric"))
@@ -47,6 +43,7 @@ fn public_keys(input: &str) -> [usize; 2] {
 mod tests {
     use infrastructure::{Input, Puzzle, test};
 
+    use super::*;
     use crate::tests::test_on_input;
 
     const DAY: usize = 25;
@@ -63,7 +60,7 @@ mod tests {
 
     #[test]
     fn loop_size() {
-        let function = |public_key| super::loop_size(7, public_key);
+        let function = |public_key| super::loop_size(SUBJECT_NUMBER, public_key);
         let cases = [(5_764_801, 8), (17_807_724, 11)];
         test::cases(function, cases);
     }
@@ -1,5 +1,7 @@
+use std::iter;
+
 use itertools::Itertools;
-use shared::string::usizes;
+use shared::{matrix::DMatrix, string::usizes};
 
 const CYCLE_LENGTH: usize = 7;
 const FIRST_CYCLE_LENGTH: usize = CYCLE_LENGTH + 2;
@@ -16,17 +18,56 @@ pub fn second_answer(input: &str) -> String {
     number_of_fish_after(256, fish).to_string()
 }
 
-fn number_of_fish_after(number_of_days: usize, mut fish: [usize; FIRST_CYCLE_LENGTH]) -> usize {
+/// Simulates every fish on its own, which is only feasible for the first puzzle.
+pub fn first_answer_by_simulation(input: &str) -> String {
+    let mut timers = usizes(input);
+    for _ in 0..80 {
+        let number_of_new_fish = timers.iter().filter(|&&timer| timer == 0).count();
+        for timer in &mut timers {
+            *timer = timer.checked_sub(1).unwrap_or(CYCLE_LENGTH - 1);
+        }
+        timers.extend(iter::repeat_n(FIRST_CYCLE_LENGTH - 1, number_of_new_fish));
+    }
+    timers.len().to_string()
+}
+
+pub fn first_answer_by_buckets(input: &str) -> String {
+    let fish = fish(input);
+    number_of_fish_after_by_buckets(80, fish).to_string()
+}
+
+pub fn second_answer_by_buckets(input: &str) -> String {
+    let fish = fish(input);
+    number_of_fish_after_by_buckets(256, fish).to_string()
+}
+
+fn number_of_fish_after(number_of_days: usize, fish: Fish) -> usize {
+    transition()
+        .pow(number_of_days)
+        .vector_mul(&fish)
+        .into_iter()
+        .sum()
+}
+
+/// Counts the fish per timer value day by day, where the fish whose timers run out reset their
+/// timers and add as many new fish.
+fn number_of_fish_after_by_buckets(number_of_days: usize, mut fish: Fish) -> usize {
     for _ in 0..number_of_days {
-        advance_one_day(&mut fish);
+        fish.rotate_left(1);
+        fish[CYCLE_LENGTH - 1] += fish[FIRST_CYCLE_LENGTH - 1];
     }
     fish.into_iter().sum()
 }
 
-fn advance_one_day(fish: &mut Fish) {
-    let number_of_spawning_fish = fish[0];
-    fish.rotate_left(1);
-    fish[CYCLE_LENGTH - 1] += number_of_spawning_fish;
+/// The matrix that maps the number of fish per timer value to that of the next day.
+fn transition() -> DMatrix<usize> {
+    let mut transition = DMatrix::zeros(FIRST_CYCLE_LENGTH, FIRST_CYCLE_LENGTH);
+    for timer in 1..FIRST_CYCLE_LENGTH {
+        transition[[timer - 1, timer]] = 1;
+    }
+    transition[[CYCLE_LENGTH - 1, 0]] = 1;
+    transition[[FIRST_CYCLE_LENGTH - 1, 0]] = 1;
+    transition
 }
 
 fn fish(input: &str) -> Fish {
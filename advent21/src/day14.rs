@@ -18,6 +18,28 @@ pub fn second_answer(input: &str) -> String {
     (maximum_count - minimum_count).to_string()
 }
 
+/// Grows the polymer itself, which is only feasible for the first puzzle.
+pub fn first_answer_by_brute_force(input: &str) -> String {
+    let (template, rules) = polymer_template_and_pair_insertion_rules(input);
+    let mut polymer = template.to_vec();
+    for _ in 0..10 {
+        let insertions = polymer.windows(2).map(|pair| rules[&[pair[0], pair[1]]]);
+        polymer = polymer
+            .iter()
+            .copied()
+            .interleave(insertions.collect_vec())
+            .collect();
+    }
+    let (&minimum_count, &maximum_count) = polymer
+        .into_iter()
+        .counts()
+        .values()
+        .minmax()
+        .into_option()
+        .expect("polymer should not be empty");
+    (maximum_count - minimum_count).to_string()
+}
+
 fn minimum_and_maximum_element_counts(input: &str, number_of_steps: usize) -> [usize; 2] {
     let (template, rules) = polymer_template_and_pair_insertion_rules(input);
     let element_counts = elements_counts(template, &rules, number_of_steps);
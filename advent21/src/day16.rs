@@ -1,137 +1,227 @@
-use core::str;
-use std::{
-    cmp,
-    ops::{Add, Mul},
-};
+use std::str::FromStr;
 
+use anyhow::{anyhow, bail};
 use bitvec::{field::BitField, order::Msb0, slice::BitSlice, vec::BitVec, view::BitView};
 
-type Packet = BitVec<usize, BitOrder>;
+/// A packet of the Buoyancy Interchange Transmission System, decoded from its hexadecimal
+/// transmission.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Packet {
+    pub version_number: VersionNumber,
+    pub kind: Kind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Kind {
+    Literal(Value),
+    Operator {
+        operator: Operator,
+        sub_packets: Vec<Packet>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Sum,
+    Product,
+    Minimum,
+    Maximum,
+    GreaterThan,
+    LessThan,
+    EqualTo,
+}
+
+impl FromStr for Packet {
+    type Err = anyhow::Error;
+
+    /// Decodes the outermost packet of a hexadecimal transmission. Bits after the packet must be
+    /// zero padding.
+    fn from_str(hexadecimal: &str) -> Result<Self, Self::Err> {
+        let bits = bits(hexadecimal.trim_end())?;
+        let (remaining, packet) = packet(&bits)?;
+        if remaining.any() {
+            bail!("transmission should end with zero padding after the outermost packet");
+        }
+        Ok(packet)
+    }
+}
+
+impl Packet {
+    /// The sum of the version numbers of this packet and all packets nested in it.
+    #[must_use]
+    pub fn version_number_sum(&self) -> VersionNumber {
+        let sub_version_number_sum = match &self.kind {
+            Kind::Literal(_) => 0,
+            Kind::Operator { sub_packets, .. } => {
+                sub_packets.iter().map(Packet::version_number_sum).sum()
+            }
+        };
+        self.version_number + sub_version_number_sum
+    }
+
+    /// The value of the expression that the packet represents.
+    #[must_use]
+    pub fn value(&self) -> Value {
+        match &self.kind {
+            Kind::Literal(value) => *value,
+            Kind::Operator {
+                operator,
+                sub_packets,
+            } => operator.apply(sub_packets.iter().map(Packet::value)),
+        }
+    }
+}
+
+impl Operator {
+    fn apply(self, values: impl Iterator<Item = Value>) -> Value {
+        match self {
+            Self::Sum => values.sum(),
+            Self::Product => values.product(),
+            Self::Minimum => values.min().expect("minimum should have sub-packets"),
+            Self::Maximum => values.max().expect("maximum should have sub-packets"),
+            Self::GreaterThan => comparison(values, |left, right| left > right),
+            Self::LessThan => comparison(values, |left, right| left < right),
+            Self::EqualTo => comparison(values, |left, right| left == right),
+        }
+    }
+}
+
+fn comparison(
+    mut values: impl Iterator<Item = Value>,
+    comparator: impl Fn(Value, Value) -> bool,
+) -> Value {
+    let (Some(left), Some(right)) = (values.next(), values.next()) else {
+        panic!("comparison should have two sub-packets");
+    };
+    comparator(left, right).into()
+}
+
 type Bits<'bits> = &'bits BitSlice<usize, BitOrder>;
 type BitOrder = Msb0;
-type VersionNumber = Decimal;
-type Value = Decimal;
+pub type VersionNumber = Decimal;
+pub type Value = Decimal;
 type Decimal = usize;
 
 pub fn first_answer(input: &str) -> String {
-    let packet = packet(input);
-    let (_, version_number_sum, _) = evaluation(&packet);
-    version_number_sum.to_string()
+    transmission(input).version_number_sum().to_string()
 }
 
 pub fn second_answer(input: &str) -> String {
-    let packet = packet(input);
-    let (_, _, value) = evaluation(&packet);
-    value.to_string()
-}
-
-fn evaluation(packet: Bits) -> (Bits, VersionNumber, Value) {
-    match type_id(packet) {
-        0 => operation(packet, <Decimal as Add>::add),
-        1 => operation(packet, <Decimal as Mul>::mul),
-        2 => operation(packet, cmp::min),
-        3 => operation(packet, cmp::max),
-        4 => literal(packet),
-        5 => operation(packet, |left, right| (left > right).into()),
-        6 => operation(packet, |left, right| (left < right).into()),
-        7 => operation(packet, |left, right| (left == right).into()),
-        _ => panic!("type id should be between zero and seven"),
-    }
-}
-
-fn operation(
-    packet: Bits,
-    operator: impl Fn(Value, Value) -> Value,
-) -> (Bits, VersionNumber, Value) {
-    let length_type_id = packet[6];
-    let (remaining, version_number_sum, sub_values) = if length_type_id {
-        sub_evaluations_number_of_sub_packets(packet)
-    } else {
-        sub_evaluations_total_length(packet)
-    };
+    transmission(input).value().to_string()
+}
 
-    let value = sub_values
-        .into_iter()
-        .reduce(operator)
-        .expect("number of sub-packets should be at least one");
-    (
-        remaining,
-        version_number(packet) + version_number_sum,
-        value,
-    )
-}
-
-fn sub_evaluations_total_length(packet: Bits) -> (Bits, VersionNumber, Vec<Value>) {
-    let total_number_of_bits = decimal(&packet[7..22]);
-    let mut sub_packets = &packet[22..22 + total_number_of_bits];
-    let mut version_number_sum = 0;
-    let mut values = vec![];
-    while !sub_packets.is_empty() {
-        let (rest, sub_version_number_sum, value) = evaluation(sub_packets);
-        sub_packets = rest;
-        version_number_sum += sub_version_number_sum;
-        values.push(value);
-    }
-    (
-        &packet[22 + total_number_of_bits..],
-        version_number_sum,
-        values,
-    )
-}
-
-fn sub_evaluations_number_of_sub_packets(packet: Bits) -> (Bits, VersionNumber, Vec<Value>) {
-    let number_of_sub_packets = decimal(&packet[7..18]);
-    let mut remaining = &packet[18..];
-    let mut version_number_sum = 0;
-    let mut values = vec![];
-    for _ in 0..number_of_sub_packets {
-        let (rest, sub_version_number, value) = evaluation(remaining);
-        remaining = rest;
-        version_number_sum += sub_version_number;
-        values.push(value);
-    }
-    (remaining, version_number_sum, values)
-}
-
-fn literal(packet: Bits) -> (Bits, VersionNumber, Value) {
-    let mut remaining = &packet[6..];
-    let mut value_bits: BitVec<usize, BitOrder> = BitVec::new();
-    while remaining[0] {
-        value_bits.extend_from_bitslice(&remaining[1..5]);
-        remaining = &remaining[5..];
+fn transmission(input: &str) -> Packet {
+    input.parse().expect("transmission should be valid")
+}
+
+fn packet(bits: Bits) -> anyhow::Result<(Bits, Packet)> {
+    let (version_number, remaining) = decimal(bits, 3)?;
+    let (type_id, remaining) = decimal(remaining, 3)?;
+    let operator = match type_id {
+        0 => Operator::Sum,
+        1 => Operator::Product,
+        2 => Operator::Minimum,
+        3 => Operator::Maximum,
+        4 => {
+            let (value, remaining) = literal(remaining)?;
+            let packet = Packet {
+                version_number,
+                kind: Kind::Literal(value),
+            };
+            return Ok((remaining, packet));
+        }
+        5 => Operator::GreaterThan,
+        6 => Operator::LessThan,
+        _ => Operator::EqualTo,
+    };
+    let (remaining, sub_packets) = sub_packets(remaining)?;
+    let number_of_sub_packets_is_valid = match operator {
+        Operator::GreaterThan | Operator::LessThan | Operator::EqualTo => sub_packets.len() == 2,
+        Operator::Sum | Operator::Product | Operator::Minimum | Operator::Maximum => {
+            !sub_packets.is_empty()
+        }
+    };
+    if !number_of_sub_packets_is_valid {
+        bail!(
+            "operator {operator:?} should not have {} sub-packets",
+            sub_packets.len()
+        );
     }
-    value_bits.extend_from_bitslice(&remaining[1..5]);
-    remaining = &remaining[5..];
-    (remaining, version_number(packet), decimal(&value_bits))
+    let packet = Packet {
+        version_number,
+        kind: Kind::Operator {
+            operator,
+            sub_packets,
+        },
+    };
+    Ok((remaining, packet))
 }
 
-fn version_number(packet: Bits) -> VersionNumber {
-    decimal(&packet[0..3])
+fn sub_packets(bits: Bits) -> anyhow::Result<(Bits, Vec<Packet>)> {
+    let (length_type_id, remaining) = decimal(bits, 1)?;
+    let mut sub_packets = vec![];
+    if length_type_id == 0 {
+        let (total_number_of_bits, remaining) = decimal(remaining, 15)?;
+        let (mut sub_bits, remaining) = split(remaining, total_number_of_bits)?;
+        while !sub_bits.is_empty() {
+            let (rest, sub_packet) = packet(sub_bits)?;
+            sub_bits = rest;
+            sub_packets.push(sub_packet);
+        }
+        Ok((remaining, sub_packets))
+    } else {
+        let (number_of_sub_packets, mut remaining) = decimal(remaining, 11)?;
+        for _ in 0..number_of_sub_packets {
+            let (rest, sub_packet) = packet(remaining)?;
+            remaining = rest;
+            sub_packets.push(sub_packet);
+        }
+        Ok((remaining, sub_packets))
+    }
 }
 
-fn type_id(packet: Bits) -> Decimal {
-    decimal(&packet[3..6])
+fn literal(mut bits: Bits) -> anyhow::Result<(Value, Bits)> {
+    let mut value_bits: BitVec<usize, BitOrder> = BitVec::new();
+    loop {
+        let (group, remaining) = split(bits, 5)?;
+        value_bits.extend_from_bitslice(&group[1..]);
+        bits = remaining;
+        if !group[0] {
+            break;
+        }
+    }
+    if value_bits.len() > Value::BITS as usize {
+        bail!("literal value should fit in {} bits", Value::BITS);
+    }
+    Ok((value_bits.load_be(), bits))
 }
 
-fn decimal(bits: Bits) -> Decimal {
-    bits.load_be()
+fn decimal(bits: Bits, number_of_bits: usize) -> anyhow::Result<(Decimal, Bits)> {
+    let (decimal, remaining) = split(bits, number_of_bits)?;
+    Ok((decimal.load_be(), remaining))
 }
 
-fn packet(mut input: &str) -> Packet {
-    input = input.trim_end();
+fn split(bits: Bits, number_of_bits: usize) -> anyhow::Result<(Bits, Bits)> {
+    if bits.len() < number_of_bits {
+        bail!("transmission should not end in the middle of a packet");
+    }
+    Ok(bits.split_at(number_of_bits))
+}
 
-    let mut packet = Packet::new();
-    for index in 0..input.len() {
-        let byte =
-            u8::from_str_radix(&input[index..=index], 16).expect("hexidecimal digit should parse");
-        packet.extend_from_bitslice(&byte.view_bits::<BitOrder>()[4..]);
+fn bits(hexadecimal: &str) -> anyhow::Result<BitVec<usize, BitOrder>> {
+    let mut bits = BitVec::new();
+    for char in hexadecimal.chars() {
+        let digit = char
+            .to_digit(16)
+            .ok_or_else(|| anyhow!("'{char}' should be a hexadecimal digit"))?;
+        let digit = u8::try_from(digit).expect("hexadecimal digit should fit in byte");
+        bits.extend_from_bitslice(&digit.view_bits::<BitOrder>()[4..]);
     }
-    packet
+    Ok(bits)
 }
 
 #[cfg(test)]
 mod tests {
-    use bitvec::bits;
     use infrastructure::{Input, Puzzle, test};
 
     use super::*;
@@ -141,7 +231,7 @@ mod tests {
 
     #[test]
     fn first_answer_example() {
-        let function = |input| evaluation(&packet(input)).1;
+        let function = |input| transmission(input).version_number_sum();
         let cases = [
             ("8A004A801A8002F478", 16),
             ("620080001611562C8802118E34", 12),
@@ -158,7 +248,7 @@ mod tests {
 
     #[test]
     fn second_examples() {
-        let function = |input| evaluation(&packet(input)).2;
+        let function = |input| transmission(input).value();
         let cases = [
             ("C200B40A82", 3),
             ("04005AC33890", 54),
@@ -184,27 +274,39 @@ mod tests {
 
     #[test]
     fn literal() {
-        let input = small_example_packet();
-        let actual = super::literal(&input);
-        let expected = (bits![usize, BitOrder; 0, 0, 0], 6, 2021);
+        let actual = transmission("D2FE28");
+        let expected = Packet {
+            version_number: 6,
+            kind: Kind::Literal(2021),
+        };
         assert_eq!(actual, expected);
     }
 
     #[test]
-    fn version_number() {
-        let actual = super::version_number(&small_example_packet());
-        let expected = 6;
+    fn operator() {
+        let actual = transmission("38006F45291200");
+        let literal = |value| Packet {
+            version_number: if value == 10 { 6 } else { 2 },
+            kind: Kind::Literal(value),
+        };
+        let expected = Packet {
+            version_number: 1,
+            kind: Kind::Operator {
+                operator: Operator::LessThan,
+                sub_packets: vec![literal(10), literal(20)],
+            },
+        };
         assert_eq!(actual, expected);
     }
 
     #[test]
-    fn type_id() {
-        let actual = super::type_id(&small_example_packet());
-        let expected = 4;
-        assert_eq!(actual, expected);
-    }
-
-    fn small_example_packet() -> Packet {
-        packet("D2FE28")
+    fn invalid() {
+        for invalid in ["", "D2FE", "D2FE2G", "D2FE2801"] {
+            let parsed = invalid.parse::<Packet>();
+            assert!(
+                parsed.is_err(),
+                "transmission '{invalid}' should be invalid"
+            );
+        }
     }
 }
@@ -1,16 +1,22 @@
-use std::fmt::Display;
+use std::{
+    fmt::{self, Display},
+    ops::Add,
+    str::FromStr,
+};
 
-use easy_cast::Cast;
+use anyhow::{anyhow, bail};
 use itertools::Itertools;
 
+/// A number of the snailfish: either a regular number or a pair of snailfish numbers. Adding two
+/// snailfish numbers reduces the sum by exploding and splitting until neither applies.
 #[derive(Debug, Clone, PartialEq, Eq)]
-enum SnailfishNumber {
+pub enum SnailfishNumber {
     RegularNumber(RegularNumber),
     Pair(Box<SnailfishNumber>, Box<SnailfishNumber>),
 }
 
 impl Display for SnailfishNumber {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             SnailfishNumber::RegularNumber(regular_number) => write!(f, "{regular_number}"),
             SnailfishNumber::Pair(left, right) => {
@@ -20,8 +26,46 @@ impl Display for SnailfishNumber {
     }
 }
 
-type RegularNumber = usize;
-type Magnitude = usize;
+impl FromStr for SnailfishNumber {
+    type Err = anyhow::Error;
+
+    /// Parses a snailfish number like `[[1,2],3]`. The number is not reduced, but it may nest at
+    /// most four pairs, as reduced numbers do, and its regular numbers may be at most
+    /// [`MAXIMUM_REGULAR_NUMBER`], so that neither adding nor the magnitude overflows.
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        let (remaining, snailfish_number) = snailfish_number(str.as_bytes(), 0)?;
+        if !remaining.is_empty() {
+            bail!("snailfish number '{str}' should end after its outermost pair");
+        }
+        Ok(snailfish_number)
+    }
+}
+
+impl Add for SnailfishNumber {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        add(self, other)
+    }
+}
+
+impl SnailfishNumber {
+    /// Three times the magnitude of the left element of a pair plus twice that of the right.
+    #[must_use]
+    pub fn magnitude(&self) -> Magnitude {
+        magnitude(self)
+    }
+}
+
+pub type RegularNumber = usize;
+pub type Magnitude = usize;
+
+/// The largest regular number that a parsed snailfish number may have. In a magnitude, the
+/// coefficients of the regular numbers of at most four nested pairs sum to at most `5^4`.
+pub const MAXIMUM_REGULAR_NUMBER: RegularNumber = RegularNumber::MAX / 625;
+
+/// How many pairs a parsed snailfish number may nest, beyond which a pair would explode.
+const MAXIMUM_DEPTH: usize = 4;
 
 pub fn first_answer(input: &str) -> String {
     let snailfish_numbers = snailfish_numbers(input);
@@ -171,24 +215,43 @@ fn half_ceil(number: RegularNumber) -> RegularNumber {
 fn snailfish_numbers(input: &str) -> impl Iterator<Item = SnailfishNumber> + '_ {
     input
         .lines()
-        .map(|line| snailfish_number(line.as_bytes()).1)
+        .map(|line| line.parse().expect("snailfish number should be valid"))
 }
 
-fn snailfish_number(bytes: &[u8]) -> (&[u8], SnailfishNumber) {
-    match bytes[0] {
-        b'[' => {
-            let (remaining, left) = snailfish_number(&bytes[1..]);
-            debug_assert_eq!(remaining[0], b',', "pair should be separated by ','");
-            let (remaining, right) = snailfish_number(&remaining[1..]);
-            debug_assert_eq!(remaining[0], b']', "pair should end with ']'");
+fn snailfish_number(bytes: &[u8], depth: usize) -> anyhow::Result<(&[u8], SnailfishNumber)> {
+    match bytes.first() {
+        Some(b'[') => {
+            if depth == MAXIMUM_DEPTH {
+                bail!("snailfish number should nest at most {MAXIMUM_DEPTH} pairs");
+            }
+            let (remaining, left) = snailfish_number(&bytes[1..], depth + 1)?;
+            let Some((b',', remaining)) = remaining.split_first() else {
+                bail!("pair should be separated by ','");
+            };
+            let (remaining, right) = snailfish_number(remaining, depth + 1)?;
+            let Some((b']', remaining)) = remaining.split_first() else {
+                bail!("pair should end with ']'");
+            };
             let pair = SnailfishNumber::Pair(Box::new(left), Box::new(right));
-            (&remaining[1..], pair)
+            Ok((remaining, pair))
         }
-        b'0'..=b'9' => {
-            let regular_number = (bytes[0] - b'0').cast();
-            (&bytes[1..], SnailfishNumber::RegularNumber(regular_number))
+        Some(b'0'..=b'9') => {
+            let number_of_digits = bytes
+                .iter()
+                .take_while(|byte| byte.is_ascii_digit())
+                .count();
+            let (digits, remaining) = bytes.split_at(number_of_digits);
+            let regular_number = str::from_utf8(digits)
+                .expect("digits should be ascii")
+                .parse()
+                .ok()
+                .filter(|&regular_number| regular_number <= MAXIMUM_REGULAR_NUMBER)
+                .ok_or_else(|| {
+                    anyhow!("regular number should be at most {MAXIMUM_REGULAR_NUMBER}")
+                })?;
+            Ok((remaining, SnailfishNumber::RegularNumber(regular_number)))
         }
-        _ => panic!("first character in pair should be '[' or digit"),
+        _ => Err(anyhow!("snailfish number should start with '[' or a digit")),
     }
 }
 
@@ -241,11 +304,50 @@ mod tests {
 
     #[test]
     fn reduced() {
-        let (remaining, snailfish_number) =
-            snailfish_number(b"[[[[[4,3],4],4],[7,[[8,4],9]]],[1,1]]");
-        assert!(remaining.is_empty(), "entire input should be parsed");
-        let actual = super::reduced(snailfish_number);
+        let [left, right] = ["[[[[4,3],4],4],[7,[[8,4],9]]]", "[1,1]"].map(|str| {
+            str.parse::<SnailfishNumber>()
+                .expect("snailfish number should be valid")
+        });
+        let actual = super::reduced(SnailfishNumber::Pair(Box::new(left), Box::new(right)));
         let expected = "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]";
         assert_eq!(actual.to_string(), expected);
     }
+
+    #[test]
+    fn snailfish_number() {
+        let [left, right] = ["[[[[4,3],4],4],[7,[[8,4],9]]]", "[1,1]"].map(|str| {
+            str.parse::<SnailfishNumber>()
+                .expect("snailfish number should be valid")
+        });
+        let sum = left + right;
+        assert_eq!(sum.to_string(), "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]");
+        assert_eq!(sum.magnitude(), 1384);
+        let mut largest = MAXIMUM_REGULAR_NUMBER.to_string();
+        for _ in 0..4 {
+            largest = format!("[{largest},{largest}]");
+        }
+        let magnitude = largest
+            .parse::<SnailfishNumber>()
+            .map(|snailfish_number| snailfish_number.magnitude());
+        assert_eq!(magnitude.ok(), Some(625 * MAXIMUM_REGULAR_NUMBER));
+        let too_large = format!("[{},1]", MAXIMUM_REGULAR_NUMBER + 1);
+        let too_deep = "[[[[[1,2],3],4],5],6]";
+        let overflowing = "[18446744073709551615,1]";
+        for invalid in [
+            "",
+            "[1,2",
+            "[1;2]",
+            "[1,2]]",
+            "[x,2]",
+            &too_large,
+            too_deep,
+            overflowing,
+        ] {
+            let parsed = invalid.parse::<SnailfishNumber>();
+            assert!(
+                parsed.is_err(),
+                "snailfish number '{invalid}' should be invalid"
+            );
+        }
+    }
 }
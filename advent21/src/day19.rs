@@ -1,22 +1,17 @@
-use std::sync::LazyLock;
-
 use ahash::AHashSet;
 use itertools::Itertools;
 use shared::{
-    matrix::{
-        self, Matrix, quarter_rotation_around_x_axis, quarter_rotation_around_y_axis,
-        quarter_rotation_around_z_axis,
-    },
+    geometry::{self, CubeRotation},
     string::isizes,
     vector::{ManhattanDistance, Vector},
 };
 
-type Scanner = AHashSet<Beacon>;
+type Scanner = Vec<Beacon>;
 type Beacon = Position;
 type Position = [Coordinate; 3];
 type Coordinate = isize;
 
-type Rotation = Matrix<Coordinate, 3, 3>;
+type Rotation = CubeRotation;
 
 const NUMBER_OF_BEACONS_IN_OVERLAP: usize = 12;
 
@@ -53,7 +48,7 @@ fn pin_scanners(scanners: &mut [Scanner], scanner_positions: &mut [Option<Positi
         if let Some((rotation, position)) = overlap(&scanners[pinned], &scanners[unpinned]) {
             let now_pinned_scanner = scanners[unpinned]
                 .iter()
-                .map(|&right_beacon| matrix::vector_mul(rotation, right_beacon).add(position))
+                .map(|&right_beacon| rotation.apply(right_beacon).add(position))
                 .collect();
 
             scanners[unpinned] = now_pinned_scanner;
@@ -65,58 +60,7 @@ fn pin_scanners(scanners: &mut [Scanner], scanner_positions: &mut [Option<Positi
 }
 
 fn overlap(left_scanner: &Scanner, right_scanner: &Scanner) -> Option<(Rotation, Position)> {
-    for rotation in all_rotations() {
-        let right_scanner_rotated = right_scanner
-            .iter()
-            .map(|&right_beacon| matrix::vector_mul(rotation, right_beacon))
-            .collect_vec();
-
-        let number_of_duplicates_if_overlap = NUMBER_OF_BEACONS_IN_OVERLAP - 1;
-        let translations = left_scanner
-            .iter()
-            .skip(number_of_duplicates_if_overlap)
-            .cartesian_product(&right_scanner_rotated[number_of_duplicates_if_overlap..])
-            .map(|(&left, &right)| left.sub(right));
-        for translation in translations {
-            let right_scanner_rotated_translated = right_scanner_rotated
-                .iter()
-                .map(|&beacon| beacon.add(translation));
-
-            let mut number_of_matches = 0;
-            for right_beacon_rotated_translated in right_scanner_rotated_translated {
-                if left_scanner.contains(&right_beacon_rotated_translated) {
-                    number_of_matches += 1;
-                    if number_of_matches == NUMBER_OF_BEACONS_IN_OVERLAP {
-                        return Some((rotation, translation));
-                    }
-                }
-            }
-        }
-    }
-    None
-}
-
-fn all_rotations() -> [Rotation; 24] {
-    static ALL_ROTATIONS: LazyLock<[Matrix<Coordinate, 3, 3>; 24]> = LazyLock::new(|| {
-        let mut all_rotations = AHashSet::new();
-        let mut rotation = matrix::identity();
-        for _ in 0..4 {
-            for _ in 0..4 {
-                for _ in 0..4 {
-                    all_rotations.insert(rotation);
-                    rotation = matrix::matrix_mul(rotation, quarter_rotation_around_z_axis());
-                }
-                rotation = matrix::matrix_mul(rotation, quarter_rotation_around_y_axis());
-            }
-            rotation = matrix::matrix_mul(rotation, quarter_rotation_around_x_axis());
-        }
-        all_rotations
-            .into_iter()
-            .collect_vec()
-            .try_into()
-            .expect("exactly 24 rotations should exist")
-    });
-    *ALL_ROTATIONS
+    geometry::register(left_scanner, right_scanner, NUMBER_OF_BEACONS_IN_OVERLAP)
 }
 
 fn maximum_manhattan_distance(positions: &[Position]) -> Coordinate {
@@ -157,7 +101,6 @@ mod tests {
     }
 
     #[test]
-    #[ignore = "this takes several minutes"]
     fn first_answer_input() {
         test_on_input(DAY, Puzzle::First, Input::PuzzleInput, 449);
     }
@@ -168,7 +111,6 @@ mod tests {
     }
 
     #[test]
-    #[ignore = "this takes several minutes"]
     fn second_answer_input() {
         test_on_input(DAY, Puzzle::Second, Input::PuzzleInput, 13128);
     }
@@ -190,7 +132,8 @@ mod tests {
     #[test]
     fn overlap() {
         let scanners = scanners(&input(DAY, Input::Example(0)));
-        let actual = super::overlap(&scanners[0], &scanners[1]);
+        let actual = super::overlap(&scanners[0], &scanners[1])
+            .map(|(rotation, position)| (rotation.matrix(), position));
         let expected = Some(([[-1, 0, 0], [0, 1, 0], [0, 0, -1]], [68, -1246, -43]));
         assert_eq!(actual, expected);
     }
@@ -198,8 +141,8 @@ mod tests {
     #[test]
     fn rotations() {
         let scanner = ORIENTATIONS[0];
-        let all_orientations = all_rotations()
-            .map(|rotation| scanner.map(|beacon| matrix::vector_mul(rotation, beacon)));
+        let all_orientations =
+            CubeRotation::all().map(|rotation| scanner.map(|beacon| rotation.apply(beacon)));
         for orientation in ORIENTATIONS {
             assert!(all_orientations.contains(&orientation));
         }
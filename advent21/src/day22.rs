@@ -1,17 +1,15 @@
-use std::{array, ops::Range};
+use std::ops::Range;
 
+use easy_cast::Conv;
 use itertools::Itertools;
-use shared::string::isizes;
+use rand::{RngExt, rngs::SmallRng};
+use shared::{interval::BoxSet, string::isizes};
 
 type RebootStep = (Switch, Cuboid);
 type Switch = bool;
 type Cuboid = [Range<Coordinate>; 3];
 type Coordinate = isize;
-
-type Direction = usize;
-const X: Direction = 0;
-const Y: Direction = 1;
-const Z: Direction = 2;
+type Reactor = BoxSet<Coordinate, 3>;
 
 pub fn first_answer(input: &str) -> String {
     let initialization_procedure = initialization_procedure(input);
@@ -25,96 +23,41 @@ pub fn second_answer(input: &str) -> String {
     number_of_cubes(&cuboids).to_string()
 }
 
-fn reboot(reboot_steps: impl Iterator<Item = RebootStep>) -> Vec<Cuboid> {
-    let mut cuboids = vec![];
+/// Up to `size` reboot steps with coordinates between `-size` and `size`, so that for sizes up
+/// to 50 every step is within the initialization area.
+pub fn generate(rng: &mut SmallRng, size: usize) -> String {
+    let size = size.max(1);
+    let limit = i32::conv(size);
+    (0..rng.random_range(1..=size))
+        .map(|_| {
+            let switch = if rng.random_bool(0.5) { "on" } else { "off" };
+            let ranges = ["x", "y", "z"].map(|axis| {
+                let bounds = [(); 2].map(|()| rng.random_range(-limit..=limit));
+                format!(
+                    "{axis}={}..{}",
+                    bounds[0].min(bounds[1]),
+                    bounds[0].max(bounds[1])
+                )
+            });
+            format!("{switch} {}\n", ranges.join(","))
+        })
+        .join("")
+}
+
+fn reboot(reboot_steps: impl Iterator<Item = RebootStep>) -> Reactor {
+    let mut reactor = Reactor::new();
     for (switch, cuboid) in reboot_steps {
-        cuboids = cuboids
-            .into_iter()
-            .flat_map(|existing| cuboid_difference(&existing, &cuboid))
-            .collect_vec();
         if switch {
-            cuboids.push(cuboid);
-        }
-    }
-    cuboids
-}
-
-fn cuboid_difference(lhs: &Cuboid, rhs: &Cuboid) -> Vec<Cuboid> {
-    if !cuboids_intersect(lhs, rhs) {
-        return vec![lhs.clone()];
-    }
-
-    let mut lhs = lhs.clone();
-    let mut difference = vec![];
-    for direction in [X, Y, Z] {
-        let (range_difference, range_intersection) =
-            range_difference(&lhs[direction], &rhs[direction]);
-        debug_assert!(!range_intersection.is_empty(), "cuboids should intersect");
-
-        let differences = range_difference
-            .into_iter()
-            .map(|range| cuboid_with(&lhs, &range, direction));
-        difference.extend(differences);
-
-        lhs = cuboid_with(&lhs, &range_intersection, direction);
-    }
-    difference
-}
-
-fn cuboids_intersect(left: &Cuboid, right: &Cuboid) -> bool {
-    let x_ranges_intersect = ranges_intersect(&left[X], &right[X]);
-    let y_ranges_intersect = ranges_intersect(&left[Y], &right[Y]);
-    let z_ranges_intersect = ranges_intersect(&left[Z], &right[Z]);
-    x_ranges_intersect && y_ranges_intersect && z_ranges_intersect
-}
-
-fn ranges_intersect(left: &Range<Coordinate>, right: &Range<Coordinate>) -> bool {
-    left.start < right.end && right.start < left.end
-}
-
-fn range_difference(
-    lhs: &Range<Coordinate>,
-    rhs: &Range<Coordinate>,
-) -> (Vec<Range<Coordinate>>, Range<Coordinate>) {
-    if lhs.start < rhs.start && rhs.end < lhs.end {
-        let remaining = vec![lhs.start..rhs.start, rhs.end..lhs.end];
-        let subtracted = rhs.clone();
-        (remaining, subtracted)
-    } else if lhs.start < rhs.start && lhs.end <= rhs.end {
-        #[expect(
-            clippy::single_range_in_vec_init,
-            reason = "difference is one contiguous range"
-        )]
-        (vec![lhs.start..rhs.start], rhs.start..lhs.end)
-    } else if rhs.start <= lhs.start && rhs.end < lhs.end {
-        #[expect(
-            clippy::single_range_in_vec_init,
-            reason = "difference is one contiguous range"
-        )]
-        (vec![rhs.end..lhs.end], lhs.start..rhs.end)
-    } else if rhs.start <= lhs.start && lhs.end <= rhs.end {
-        (vec![], lhs.clone())
-    } else {
-        panic!("ranges should intersect");
-    }
-}
-
-fn cuboid_with(cuboid: &Cuboid, range: &Range<Coordinate>, direction: Direction) -> Cuboid {
-    array::from_fn(|index| {
-        if index == direction {
-            range.clone()
+            reactor.insert(cuboid);
         } else {
-            cuboid[index].clone()
+            reactor.remove(&cuboid);
         }
-    })
-}
-
-fn number_of_cubes(cuboids: &[Cuboid]) -> usize {
-    cuboids.iter().map(volume).sum()
+    }
+    reactor
 }
 
-fn volume(cuboid: &Cuboid) -> usize {
-    cuboid.iter().map(ExactSizeIterator::len).product()
+fn number_of_cubes(reactor: &Reactor) -> usize {
+    usize::conv(reactor.volume())
 }
 
 fn initialization_procedure(input: &str) -> impl Iterator<Item = RebootStep> + '_ {
@@ -161,8 +104,7 @@ fn cuboid(str: &str) -> Cuboid {
 
 #[cfg(test)]
 mod tests {
-    use std::ops::Range;
-
+    use ahash::AHashSet;
     use infrastructure::{Input, Puzzle, test};
 
     use super::*;
@@ -170,6 +112,26 @@ mod tests {
 
     const DAY: usize = 22;
 
+    fn number_of_cubes_cube_by_cube(input: &str) -> String {
+        let mut reactor = AHashSet::new();
+        for (switch, cuboid) in reboot_steps(input) {
+            for cube in cuboid.into_iter().multi_cartesian_product() {
+                if switch {
+                    reactor.insert(cube);
+                } else {
+                    reactor.remove(&cube);
+                }
+            }
+        }
+        reactor.len().to_string()
+    }
+
+    #[test]
+    fn answers_generated() {
+        let solutions = [first_answer, second_answer, number_of_cubes_cube_by_cube];
+        test::agree_on_generated_inputs(&generate, 6, 1000, &solutions);
+    }
+
     #[test]
     fn first_examples() {
         test_on_input(DAY, Puzzle::First, Input::Example(0), 39);
@@ -201,75 +163,6 @@ mod tests {
         );
     }
 
-    #[test]
-    fn cuboid_difference() {
-        let lhs = [5..15, 5..15, 5..15];
-        let rhs = [0..20, 7..11, 1..8];
-        let difference = super::cuboid_difference(&lhs, &rhs);
-        let actual_number_of_cubes = number_of_cubes(&difference);
-        let expected_number_of_cubes = 1000 - 10 * 4 * 3;
-        assert_eq!(actual_number_of_cubes, expected_number_of_cubes);
-    }
-
-    #[test]
-    fn range_difference() {
-        let sample = 5..15;
-        let function = |rhs| super::range_difference(&sample, &rhs);
-        #[expect(
-            clippy::single_range_in_vec_init,
-            reason = "a list of ranges is desired"
-        )]
-        let cases = [
-            (6..14, (vec![5..6, 14..15], 6..14)),
-            (1..14, (vec![14..15], 5..14)),
-            (14..22, (vec![5..14], 14..15)),
-            (4..16, (vec![], 5..15)),
-            (5..15, (vec![], 5..15)),
-        ];
-        test::cases(function, cases);
-    }
-
-    #[test]
-    fn cuboid_with() {
-        let sample = [5..15, 5..15, 5..15];
-        let function = |(range, direction)| super::cuboid_with(&sample, &range, direction);
-        let cases = [
-            ((7..9, X), [7..9, 5..15, 5..15]),
-            ((11..15, Y), [5..15, 11..15, 5..15]),
-            ((5..15, Z), [5..15, 5..15, 5..15]),
-        ];
-        test::cases(function, cases);
-    }
-
-    #[test]
-    fn cuboids_intersect() {
-        let function = |[left, right]: [Cuboid; 2]| super::cuboids_intersect(&left, &right);
-        let cases = [
-            ([[2..4, 2..4, 2..4], [2..4, 2..4, 2..4]], true),
-            ([[1..3, 1..3, 1..3], [2..4, 2..4, 2..4]], true),
-            ([[2..4, 2..4, 2..4], [4..6, 2..4, 2..4]], false),
-            ([[2..4, 2..4, 2..4], [2..4, 4..6, 2..4]], false),
-            ([[2..4, 2..4, 2..4], [2..4, 2..4, 4..6]], false),
-        ];
-        test::cases(function, cases);
-    }
-
-    #[test]
-    fn ranges_intersect() {
-        let function =
-            |[left, right]: [Range<Coordinate>; 2]| super::ranges_intersect(&left, &right);
-        let cases = [
-            ([2..4, 4..6], false),
-            ([5..6, 2..5], false),
-            ([2..4, 3..7], true),
-            ([2..4, 0..3], true),
-            ([7..13, 7..13], true),
-            ([7..18, 7..13], true),
-            ([7..13, 7..14], true),
-        ];
-        test::cases(function, cases);
-    }
-
     #[test]
     fn is_within_initialization_area() {
         let function = |cuboid| super::is_within_initialization_area(&cuboid);
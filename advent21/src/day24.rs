@@ -1,3 +1,49 @@
+use std::collections::VecDeque;
+
+use itertools::Itertools;
+use shared::vm::{self, Flow, Machine};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Instruction {
+    Inp(Register),
+    Add(Register, Operand),
+    Mul(Register, Operand),
+    Div(Register, Operand),
+    Mod(Register, Operand),
+    Eql(Register, Operand),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operand {
+    Register(Register),
+    Number(Number),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Parameters {
+    divisor: Number,
+    shift: Number,
+    addition: Number,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Extreme {
+    Largest,
+    Smallest,
+}
+
+type Register = usize;
+type Registers = [Number; 4];
+type Number = isize;
+type Digit = Number;
+
+const W: Register = 0;
+const X: Register = 1;
+const Y: Register = 2;
+const Z: Register = 3;
+
+const ITERATION_LENGTH: usize = 18;
+
 /// Returns the answer to the first puzzle of day 24.
 ///
 /// # Correctness
@@ -32,56 +78,260 @@
 /// A model number is valid if `z == 0` after the MONAD has finished. That is, the stack must be
 /// empty or all integers on the stack must be `0`.
 ///
-/// Below, the values of the parameters are listed. Notice that `divisor` is `26` seven times, which
-/// results in seven pops from the stack. During the other seven iterations, `shift >= 10`, meaning
-/// `top_shifted != digit` because `digit < 10`. Thus, we push `digit + addition` onto the stack
-/// during these iterations. Because it's always the case that `addition > 0`, we never push zeros
-/// onto the stack and thus, the stack must end up empty in order to accept the model number.
-///
-/// | iteration  |  0 |  1 |  2 |  3 |  4 |   5 |  6 |  7 |   8 |  9 |  10 | 11 |  12 | 13 |
-/// |:-----------|---:|---:|---:|---:|---:|----:|---:|---:|----:|---:|----:|---:|----:|---:|
-/// | `divisor`  |  1 |  1 |  1 |  1 |  1 |  26 | 26 |  1 |  26 |  1 |  26 | 26 |  26 | 26 |
-/// | `shift`    | 10 | 10 | 14 | 11 | 14 | -14 |  0 | 10 | -10 | 13 | -12 | -3 | -11 | -2 |
-/// | `addition` |  2 |  4 |  8 |  7 | 12 |   7 | 10 | 14 |   2 |  6 |   8 | 11 |   5 | 11 |
+/// The parameters are extracted from the MONAD by [`parameters`]. In every MONAD, `divisor` is
+/// `26` seven times, which results in seven pops from the stack. During the other seven
+/// iterations, `shift >= 10`, meaning `top_shifted != digit` because `digit < 10`. Thus, we push
+/// `digit + addition` onto the stack during these iterations. Because it's always the case that
+/// `addition > 0`, we never push zeros onto the stack and thus, the stack must end up empty in
+/// order to accept the model number.
 ///
 /// The seven unavoidable pushes and the seven unavoidable pops yield an empty stack, if no other
 /// pushes occur. That is, we must have `top_shifted == digit` during all iterations where
-/// `divisor == 26`.
-///
-/// Let's consider iteration 13, which should handle the number `digit[0] + additon[0]` pushed
-/// during iteration 0: We have `top_shifted[13] == digit[0] + additon[0] + shift[13]`, which
-/// reduces to `top_shifted[13] == digit[0]` because `additon[0] == -shift[13]`. In order to avoid a
-/// stack push, we must have `top_shifted[13] == digit[13]`, which is equivalent to
-/// `digit[0] == digit[13]`. Thus, as long as the first digit and the last digit of the model number
-/// are identical, we avoid a push during iteration 13. Since we are interested in the largest
-/// accepted model number, we choose `digit[0] == digit[13] == 9`.
-///
-/// Now, let's consider iteration 12, which should handle the number `digit[1] + addition[1]` pushed
-/// during iteration 1: We have `top_shifted[12] == digit[1] + addition[1] + shift[12]`, which
-/// implies `digit[12] == digit[1] - 7`. Since we are looking for the largest accepted model number,
-/// we choose `digit[1] == 9` and `digit[12] == 2`.
-///
-/// If we perform this analysis for every iteration, we find that the largest accepted model number
-/// is `99_429_795_993_929`.
-pub fn first_answer(_: &str) -> String {
-    99_429_795_993_929_isize.to_string()
+/// `divisor == 26`. If iteration `pop` pops the integer pushed during iteration `push`, this is
+/// the case when `digit[pop] == digit[push] + addition[push] + shift[pop]`. Every such pair of
+/// digits can be chosen independently of the other pairs, so we choose the largest pair that
+/// satisfies its constraint, which is done by [`model_number`].
+pub fn first_answer(input: &str) -> String {
+    accepted_model_number(&instructions(input), Extreme::Largest)
+        .expect("program should be a MONAD accepting some model number")
+        .to_string()
 }
 
-/// Returns the answer to the first puzzle of day 24.
+/// Returns the answer to the second puzzle of day 24.
 ///
 /// # Correctness
 ///
-/// An analysis similar to that of [`first`] yields that the smallest accepted model number is
-/// `18_113_181_571_611`.
-pub fn second_answer(_: &str) -> String {
-    18_113_181_571_611_isize.to_string()
+/// The analysis of [`first_answer`] applies, except that we choose the smallest pair of digits
+/// satisfying each constraint.
+pub fn second_answer(input: &str) -> String {
+    accepted_model_number(&instructions(input), Extreme::Smallest)
+        .expect("program should be a MONAD accepting some model number")
+        .to_string()
+}
+
+/// Returns the largest or smallest model number accepted by the MONAD, or `None` if the program
+/// is not a MONAD or accepts no model number. The model number found by analyzing the parameters
+/// is confirmed by running the MONAD on the ALU.
+fn accepted_model_number(instructions: &[Instruction], extreme: Extreme) -> Option<Number> {
+    let parameters = parameters(instructions)?;
+    model_number(&parameters, extreme).filter(|&number| is_accepted(instructions, number))
+}
+
+fn is_accepted(instructions: &[Instruction], model_number: Number) -> bool {
+    let digits = model_number
+        .to_string()
+        .bytes()
+        .map(|digit| Digit::from(digit - b'0'))
+        .collect_vec();
+    run(instructions, digits)[Z] == 0
+}
+
+/// Returns the largest or smallest model number accepted by a MONAD with the given parameters,
+/// or `None` if no model number is accepted.
+fn model_number(parameters: &[Parameters], extreme: Extreme) -> Option<Number> {
+    let mut digits = vec![0; parameters.len()];
+    let mut stack = vec![];
+    for (pop, &Parameters { divisor, shift, .. }) in parameters.iter().enumerate() {
+        if divisor == 1 {
+            stack.push(pop);
+            continue;
+        }
+        let push = stack.pop()?;
+        let difference = parameters[push].addition + shift;
+        let [push_digit, pop_digit] = digit_pair(difference, extreme)?;
+        [digits[push], digits[pop]] = [push_digit, pop_digit];
+    }
+    stack.is_empty().then(|| {
+        digits
+            .into_iter()
+            .fold(0, |number, digit| 10 * number + digit)
+    })
+}
+
+/// The largest or smallest digits `[push, pop]` such that `pop == push + difference`.
+fn digit_pair(difference: Number, extreme: Extreme) -> Option<[Digit; 2]> {
+    if difference.abs() > 8 {
+        return None;
+    }
+    let push = match extreme {
+        Extreme::Largest => 9 - difference.max(0),
+        Extreme::Smallest => 1 - difference.min(0),
+    };
+    Some([push, push + difference])
+}
+
+/// Extracts the parameters of every iteration of a MONAD, or returns `None` if the program does
+/// not consist of iterations of the routine described in [`first_answer`].
+fn parameters(instructions: &[Instruction]) -> Option<Vec<Parameters>> {
+    instructions
+        .chunks(ITERATION_LENGTH)
+        .map(|chunk| {
+            let parameters = Parameters {
+                divisor: number(chunk.get(4)?)?,
+                shift: number(chunk.get(5)?)?,
+                addition: number(chunk.get(15)?)?,
+            };
+            let is_push = parameters.divisor == 1 && parameters.shift >= 10;
+            let is_pop = parameters.divisor == 26;
+            let is_valid = chunk == iteration(&parameters) && parameters.addition > 0;
+            (is_valid && (is_push || is_pop)).then_some(parameters)
+        })
+        .collect()
+}
+
+fn number(instruction: &Instruction) -> Option<Number> {
+    match instruction {
+        Instruction::Add(_, Operand::Number(number))
+        | Instruction::Div(_, Operand::Number(number)) => Some(*number),
+        Instruction::Inp(_)
+        | Instruction::Add(..)
+        | Instruction::Mul(..)
+        | Instruction::Div(..)
+        | Instruction::Mod(..)
+        | Instruction::Eql(..) => None,
+    }
+}
+
+/// The instructions of one iteration of the MONAD processing a single digit.
+fn iteration(
+    &Parameters {
+        divisor,
+        shift,
+        addition,
+    }: &Parameters,
+) -> [Instruction; ITERATION_LENGTH] {
+    use Instruction::{Add, Div, Eql, Inp, Mod, Mul};
+    use Operand::{Number, Register};
+    [
+        Inp(W),
+        Mul(X, Number(0)),
+        Add(X, Register(Z)),
+        Mod(X, Number(26)),
+        Div(Z, Number(divisor)),
+        Add(X, Number(shift)),
+        Eql(X, Register(W)),
+        Eql(X, Number(0)),
+        Mul(Y, Number(0)),
+        Add(Y, Number(25)),
+        Mul(Y, Register(X)),
+        Add(Y, Number(1)),
+        Mul(Z, Register(Y)),
+        Mul(Y, Number(0)),
+        Add(Y, Register(W)),
+        Add(Y, Number(addition)),
+        Mul(Y, Register(X)),
+        Add(Z, Register(Y)),
+    ]
+}
+
+/// The arithmetic logic unit executing a program.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Alu {
+    registers: Registers,
+    input: VecDeque<Number>,
+}
+
+impl vm::Instruction for Instruction {
+    type State = Alu;
+
+    /// # Panics
+    ///
+    /// Panics if the instruction reads exhausted input, divides by zero, or takes the remainder of
+    /// a negative number or by a non-positive number.
+    fn execute(&self, Alu { registers, input }: &mut Alu) -> Flow {
+        match *self {
+            Instruction::Inp(register) => {
+                registers[register] = input.pop_front().expect("input should not be exhausted");
+            }
+            Instruction::Add(register, operand) => {
+                registers[register] += value(registers, operand);
+            }
+            Instruction::Mul(register, operand) => {
+                registers[register] *= value(registers, operand);
+            }
+            Instruction::Div(register, operand) => {
+                let divisor = value(registers, operand);
+                assert_ne!(divisor, 0, "divisor should not be zero");
+                registers[register] /= divisor;
+            }
+            Instruction::Mod(register, operand) => {
+                let modulus = value(registers, operand);
+                assert!(
+                    registers[register] >= 0 && modulus > 0,
+                    "remainder should be of a non-negative number by a positive number"
+                );
+                registers[register] %= modulus;
+            }
+            Instruction::Eql(register, operand) => {
+                registers[register] = (registers[register] == value(registers, operand)).into();
+            }
+        }
+        Flow::Next
+    }
+}
+
+/// Runs the program on the ALU and returns the registers afterwards.
+fn run(instructions: &[Instruction], input: impl IntoIterator<Item = Number>) -> Registers {
+    let alu = Alu {
+        registers: [0; 4],
+        input: input.into_iter().collect(),
+    };
+    let mut machine = Machine::new(instructions, alu);
+    machine.run();
+    machine.into_state().registers
+}
+
+fn value(registers: &Registers, operand: Operand) -> Number {
+    match operand {
+        Operand::Register(register) => registers[register],
+        Operand::Number(number) => number,
+    }
+}
+
+fn instructions(input: &str) -> Vec<Instruction> {
+    input.lines().map(instruction).collect_vec()
+}
+
+fn instruction(line: &str) -> Instruction {
+    let mut parts = line.split_whitespace();
+    let operation = parts.next().expect("instruction should have an operation");
+    let register = register(parts.next().expect("instruction should have a register"));
+    let mut operand = || {
+        let operand = parts
+            .next()
+            .expect("instruction should have a second operand");
+        match operand {
+            "w" | "x" | "y" | "z" => Operand::Register(self::register(operand)),
+            _ => Operand::Number(operand.parse().expect("operand should be a number")),
+        }
+    };
+    match operation {
+        "inp" => Instruction::Inp(register),
+        "add" => Instruction::Add(register, operand()),
+        "mul" => Instruction::Mul(register, operand()),
+        "div" => Instruction::Div(register, operand()),
+        "mod" => Instruction::Mod(register, operand()),
+        "eql" => Instruction::Eql(register, operand()),
+        _ => panic!("operation should be 'inp', 'add', 'mul', 'div', 'mod', or 'eql'"),
+    }
+}
+
+fn register(name: &str) -> Register {
+    match name {
+        "w" => W,
+        "x" => X,
+        "y" => Y,
+        "z" => Z,
+        _ => panic!("register should be 'w', 'x', 'y', or 'z'"),
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use infrastructure::{Input, Puzzle};
+    use infrastructure::{Input, Puzzle, test};
 
-    use crate::tests::test_on_input;
+    use super::*;
+    use crate::tests::{input, test_on_input};
 
     const DAY: usize = 24;
 
@@ -104,4 +354,103 @@ mod tests {
             18_113_181_571_611_isize,
         );
     }
+
+    #[test]
+    fn run_negation() {
+        let instructions = instructions("inp x\nmul x -1\n");
+        assert_eq!(run(&instructions, [7]), [0, -7, 0, 0]);
+    }
+
+    #[test]
+    fn run_three_times_larger() {
+        let instructions = instructions("inp z\ninp x\nmul z 3\neql z x\n");
+        let function = |input: [Number; 2]| run(&instructions, input)[Z];
+        let cases = [([2, 6], 1), ([2, 5], 0), ([-3, -9], 1)];
+        test::cases(function, cases);
+    }
+
+    #[test]
+    fn run_binary() {
+        let program = "\
+            inp w\nadd z w\nmod z 2\ndiv w 2\nadd y w\nmod y 2\ndiv w 2\n\
+            add x w\nmod x 2\ndiv w 2\nmod w 2\n\
+        ";
+        let instructions = instructions(program);
+        assert_eq!(run(&instructions, [11]), [1, 0, 1, 1]);
+        assert_eq!(run(&instructions, [6]), [0, 1, 1, 0]);
+    }
+
+    #[test]
+    fn parameters_of_input() {
+        let parameters = parameters(&instructions(&input(DAY, Input::PuzzleInput)))
+            .expect("program should be a MONAD");
+        let divisors = parameters.iter().map(|p| p.divisor).collect_vec();
+        let shifts = parameters.iter().map(|p| p.shift).collect_vec();
+        let additions = parameters.iter().map(|p| p.addition).collect_vec();
+        assert_eq!(divisors, [1, 1, 1, 1, 1, 26, 26, 1, 26, 1, 26, 26, 26, 26]);
+        assert_eq!(
+            shifts,
+            [10, 10, 14, 11, 14, -14, 0, 10, -10, 13, -12, -3, -11, -2]
+        );
+        assert_eq!(additions, [2, 4, 8, 7, 12, 7, 10, 14, 2, 6, 8, 11, 5, 11]);
+    }
+
+    #[test]
+    fn parameters_of_other_program() {
+        assert_eq!(parameters(&instructions("inp x\nmul x -1\n")), None);
+    }
+
+    #[test]
+    fn model_numbers_are_accepted() {
+        let instructions = instructions(&input(DAY, Input::PuzzleInput));
+        let parameters = parameters(&instructions).expect("program should be a MONAD");
+        for extreme in [Extreme::Largest, Extreme::Smallest] {
+            let model_number =
+                model_number(&parameters, extreme).expect("MONAD should accept a model number");
+            assert!(
+                is_accepted(&instructions, model_number),
+                "{model_number} should be accepted"
+            );
+        }
+    }
+
+    #[test]
+    fn model_number_of_generated_monad() {
+        let parameters =
+            [(1, 12, 4), (1, 11, 3), (26, -5, 2), (26, -1, 1)].map(|(divisor, shift, addition)| {
+                Parameters {
+                    divisor,
+                    shift,
+                    addition,
+                }
+            });
+        let instructions = parameters.iter().flat_map(iteration).collect_vec();
+        assert_eq!(super::parameters(&instructions), Some(parameters.to_vec()));
+        let function = |extreme| model_number(&parameters, extreme);
+        let cases = [
+            (Extreme::Largest, Some(6_979)),
+            (Extreme::Smallest, Some(1_314)),
+        ];
+        test::cases(function, cases);
+        for model_number in [6_979, 1_314] {
+            assert!(
+                is_accepted(&instructions, model_number),
+                "{model_number} should be accepted"
+            );
+        }
+        assert!(
+            !is_accepted(&instructions, 6_989),
+            "6989 should not be accepted"
+        );
+    }
+
+    #[test]
+    fn unsatisfiable_monad() {
+        let parameters = [(1, 12, 9), (26, 0, 1)].map(|(divisor, shift, addition)| Parameters {
+            divisor,
+            shift,
+            addition,
+        });
+        assert_eq!(model_number(&parameters, Extreme::Largest), None);
+    }
 }
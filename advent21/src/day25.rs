@@ -1,8 +1,4 @@
-use easy_cast::Conv;
-use shared::{
-    grid::{Coordinate, Direction, Grid, Position},
-    vector::Vector,
-};
+use shared::grid::{Direction, Grid, Position, Topology, Torus};
 
 type Seafloor = Grid<Cucumber>;
 type Cucumber = u8;
@@ -55,14 +51,9 @@ fn step_kind(seafloor: &Seafloor, kind: Cucumber) -> (Seafloor, bool) {
 }
 
 fn next_position(seafloor: &Seafloor, position: Position, direction: Direction) -> Position {
-    let mut next_position = position.add(direction);
-    let is_outside_grid = seafloor.get(next_position).is_none();
-    if is_outside_grid {
-        next_position = [
-            next_position[0] % Coordinate::conv(seafloor.height()),
-            next_position[1] % Coordinate::conv(seafloor.width()),
-        ];
-    }
+    let (next_position, _) = Torus(seafloor.bounds())
+        .step(position, direction)
+        .expect("seafloor should have no edges");
     next_position
 }
 
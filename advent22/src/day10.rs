@@ -1,6 +1,16 @@
 use easy_cast::Conv;
+use itertools::Itertools;
 
-use shared::grid::Grid;
+use shared::{
+    grid::Grid,
+    vm::{self, Flow, Machine},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Instruction {
+    Noop,
+    Addx(Register),
+}
 
 type SignalStrength = isize;
 type Register = isize;
@@ -42,23 +52,37 @@ fn sum_of_signal_strengths(input: &str) -> SignalStrength {
     sum_of_signal_strengths
 }
 
-fn execute(input: &str, mut on_cycle: impl FnMut(Register, usize)) {
-    let mut register = 1;
-    let mut number_of_completed_cycles = 0;
-    for line in input.lines() {
-        let (next_register, execution_time): (Register, _) = match &line[0..4] {
-            "noop" => (register, 1),
-            "addx" => {
-                let operand: Register = line[5..].parse().expect("operand should be numerical");
-                (register + operand, 2)
-            }
-            _ => panic!("instruction shoul be 'noop' or 'addx'"),
-        };
-        for _ in 0..execution_time {
-            on_cycle(register, number_of_completed_cycles);
-            number_of_completed_cycles += 1;
+impl vm::Instruction for Instruction {
+    type State = Register;
+
+    fn cycles(&self) -> usize {
+        match self {
+            Instruction::Noop => 1,
+            Instruction::Addx(_) => 2,
+        }
+    }
+
+    fn execute(&self, register: &mut Register) -> Flow {
+        if let Instruction::Addx(operand) = self {
+            *register += operand;
         }
-        register = next_register;
+        Flow::Next
+    }
+}
+
+fn execute(input: &str, mut on_cycle: impl FnMut(Register, usize)) {
+    let instructions = input.lines().map(instruction).collect_vec();
+    let mut machine = Machine::new(&instructions, 1);
+    machine.run_with(|number_of_completed_cycles, &register| {
+        on_cycle(register, number_of_completed_cycles);
+    });
+}
+
+fn instruction(line: &str) -> Instruction {
+    match &line[0..4] {
+        "noop" => Instruction::Noop,
+        "addx" => Instruction::Addx(line[5..].parse().expect("operand should be numerical")),
+        _ => panic!("instruction should be 'noop' or 'addx'"),
     }
 }
 
@@ -1,15 +1,44 @@
-use core::panic;
-use std::cmp::Ordering;
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display},
+    str::FromStr,
+};
 
+use anyhow::{anyhow, bail};
 use itertools::Itertools;
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-enum Packet {
+/// A packet of the distress signal: either an integer or a list of packets. The order in which
+/// packets are right is given by [`compare`], not by a trait, because it deems an integer equal to
+/// the list of just that integer.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Packet {
     List(Vec<Packet>),
     Integer(Integer),
 }
 
-type Integer = u8;
+pub type Integer = u8;
+
+impl FromStr for Packet {
+    type Err = anyhow::Error;
+
+    /// Parses a packet like `[1,[2,[]]]`.
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        let (remaining, packet) = parse_packet(str.as_bytes())?;
+        if !remaining.is_empty() {
+            bail!("packet '{str}' should end after its outermost list or integer");
+        }
+        Ok(packet)
+    }
+}
+
+impl Display for Packet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Integer(integer) => write!(f, "{integer}"),
+            Self::List(list) => write!(f, "[{}]", list.iter().join(",")),
+        }
+    }
+}
 
 pub fn first_answer(input: &str) -> String {
     (1..)
@@ -42,7 +71,10 @@ fn divider_packet_indices<const NUM_DIVIDER_PACKETS: usize>(
     })
 }
 
-fn compare(left: &Packet, right: &Packet) -> Ordering {
+/// Compares the packets in the order in which they are right: Integers compare as numbers, lists
+/// compare lexicographically, and an integer compared to a list is first wrapped in a list.
+#[must_use]
+pub fn compare(left: &Packet, right: &Packet) -> Ordering {
     match [left, right] {
         [Packet::List(left), Packet::List(right)] => compare_lists(left, right),
         [Packet::List(left), Packet::Integer(right)] => {
@@ -87,60 +119,63 @@ fn all_packets(input: &str) -> impl Iterator<Item = Packet> + '_ {
 }
 
 fn packet(line: &str) -> Packet {
-    let (remaining, packet) = parse_packet(line.as_bytes());
-    debug_assert!(remaining.is_empty(), "entire line should be parsed");
-    packet
+    line.parse().expect("packet should be valid")
 }
 
-fn parse_packet(bytes: &[u8]) -> (&[u8], Packet) {
-    match bytes[0] {
-        b'[' => {
-            let (remaining, list) = parse_list(bytes);
-            (remaining, Packet::List(list))
+fn parse_packet(bytes: &[u8]) -> anyhow::Result<(&[u8], Packet)> {
+    match bytes.first() {
+        Some(b'[') => {
+            let (remaining, list) = parse_list(bytes)?;
+            Ok((remaining, Packet::List(list)))
         }
-        byte if byte.is_ascii_digit() => {
-            let (remaining, integer) = parse_integer(bytes);
-            (remaining, Packet::Integer(integer))
+        Some(byte) if byte.is_ascii_digit() => {
+            let (remaining, integer) = parse_integer(bytes)?;
+            Ok((remaining, Packet::Integer(integer)))
         }
-        _ => panic!("packet shoud start with '[' or a digit"),
+        _ => Err(anyhow!("packet should start with '[' or a digit")),
     }
 }
 
-fn parse_list(mut bytes: &[u8]) -> (&[u8], Vec<Packet>) {
+fn parse_list(mut bytes: &[u8]) -> anyhow::Result<(&[u8], Vec<Packet>)> {
     debug_assert_eq!(bytes[0], b'[', "first character of list should be '['");
-    if bytes[1] == b']' {
-        return (&bytes[2..], vec![]);
+    if bytes.get(1) == Some(&b']') {
+        return Ok((&bytes[2..], vec![]));
     }
 
     bytes = &bytes[1..];
     let mut elements = vec![];
     loop {
-        let (remaining, packet) = parse_packet(bytes);
+        let (remaining, packet) = parse_packet(bytes)?;
         bytes = remaining;
         elements.push(packet);
-        if bytes[0] != b',' {
+        if bytes.first() != Some(&b',') {
             break;
         }
         bytes = &bytes[1..];
     }
-    debug_assert_eq!(bytes[0], b']', "last character of list should be ']'");
-    (&bytes[1..], elements)
+    match bytes.split_first() {
+        Some((b']', remaining)) => Ok((remaining, elements)),
+        _ => Err(anyhow!("list should end with ']'")),
+    }
 }
 
-fn parse_integer(bytes: &[u8]) -> (&[u8], Integer) {
-    let (number_of_digits, integer) = bytes
+fn parse_integer(bytes: &[u8]) -> anyhow::Result<(&[u8], Integer)> {
+    let number_of_digits = bytes
         .iter()
         .take_while(|byte| byte.is_ascii_digit())
-        .fold((0, 0), |(index, integer), byte| {
-            (index + 1, integer * 10 + (byte - b'0'))
-        });
-    (&bytes[number_of_digits..], integer)
+        .count();
+    let digits = str::from_utf8(&bytes[..number_of_digits]).expect("digits should be ascii");
+    let integer = digits
+        .parse()
+        .map_err(|_| anyhow!("integer '{digits}' should be at most {}", Integer::MAX))?;
+    Ok((&bytes[number_of_digits..], integer))
 }
 
 #[cfg(test)]
 mod tests {
     use infrastructure::{Input, Puzzle};
 
+    use super::*;
     use crate::tests::test_on_input;
 
     const DAY: usize = 13;
@@ -164,4 +199,24 @@ mod tests {
     fn second_answer_input() {
         test_on_input(DAY, Puzzle::Second, Input::PuzzleInput, 21890);
     }
+
+    #[test]
+    fn packet() {
+        let packet: Packet = "[1,[2,[]],10]".parse().expect("packet should be valid");
+        assert_eq!(packet.to_string(), "[1,[2,[]],10]");
+        for invalid in ["", "[1,2", "[1]]", "[a]", "[256]"] {
+            assert!(
+                invalid.parse::<Packet>().is_err(),
+                "packet '{invalid}' should be invalid"
+            );
+        }
+    }
+
+    #[test]
+    fn compare() {
+        let [left, right] = ["[[1],[2,3,4]]", "[[1],4]"].map(super::packet);
+        assert_eq!(super::compare(&left, &right), Ordering::Less);
+        let [integer, list] = ["7", "[7]"].map(super::packet);
+        assert_eq!(super::compare(&integer, &list), Ordering::Equal);
+    }
 }
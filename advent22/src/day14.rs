@@ -112,9 +112,13 @@ fn path(line: &str) -> Vec<Position> {
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use infrastructure::{Input, Puzzle};
+    use shared::render::{self, Color};
 
-    use crate::tests::test_on_input;
+    use super::*;
+    use crate::tests::{input, test_on_input};
 
     const DAY: usize = 14;
 
@@ -137,4 +141,34 @@ mod tests {
     fn second_answer_input() {
         test_on_input(DAY, Puzzle::Second, Input::PuzzleInput, 23416);
     }
+
+    #[test]
+    #[ignore = "writes an animation to the temporary directory"]
+    fn sand_animation() {
+        let (mut cave, sand_source) = super::cave(&input(DAY, Input::Example(0)));
+        let empty_cave = cave.clone();
+        let mut rest_positions = vec![];
+        produce_sand(&mut cave, sand_source, |rest_position| {
+            rest_positions.push(rest_position);
+            rest_position == sand_source
+        });
+        let frames = (0..=rest_positions.len()).map(|number_of_units| {
+            let mut cave = empty_cave.clone();
+            for &rest_position in &rest_positions[..number_of_units] {
+                cave[rest_position] = b'o';
+            }
+            render::scaled(&render::grid(&cave, |&element| element_color(element)), 8)
+        });
+        let path = std::env::temp_dir().join("advent22-14-sand.gif");
+        render::save_animation(frames, Duration::from_millis(50), path)
+            .expect("animation should be saved");
+    }
+
+    fn element_color(element: u8) -> Color {
+        match element {
+            b'#' => [120, 120, 120],
+            b'o' => [230, 200, 120],
+            _ => render::BLACK,
+        }
+    }
 }
@@ -1,89 +1,58 @@
-use std::{cmp, fmt::Debug};
+use std::{fmt::Debug, ops::Range};
 
 use ahash::AHashSet;
 use easy_cast::Conv;
+use infrastructure::parameters::Parameter;
 use itertools::Itertools;
 
-use shared::{string::isizes, vector::ManhattanDistance};
+use shared::{interval::IntervalSet, string::isizes, vector::ManhattanDistance};
 
 type Sensor = Position;
 type Beacon = Position;
-type Range = [Coordinate; 2];
 type Position = [Coordinate; 2];
 type Coordinate = isize;
 
+const ROW: Parameter<Coordinate> = Parameter::new("row", 2_000_000, 10);
+const COORDINATE_MAX: Parameter<Coordinate> = Parameter::new("coordinate_max", 4_000_000, 20);
+pub const PARAMETERS: &[&str] = &[ROW.key(), COORDINATE_MAX.key()];
+
 pub fn first_answer(input: &str) -> String {
-    number_of_impossible_positions_from_input(input, 2_000_000).to_string()
+    number_of_impossible_positions_from_input(input, ROW.get()).to_string()
 }
 
 pub fn second_answer(input: &str) -> String {
-    let distress_beacon = distress_beacon_from_input(input, 4_000_000);
+    let distress_beacon = distress_beacon_from_input(input, COORDINATE_MAX.get());
     tuning_frequency(distress_beacon).to_string()
 }
 
 fn distress_beacon_from_input(input: &str, coordinate_max: Coordinate) -> Position {
     let (sensors, beacons) = sensors_and_closest_beacons(input);
     let distances = distances_to_closest_beacons(&sensors, &beacons);
-    let (row, [column_start, column_end]) =
-        possible_positions(&sensors, &distances, coordinate_max)
-            .exactly_one()
-            .expect("exactly one row should be a possible row for the beacon");
+    let (row, columns) = possible_positions(&sensors, &distances, coordinate_max)
+        .exactly_one()
+        .expect("exactly one row should be a possible row for the beacon");
     debug_assert!(
-        column_start + 1 == column_end,
+        columns.len() == 1,
         "exactly one column should be a possible column for the beacon",
     );
-    [row, column_start]
+    [row, columns.start]
 }
 
 fn possible_positions<'data>(
     sensors: &'data [Sensor],
     distances: &'data [Coordinate],
     coordinate_max: Coordinate,
-) -> impl Iterator<Item = (Coordinate, Range)> + Debug + 'data {
+) -> impl Iterator<Item = (Coordinate, Range<Coordinate>)> + Debug + 'data {
     (0..=coordinate_max).flat_map(move |row| {
-        let impossible_ranges = impossible_ranges(sensors, distances, row);
-        complement_ranges(impossible_ranges, [0, coordinate_max + 1])
-            .into_iter()
-            .map(move |range| (row, range))
+        impossible_ranges(sensors, distances, row)
+            .complement(0..coordinate_max + 1)
+            .intervals()
+            .iter()
+            .map(move |range| (row, range.clone()))
+            .collect_vec()
     })
 }
 
-fn complement_ranges(sorted_ranges: Vec<Range>, [min, max]: [Coordinate; 2]) -> Vec<Range> {
-    let ranges_overlapping_universe = sorted_ranges
-        .into_iter()
-        .skip_while(move |&[_, end]| end <= min)
-        .take_while(move |&[start, _]| start < max)
-        .collect_vec();
-    if ranges_overlapping_universe.is_empty() {
-        return vec![[min, max]];
-    }
-
-    let mut complement_ranges = vec![];
-
-    let &[first_start, _] = ranges_overlapping_universe
-        .first()
-        .expect("at least one range should overlap universe");
-    if min < first_start {
-        complement_ranges.push([min, first_start]);
-    }
-
-    let between_ranges = ranges_overlapping_universe
-        .iter()
-        .copied()
-        .tuple_windows()
-        .map(move |([_, left_end], [right_start, _])| [left_end, right_start]);
-    complement_ranges.extend(between_ranges);
-
-    let &[_, last_end] = ranges_overlapping_universe
-        .last()
-        .expect("at least one range should overlap universe");
-    if last_end < max {
-        complement_ranges.push([last_end, max]);
-    }
-
-    complement_ranges
-}
-
 fn tuning_frequency([row, column]: Position) -> isize {
     column * 4_000_000 + row
 }
@@ -98,72 +67,45 @@ fn number_of_impossible_positions_from_input(input: &str, row: Coordinate) -> us
 
 fn number_of_impossible_positions(
     beacons: &AHashSet<Beacon>,
-    impossible_ranges: &[Range],
+    impossible_ranges: &IntervalSet<Coordinate>,
     row: Coordinate,
 ) -> usize {
     let beacons = beacons
         .iter()
         .filter(|&&beacon| beacon_within_ranges(beacon, impossible_ranges, row))
         .count();
-    let impossible_positions: isize = impossible_ranges
-        .iter()
-        .map(|&[start, end]| end - start)
-        .sum();
-    usize::conv(impossible_positions) - beacons
+    usize::conv(impossible_ranges.measure()) - beacons
 }
 
 fn beacon_within_ranges(
     [beacon_row, beacon_column]: Beacon,
-    ranges: &[Range],
+    ranges: &IntervalSet<Coordinate>,
     row: Coordinate,
 ) -> bool {
-    let correct_row = beacon_row == row;
-    let within_range = ranges
-        .iter()
-        .any(|&[start, end]| start <= beacon_column && beacon_column < end);
-    correct_row && within_range
+    beacon_row == row && ranges.contains(&beacon_column)
 }
 
-fn impossible_ranges(sensors: &[Sensor], distances: &[Coordinate], row: Coordinate) -> Vec<Range> {
-    let mut overlapping_impossible_ranges = sensors
+fn impossible_ranges(
+    sensors: &[Sensor],
+    distances: &[Coordinate],
+    row: Coordinate,
+) -> IntervalSet<Coordinate> {
+    sensors
         .iter()
         .zip_eq(distances)
         .filter_map(|(&sensor, &distance)| impossible_range(sensor, distance, row))
-        .collect_vec();
-    overlapping_impossible_ranges.sort_unstable();
-
-    let mut overlapping_impossible_ranges = &overlapping_impossible_ranges[0..];
-    let mut merged_impossible_ranges = vec![];
-    while !overlapping_impossible_ranges.is_empty() {
-        let (remaining, merged_range) = merged_range(overlapping_impossible_ranges);
-        overlapping_impossible_ranges = remaining;
-        merged_impossible_ranges.push(merged_range);
-    }
-    merged_impossible_ranges
-}
-
-fn merged_range(sorted_ranges: &[Range]) -> (&[Range], Range) {
-    let [start, mut end] = [sorted_ranges[0][0], sorted_ranges[0][1]];
-    for (index, range) in sorted_ranges[0..].iter().enumerate().skip(1) {
-        if end < range[0] {
-            return (&sorted_ranges[index..], [start, end]);
-        }
-        end = cmp::max(end, range[1]);
-    }
-    (&[], [start, end])
+        .collect()
 }
 
 fn impossible_range(
     [sensor_row, sensor_column]: Sensor,
     distance: Coordinate,
     row: Coordinate,
-) -> Option<Range> {
+) -> Option<Range<Coordinate>> {
     let perimeter_row_farthest_distance = distance - (sensor_row - row).abs();
     (!perimeter_row_farthest_distance.is_negative()).then(|| {
-        [
-            sensor_column - perimeter_row_farthest_distance,
-            sensor_column + perimeter_row_farthest_distance + 1,
-        ]
+        sensor_column - perimeter_row_farthest_distance
+            ..sensor_column + perimeter_row_farthest_distance + 1
     })
 }
 
@@ -191,16 +133,13 @@ fn sensors_and_closest_beacons(input: &str) -> (Vec<Sensor>, Vec<Beacon>) {
 mod tests {
     use infrastructure::{Input, Puzzle, test};
 
-    use super::*;
-    use crate::tests::{input, test_on_input};
+    use crate::tests::test_on_input;
 
     const DAY: usize = 15;
 
     #[test]
     fn first_answer_example() {
-        let input = input(DAY, Input::Example(0));
-        let actual = number_of_impossible_positions_from_input(&input, 10);
-        assert_eq!(actual, 26);
+        test_on_input(DAY, Puzzle::First, Input::Example(0), 26);
     }
 
     #[test]
@@ -210,9 +149,7 @@ mod tests {
 
     #[test]
     fn second_answer_example() {
-        let input = input(DAY, Input::Example(0));
-        let actual = tuning_frequency(distress_beacon_from_input(&input, 20));
-        assert_eq!(actual, 56_000_011);
+        test_on_input(DAY, Puzzle::Second, Input::Example(0), 56_000_011);
     }
 
     #[test]
@@ -225,28 +162,16 @@ mod tests {
         );
     }
 
-    #[test]
-    fn complement_ranges() {
-        let universe @ [min, max] = [0, 21];
-        let function = |sorted_ranges| super::complement_ranges(sorted_ranges, universe);
-        let cases = [
-            (vec![[-10, 0]], vec![universe]),
-            (vec![[7, 9]], vec![[min, 7], [9, max]]),
-            (vec![[-3, 14], [15, 26]], vec![[14, 15]]),
-        ];
-        test::cases(function, cases);
-    }
-
     #[test]
     fn impossible_beacon_range() {
         let function = |row| super::impossible_range([7, 8], 9, row);
         let cases = [
             (-3, None),
-            (-2, Some([8, 9])),
-            (3, Some([3, 14])),
-            (7, Some([-1, 18])),
-            (10, Some([2, 15])),
-            (14, Some([6, 11])),
+            (-2, Some(8..9)),
+            (3, Some(3..14)),
+            (7, Some(-1..18)),
+            (10, Some(2..15)),
+            (14, Some(6..11)),
             (17, None),
         ];
         test::cases(function, cases);
@@ -28,6 +28,11 @@ fn maximum_release_from_input<const NUM_AGENTS: usize>(
 ) -> Pressure {
     let cave = cave(input);
     let contracted_cave = contracted_cave(&cave, start);
+    infrastructure::trace::event("broken valves pruned", |event| {
+        event
+            .field("valves", cave.len())
+            .field("remaining", contracted_cave.len())
+    });
     let mut closed_valves = contracted_cave
         .iter()
         .filter_map(|(&valve, &(flow, _))| (flow != 0).then_some(valve))
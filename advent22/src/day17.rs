@@ -59,6 +59,13 @@ fn tower_height(jets: &str, mut number_of_rocks: usize) -> Coordinate {
         if let Some((cycle_length, tower_height_cycle_start)) =
             cycle_length_and_tower_height_cycle_start(&past_states_and_tower_heights, &state)
         {
+            infrastructure::trace::event("cycle detected", |event| {
+                event
+                    .field("rocks_dropped", past_states_and_tower_heights.len())
+                    .field("length", cycle_length)
+                    .field("tower_height", tower_height)
+                    .field("tower_height_cycle_start", tower_height_cycle_start)
+            });
             let remaining_cycles = number_of_rocks / cycle_length;
             let tower_height_complete_cycles =
                 (tower_height - tower_height_cycle_start) * remaining_cycles;
@@ -230,7 +237,10 @@ fn add_empty_row(chamber: &mut Chamber) {
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use infrastructure::{Input, Puzzle};
+    use shared::{grid::Rectangle, render};
 
     use super::*;
     use crate::tests::{input, test_on_input};
@@ -361,4 +371,30 @@ mod tests {
         }
         (chamber, tower_height)
     }
+
+    #[test]
+    #[ignore = "writes an animation to the temporary directory"]
+    fn falling_rocks_animation() {
+        let number_of_rocks = 40;
+        let (_, tower_height) = chamber(Input::Example(0), number_of_rocks);
+        let height: isize = (1 + tower_height).cast();
+        let bounds = Rectangle {
+            top_left: [0, 0],
+            dimensions: [height, CHAMBER_WIDTH_INCLUDING_WALLS.cast()],
+        };
+        let frames = (0..=number_of_rocks).map(|number_of_rocks| {
+            let (chamber, _) = chamber(Input::Example(0), number_of_rocks);
+            let rocks = chamber.into_iter().enumerate().flat_map(|(row, cells)| {
+                let row: isize = row.cast();
+                (0..CHAMBER_WIDTH_INCLUDING_WALLS)
+                    .filter(move |&column| cells[column])
+                    .map(move |column| [height - 1 - row, column.cast()])
+            });
+            let image = render::positions(rocks, bounds, [render::WHITE, render::BLACK]);
+            render::scaled(&image, 8)
+        });
+        let path = std::env::temp_dir().join("advent22-17-falling-rocks.gif");
+        render::save_animation(frames, Duration::from_millis(200), path)
+            .expect("animation should be saved");
+    }
 }
@@ -1,11 +1,12 @@
 use std::{char, str};
 
-use easy_cast::{Cast, Conv};
+use easy_cast::Conv;
 use itertools::Itertools;
 
 use shared::{
-    grid::{self, Coordinate, Direction, Grid, Position},
-    vector::{CrossProduct, Negation, RotationInTwoDimensions, Vector},
+    cube,
+    grid::{self, Coordinate, Direction, Glued, Grid, Position, Topology},
+    vector::{RotationInTwoDimensions, Vector},
 };
 
 type Board = Grid<char>;
@@ -19,13 +20,15 @@ enum Instruction {
 
 pub fn first_answer(input: &str) -> String {
     let (board, path) = board_and_path(input);
-    let (final_position, final_direction) = final_position_and_direction(wrap_plane, &board, path);
+    let topology = plane(&board);
+    let (final_position, final_direction) = final_position_and_direction(&topology, &board, path);
     final_password(final_position, final_direction).to_string()
 }
 
 pub fn second_answer(input: &str) -> String {
     let (board, path) = board_and_path(input);
-    let (final_position, final_direction) = final_position_and_direction(wrap_cube, &board, path);
+    let topology = cube(&board);
+    let (final_position, final_direction) = final_position_and_direction(&topology, &board, path);
     final_password(final_position, final_direction).to_string()
 }
 
@@ -42,7 +45,7 @@ fn final_password(final_position: Position, final_direction: Direction) -> isize
 }
 
 fn final_position_and_direction(
-    wrap: Wrap,
+    topology: &impl Topology,
     board: &Board,
     path: impl Iterator<Item = Instruction>,
 ) -> (Position, Direction) {
@@ -56,35 +59,40 @@ fn final_position_and_direction(
             Instruction::Left => direction = direction.left(),
             Instruction::Right => direction = direction.right(),
             Instruction::Forward(number_of_tiles) => {
-                (position, direction) = forward(wrap, board, position, direction, number_of_tiles);
+                (position, direction) =
+                    forward(topology, board, position, direction, number_of_tiles);
             }
         }
     }
     (position, direction)
 }
 
-type Wrap = fn(&Board, Position, Direction) -> (Position, Direction);
+fn plane(board: &Board) -> impl Topology + '_ {
+    Glued {
+        inside: |position| matches!(board.get(position), Some('.' | '#')),
+        glue: |position, direction| Some(wrap_plane(board, position, direction)),
+    }
+}
+
+fn cube(board: &Board) -> cube::Net {
+    cube::Net::from_grid(board, |&tile| tile != ' ').expect("board should be a net of a cube")
+}
 
 fn forward(
-    wrap: Wrap,
+    topology: &impl Topology,
     board: &Board,
     mut position: Position,
     mut direction: Direction,
     number_of_tiles: usize,
 ) -> (Position, Direction) {
     for _ in 0..number_of_tiles {
-        let neighbor = position.add(direction);
-        (position, direction) = match board.get(neighbor) {
-            Some('.') => (neighbor, direction),
-            Some('#') => return (position, direction),
-            Some(' ') | None => {
-                let (wrap_position, wrap_direction) = wrap(board, position, direction);
-                if board[wrap_position] == '#' {
-                    return (position, direction);
-                }
-                (wrap_position, wrap_direction)
-            }
-            _ => panic!("tile on the board should be '.', '#', or ' '"),
+        let (next_position, next_direction) = topology
+            .step(position, direction)
+            .expect("board should have no edges");
+        match board[next_position] {
+            '.' => (position, direction) = (next_position, next_direction),
+            '#' => return (position, direction),
+            _ => panic!("tile on the board should be '.' or '#'"),
         }
     }
     (position, direction)
@@ -108,121 +116,6 @@ fn wrap_plane(
     (wrap_position, direction)
 }
 
-fn wrap_cube(
-    board: &Grid<char>,
-    position: Position,
-    direction: Direction,
-) -> (Position, Direction) {
-    let face_size = face_size(board).cast();
-
-    let mut plane_face = position.map(|coordinate| coordinate / face_size);
-    let mut plane_direction = direction.right();
-    let mut cube_face = [0, 0, -1];
-    let mut cube_direction = [plane_direction[0], plane_direction[1], 0];
-    while !(cube_face[0..2] == direction[0..2] && cube_direction.cross(cube_face) == [0, 0, -1]) {
-        (plane_face, plane_direction, cube_face, cube_direction) = next_face(
-            board,
-            face_size,
-            plane_face,
-            plane_direction,
-            cube_face,
-            cube_direction,
-        );
-    }
-
-    let wrap_direction = plane_direction.right();
-    let wrap_position =
-        cube_wrap_position(face_size, position, direction, plane_face, wrap_direction);
-    (wrap_position, wrap_direction)
-}
-
-fn next_face(
-    board: &Board,
-    face_size: Coordinate,
-    plane_face: Position,
-    plane_direction: Direction,
-    cube_face: [Coordinate; 3],
-    cube_direction: [Coordinate; 3],
-) -> (Position, Direction, [Coordinate; 3], [Coordinate; 3]) {
-    let plane_face_forward = plane_face.add(plane_direction);
-    let plane_face_forward_left = plane_face_forward.add(plane_direction.left());
-    if face_exists(board, face_size, plane_face_forward_left) {
-        (
-            plane_face_forward_left,
-            plane_direction.left(),
-            cube_direction.cross(cube_face),
-            cube_direction.neg(),
-        )
-    } else if face_exists(board, face_size, plane_face_forward) {
-        (
-            plane_face_forward,
-            plane_direction,
-            cube_direction,
-            cube_face.neg(),
-        )
-    } else {
-        (
-            plane_face,
-            plane_direction.right(),
-            cube_face,
-            cube_face.cross(cube_direction),
-        )
-    }
-}
-
-fn cube_wrap_position(
-    face_size: Coordinate,
-    [source_row, source_column]: Position,
-    source_direction: Direction,
-    destination_face: Position,
-    destination_direction: Direction,
-) -> Position {
-    let source_distance_to_face_border = match source_direction {
-        grid::NORTH => face_size - source_column % face_size - 1,
-        grid::EAST => face_size - source_row % face_size - 1,
-        grid::SOUTH => source_column % face_size,
-        grid::WEST => source_row % face_size,
-        _ => panic!("direction should be one of four unit vectors"),
-    };
-
-    let [top_of_face, left_of_face] = destination_face.mul(face_size);
-    match destination_direction {
-        grid::NORTH => [
-            top_of_face + face_size - 1,
-            left_of_face + face_size - 1 - source_distance_to_face_border,
-        ],
-        grid::EAST => [
-            top_of_face + face_size - 1 - source_distance_to_face_border,
-            left_of_face,
-        ],
-        grid::SOUTH => [top_of_face, left_of_face + source_distance_to_face_border],
-        grid::WEST => [
-            top_of_face + source_distance_to_face_border,
-            left_of_face + face_size - 1,
-        ],
-        _ => panic!("direction should be one of four unit vectors"),
-    }
-}
-
-fn face_size(board: &Board) -> usize {
-    let mut board_sides = [board.height(), board.width()];
-    board_sides.sort_unstable();
-    if board_sides[0] * 5 == board_sides[1] * 2 {
-        board_sides[0] / 2
-    } else {
-        board_sides[0] / 3
-    }
-}
-
-fn face_exists(board: &Board, face_size: Coordinate, face_position: Position) -> bool {
-    let top_left_of_face = face_position.mul(face_size);
-    match board.get(top_left_of_face) {
-        Some(' ') | None => false,
-        Some('.' | '#') => true,
-        _ => panic!("tile on the board should be '.', '#', or ' '"),
-    }
-}
-
 fn board_and_path(input: &str) -> (Board, impl Iterator<Item = Instruction> + '_) {
     let (board, path) = input
         .trim_end()
@@ -297,40 +190,16 @@ mod tests {
     }
 
     #[test]
-    fn wrap_cube() {
-        let board = board(Input::Example(0));
-        let function = |(position, direction)| super::wrap_cube(&board, position, direction);
-        let cases = [
-            ((A, grid::EAST), (B, grid::SOUTH)),
-            ((B, grid::NORTH), (A, grid::WEST)),
-            ((C, grid::SOUTH), (D, grid::NORTH)),
-            ((D, grid::SOUTH), (C, grid::NORTH)),
-            ((E, grid::NORTH), (F, grid::EAST)),
-            ((F, grid::WEST), (E, grid::SOUTH)),
-        ];
-        test::cases(function, cases);
-    }
-
-    #[test]
-    fn cube_wrap_position() {
-        let face_size = super::face_size(&board(Input::Example(0))).cast();
-        let function =
-            |(source_position, source_direction, destination_face, destination_direction)| {
-                super::cube_wrap_position(
-                    face_size,
-                    source_position,
-                    source_direction,
-                    destination_face,
-                    destination_direction,
-                )
-            };
+    fn cube() {
+        let cube = super::cube(&board(Input::Example(0)));
+        let function = |(position, direction)| cube.step(position, direction);
         let cases = [
-            ((A, grid::EAST, [2, 3], grid::SOUTH), B),
-            ((B, grid::NORTH, [1, 2], grid::WEST), A),
-            ((C, grid::SOUTH, [1, 0], grid::NORTH), D),
-            ((D, grid::SOUTH, [2, 2], grid::NORTH), C),
-            ((E, grid::NORTH, [0, 2], grid::EAST), F),
-            ((F, grid::WEST, [1, 1], grid::SOUTH), E),
+            ((A, grid::EAST), Some((B, grid::SOUTH))),
+            ((B, grid::NORTH), Some((A, grid::WEST))),
+            ((C, grid::SOUTH), Some((D, grid::NORTH))),
+            ((D, grid::SOUTH), Some((C, grid::NORTH))),
+            ((E, grid::NORTH), Some((F, grid::EAST))),
+            ((F, grid::WEST), Some((E, grid::SOUTH))),
         ];
         test::cases(function, cases);
     }
@@ -344,7 +213,7 @@ mod tests {
 
     #[test]
     fn face_size() {
-        let function = |input| super::face_size(&board(input));
+        let function = |input| super::cube(&board(input)).face_size();
         let cases = [(Input::Example(0), 4), (Input::Example(1), 1)];
         test::cases(function, cases);
     }
@@ -2,10 +2,7 @@ use ahash::AHashSet;
 use easy_cast::{Cast, Conv};
 use itertools::Itertools;
 
-use shared::{
-    grid::{self, Coordinate, Direction, Position, direction},
-    vector::Vector,
-};
+use shared::grid::{self, Coordinate, Direction, Position, Rectangle, Topology, Torus, direction};
 
 type Blizzards = Vec<Blizzard>;
 type Blizzard = (Position, Direction);
@@ -71,11 +68,11 @@ fn fastest_path(
 }
 
 fn move_blizzards(valley_dimensions: [Coordinate; 2], blizzards: &mut Blizzards) {
+    let interior = interior(valley_dimensions);
     for (position, direction) in blizzards.iter_mut() {
-        *position = position.add(*direction);
-        if is_outside_valley(valley_dimensions, *position) {
-            *position = opposite_side(valley_dimensions, *position, *direction);
-        }
+        (*position, _) = interior
+            .step(*position, *direction)
+            .expect("blizzards should wrap around within the valley");
     }
     blizzards.sort_unstable();
 }
@@ -87,18 +84,11 @@ fn is_outside_valley([height, width]: [Coordinate; 2], [row, column]: Position)
         || column == Coordinate::conv(width) - 1
 }
 
-fn opposite_side(
-    [height, width]: [Coordinate; 2],
-    [row, column]: Position,
-    direction: Direction,
-) -> Position {
-    match direction {
-        grid::NORTH => [height - 2, column],
-        grid::EAST => [row, 1],
-        grid::SOUTH => [1, column],
-        grid::WEST => [row, width - 2],
-        _ => panic!("direction should be one of four unit vectors"),
-    }
+fn interior([height, width]: [Coordinate; 2]) -> Torus {
+    Torus(Rectangle {
+        top_left: [1, 1],
+        dimensions: [height - 2, width - 2],
+    })
 }
 
 fn valley(input: &str) -> ([Coordinate; 2], Blizzards) {
@@ -157,13 +147,14 @@ mod tests {
     }
 
     #[test]
-    fn opposite_side() {
-        let function = |(position, direction)| super::opposite_side([6, 7], position, direction);
+    fn interior() {
+        let interior = super::interior([6, 7]);
+        let function = |(position, direction)| interior.step(position, direction);
         let cases = [
-            (([0, 5], grid::NORTH), [4, 5]),
-            (([3, 7], grid::EAST), [3, 1]),
-            (([5, 3], grid::SOUTH), [1, 3]),
-            (([4, 0], grid::WEST), [4, 5]),
+            (([1, 5], grid::NORTH), Some(([4, 5], grid::NORTH))),
+            (([3, 5], grid::EAST), Some(([3, 1], grid::EAST))),
+            (([4, 3], grid::SOUTH), Some(([1, 3], grid::SOUTH))),
+            (([4, 1], grid::WEST), Some(([4, 5], grid::WEST))),
         ];
         test::cases(function, cases);
     }
@@ -1,4 +1,62 @@
-type Decimal = isize;
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+use anyhow::{anyhow, bail};
+
+/// A number in the balanced base-five system of the hot-air balloons, in which the digits `=`,
+/// `-`, `0`, `1` and `2` stand for minus two to two.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Snafu(pub Decimal);
+
+pub type Decimal = isize;
+
+impl FromStr for Snafu {
+    type Err = anyhow::Error;
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        if str.is_empty() {
+            bail!("snafu number should have at least one digit");
+        }
+        let invalid = || {
+            anyhow!(
+                "snafu number '{str}' should be valid and fit in {} bits",
+                Decimal::BITS
+            )
+        };
+        // Wider than `Decimal`, as the last digit can bring a number that overflows it back in.
+        let mut decimal: i128 = 0;
+        for char in str.chars() {
+            decimal = decimal
+                .checked_mul(5)
+                .and_then(|decimal| decimal.checked_add(digit(char)?.try_into().ok()?))
+                .ok_or_else(invalid)?;
+        }
+        Decimal::try_from(decimal).map(Self).map_err(|_| invalid())
+    }
+}
+
+impl Display for Snafu {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut decimal = self.0;
+        let mut snafu = vec![];
+        while decimal != 0 {
+            // A digit of three or four is written as minus two or minus one, with one carried,
+            // which cannot overflow at the extremes as adding two before dividing would.
+            let (value, carry) = match decimal.rem_euclid(5) {
+                digit @ 0..=2 => (digit, 0),
+                digit => (digit - 5, 1),
+            };
+            snafu.push(symbol(value));
+            decimal = decimal.div_euclid(5) + carry;
+        }
+        if snafu.is_empty() {
+            snafu.push('0');
+        }
+        write!(f, "{}", snafu.into_iter().rev().collect::<String>())
+    }
+}
 
 pub fn first_answer(input: &str) -> String {
     let sum: Decimal = input.lines().map(decimal).sum();
@@ -9,51 +67,35 @@ pub fn second_answer(_input: &str) -> String {
     "There is no second puzzle on the 25th. Merry Christmas!".to_owned()
 }
 
-fn snafu(mut decimal: Decimal) -> String {
-    let mut snafu = vec![];
-    while decimal > 0 {
-        let mut symbol = decimal % 5;
-        if symbol > 2 {
-            symbol -= 5;
-            decimal -= symbol;
-        }
-        snafu.push(snafu_symbol(symbol));
-        decimal /= 5;
-    }
-    snafu.reverse();
-    if snafu.is_empty() {
-        snafu.push(b'0');
-    }
-    String::from_utf8(snafu).expect("snafu number should consist of only ascii characters")
+fn snafu(decimal: Decimal) -> String {
+    Snafu(decimal).to_string()
 }
 
-fn snafu_symbol(value: Decimal) -> u8 {
+fn symbol(value: Decimal) -> char {
     match value {
-        -2 => b'=',
-        -1 => b'-',
-        0 => b'0',
-        1 => b'1',
-        2 => b'2',
+        -2 => '=',
+        -1 => '-',
+        0 => '0',
+        1 => '1',
+        2 => '2',
         _ => panic!("value should be '-2', '-1', '0', '1', or '2'"),
     }
 }
 
 fn decimal(line: &str) -> Decimal {
-    let mut decimal = 0;
-    for char in line.chars() {
-        decimal = 5 * decimal + digit(char);
-    }
-    decimal
+    line.parse::<Snafu>()
+        .expect("snafu number should be valid")
+        .0
 }
 
-fn digit(char: char) -> Decimal {
+fn digit(char: char) -> Option<Decimal> {
     match char {
-        '=' => -2,
-        '-' => -1,
-        '0' => 0,
-        '1' => 1,
-        '2' => 2,
-        _ => panic!("digit should be '=', '-', '0', '1', or '2'"),
+        '=' => Some(-2),
+        '-' => Some(-1),
+        '0' => Some(0),
+        '1' => Some(1),
+        '2' => Some(2),
+        _ => None,
     }
 }
 
@@ -98,6 +140,14 @@ mod tests {
         test::cases(decimal, SMALL_SNAFU.into_iter().zip_eq(SMALL_DECIMAL));
     }
 
+    #[test]
+    fn extremes_round_trip() {
+        for decimal in [Decimal::MAX, Decimal::MIN] {
+            let snafu = Snafu(decimal).to_string();
+            assert_eq!(snafu.parse::<Snafu>().ok(), Some(Snafu(decimal)), "{snafu}");
+        }
+    }
+
     #[test]
     fn large_decimal_to_snafu() {
         test::cases(
@@ -113,6 +163,24 @@ mod tests {
         test::cases(decimal, LARGE_SNAFU.into_iter().zip_eq(LARGE_DECIMAL));
     }
 
+    #[test]
+    fn negative() {
+        for decimal in -30..=0 {
+            assert_eq!(self::decimal(&snafu(decimal)), decimal);
+        }
+        assert_eq!(snafu(-3), "-2");
+    }
+
+    #[test]
+    fn invalid() {
+        for invalid in ["", "12a", "2222222222222222222222222222"] {
+            assert!(
+                invalid.parse::<Snafu>().is_err(),
+                "snafu number '{invalid}' should be invalid"
+            );
+        }
+    }
+
     const SMALL_DECIMAL: [Decimal; 11] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
     const SMALL_SNAFU: [&str; 11] = [
         "0", "1", "2", "1=", "1-", "10", "11", "12", "2=", "2-", "20",
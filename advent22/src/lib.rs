@@ -24,41 +24,63 @@ mod day23;
 mod day24;
 mod day25;
 
-const SOLUTIONS: &[[infrastructure::Solution; 2]] = &[
-    [day01::first_answer, day01::second_answer],
-    [day02::first_answer, day02::second_answer],
-    [day03::first_answer, day03::second_answer],
-    [day04::first_answer, day04::second_answer],
-    [day05::first_answer, day05::second_answer],
-    [day06::first_answer, day06::second_answer],
-    [day07::first_answer, day07::second_answer],
-    [day08::first_answer, day08::second_answer],
-    [day09::first_answer, day09::second_answer],
-    [day10::first_answer, day10::second_answer],
-    [day11::first_answer, day11::second_answer],
-    [day12::first_answer, day12::second_answer],
-    [day13::first_answer, day13::second_answer],
-    [day14::first_answer, day14::second_answer],
-    [day15::first_answer, day15::second_answer],
-    [day16::first_answer, day16::second_answer],
-    [day17::first_answer, day17::second_answer],
-    [day18::first_answer, day18::second_answer],
-    [day19::first_answer, day19::second_answer],
-    [day20::first_answer, day20::second_answer],
-    [day21::first_answer, day21::second_answer],
-    [day22::first_answer, day22::second_answer],
-    [day23::first_answer, day23::second_answer],
-    [day24::first_answer, day24::second_answer],
-    [day25::first_answer, day25::second_answer],
-];
+/// The packets of the distress signal and the snafu numbers of the hot-air balloons.
+pub mod models {
+    /// The distress signal packets of day 13.
+    pub mod distress_signal {
+        pub use crate::day13::{Integer, Packet, compare};
+    }
 
-/// # Errors
-///
-/// Errors if the wanted puzzle input cannot be read or the wanted solution does not exist.
-pub fn answer(day: infrastructure::Day, puzzle: infrastructure::Puzzle) -> anyhow::Result<String> {
-    infrastructure::answer(env!("CARGO_MANIFEST_DIR"), day, puzzle, SOLUTIONS)
+    /// The SNAFU numbers of day 25.
+    pub mod snafu {
+        pub use crate::day25::{Decimal, Snafu};
+    }
 }
 
+/// The solutions to both puzzles of each day, or `None` for the days that are not solved yet.
+const SOLUTIONS: &[Option<[infrastructure::Solution; 2]>] = &[
+    Some([day01::first_answer, day01::second_answer]),
+    Some([day02::first_answer, day02::second_answer]),
+    Some([day03::first_answer, day03::second_answer]),
+    Some([day04::first_answer, day04::second_answer]),
+    Some([day05::first_answer, day05::second_answer]),
+    Some([day06::first_answer, day06::second_answer]),
+    Some([day07::first_answer, day07::second_answer]),
+    Some([day08::first_answer, day08::second_answer]),
+    Some([day09::first_answer, day09::second_answer]),
+    Some([day10::first_answer, day10::second_answer]),
+    Some([day11::first_answer, day11::second_answer]),
+    Some([day12::first_answer, day12::second_answer]),
+    Some([day13::first_answer, day13::second_answer]),
+    Some([day14::first_answer, day14::second_answer]),
+    Some([day15::first_answer, day15::second_answer]),
+    Some([day16::first_answer, day16::second_answer]),
+    Some([day17::first_answer, day17::second_answer]),
+    Some([day18::first_answer, day18::second_answer]),
+    Some([day19::first_answer, day19::second_answer]),
+    Some([day20::first_answer, day20::second_answer]),
+    Some([day21::first_answer, day21::second_answer]),
+    Some([day22::first_answer, day22::second_answer]),
+    Some([day23::first_answer, day23::second_answer]),
+    Some([day24::first_answer, day24::second_answer]),
+    Some([day25::first_answer, day25::second_answer]),
+];
+
+const VARIANTS: &[infrastructure::variants::Variant] = &[];
+
+const PARAMETERS: &infrastructure::parameters::Keys = &[(15, day15::PARAMETERS)];
+
+const GENERATORS: &infrastructure::generate::Generators = &[];
+
+pub const YEAR: infrastructure::year::Year = infrastructure::year::Year {
+    year: 2022,
+    manifest_directory: env!("CARGO_MANIFEST_DIR"),
+    solutions: SOLUTIONS,
+    variants: VARIANTS,
+    parameters: PARAMETERS,
+    generators: GENERATORS,
+};
+
 #[cfg(test)]
 mod tests {
     use std::fmt::Display;
@@ -67,13 +89,13 @@ mod tests {
 
     /// # Panics
     ///
-    /// Panics if the return value of the solution applied to the input does not equal
-    /// `expected.to_string()`.
+    /// Panics if the return value of the solution or one of its variants applied to the input
+    /// does not equal `expected.to_string()`.
     pub fn test_on_input(day: Day, puzzle: Puzzle, input: Input, expected: impl Display) {
-        infrastructure::test::on_input(self::input, super::SOLUTIONS, day, puzzle, input, expected);
+        infrastructure::test::on_input(&super::YEAR, day, puzzle, input, expected);
     }
 
-    pub fn input(day: infrastructure::Day, input: infrastructure::Input) -> String {
-        infrastructure::input(env!("CARGO_MANIFEST_DIR"), day, input).expect("input should exist")
+    pub fn input(day: Day, input: Input) -> String {
+        super::YEAR.input(day, input).expect("input should exist")
     }
 }
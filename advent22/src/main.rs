@@ -1,3 +1,3 @@
 fn main() -> anyhow::Result<()> {
-    infrastructure::cli::main(advent22::answer)
+    infrastructure::cli::main(&advent22::YEAR)
 }
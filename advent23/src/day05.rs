@@ -1,12 +1,14 @@
-use std::{collections::BTreeMap, ops::Range};
-
 use itertools::Itertools;
+use rand::{RngExt, rngs::SmallRng, seq::IteratorRandom};
 
-use shared::string::isizes;
+use shared::{
+    interval::{IntervalSet, OffsetMap},
+    string::isizes,
+};
 
-type Map = BTreeMap<Number, Offset>;
+type Map = OffsetMap<Number>;
+type Seeds = IntervalSet<Number>;
 type Number = isize;
-type Offset = isize;
 
 pub fn first_answer(input: &str) -> String {
     minimum_location_from_input(input, singleton_seed_ranges).to_string()
@@ -16,62 +18,70 @@ pub fn second_answer(input: &str) -> String {
     minimum_location_from_input(input, seed_ranges).to_string()
 }
 
-fn minimum_location_from_input(input: &str, seed_ranges: fn(&str) -> Vec<Range<Number>>) -> Number {
-    let blocks = input.split("\n\n").collect_vec();
-    let seed_ranges = seed_ranges(blocks[0]);
-    let maps = blocks[1..].iter().map(|&block| map(block)).collect_vec();
-    minimum_location(&maps, seed_ranges)
+const CATEGORIES: [&str; 8] = [
+    "seed",
+    "soil",
+    "fertilizer",
+    "water",
+    "light",
+    "temperature",
+    "humidity",
+    "location",
+];
+
+/// An almanac with up to `size` seed ranges and up to `size` lines per map, whose numbers are
+/// at most `10 * size` and whose source ranges within each map do not overlap.
+pub fn generate(rng: &mut SmallRng, size: usize) -> String {
+    let size = size.max(1);
+    let limit = 10 * size;
+    let seeds = (0..rng.random_range(1..=size))
+        .map(|_| {
+            let start = rng.random_range(0..limit);
+            format!("{start} {}", rng.random_range(1..=limit - start))
+        })
+        .join(" ");
+    let maps = CATEGORIES
+        .iter()
+        .tuple_windows()
+        .map(|(source, destination)| {
+            let number_of_lines = rng.random_range(1..=size);
+            let lines = (0..=limit)
+                .sample(rng, 2 * number_of_lines)
+                .into_iter()
+                .sorted_unstable()
+                .tuples()
+                .map(|(start, end)| {
+                    format!("{} {start} {}", rng.random_range(0..limit), end - start)
+                })
+                .join("\n");
+            format!("{source}-to-{destination} map:\n{lines}")
+        });
+    format!("seeds: {seeds}\n\n{}\n", maps.format("\n\n"))
 }
 
-fn minimum_location(maps: &[Map], seed_ranges: Vec<Range<Number>>) -> Number {
-    seed_ranges
-        .into_iter()
-        .flat_map(|seed_range| location(maps, seed_range))
-        .map(|location_range| location_range.start)
-        .min()
-        .expect("there should be at least one seed range")
+fn minimum_location_from_input(input: &str, seeds: fn(&str) -> Seeds) -> Number {
+    let blocks = input.split("\n\n").collect_vec();
+    let seeds = seeds(blocks[0]);
+    let maps = blocks[1..].iter().map(|&block| map(block)).collect_vec();
+    minimum_location(&maps, &seeds)
 }
 
-fn location(maps: &[Map], seed_range: Range<Number>) -> Vec<Range<Number>> {
-    let mut number_ranges = Vec::from([seed_range]);
-    for map in maps {
-        number_ranges = number_ranges
-            .into_iter()
-            .flat_map(|number_range| mapped_number_range(map, number_range))
-            .collect_vec();
-    }
-    number_ranges
-}
-
-fn mapped_number_range(map: &Map, number_range: Range<Number>) -> Vec<Range<Number>> {
-    let predcecessor = map
-        .range(0..=number_range.start)
-        .next_back()
-        .expect("every map should have a map range starting at zero");
-    let mut subranges_ends_and_translations = Vec::from([(number_range.start, *predcecessor.1)]);
-    for (&start, &translation) in map.range(number_range.clone()) {
-        subranges_ends_and_translations.push((start, translation));
-    }
-    subranges_ends_and_translations.push((number_range.end, 0)); // translation irrelevant
-    subranges_ends_and_translations.dedup_by_key(|(endpoint, _)| *endpoint);
-
-    subranges_ends_and_translations
-        .windows(2)
-        .map(|pair| {
-            let translation = pair[0].1;
-            pair[0].0 + translation..pair[1].0 + translation
-        })
-        .collect_vec()
+fn minimum_location(maps: &[Map], seeds: &Seeds) -> Number {
+    let seed_to_location = maps
+        .iter()
+        .fold(Map::new(), |composition, map| composition.compose(map));
+    seed_to_location
+        .image(seeds)
+        .intervals()
+        .first()
+        .expect("there should be at least one seed")
+        .start
 }
 
 fn map(block: &str) -> Map {
-    let mut numbers = map_numbers(block);
-    numbers.sort_unstable_by_key(|&[_, source, _]| source);
-
-    let mut map = BTreeMap::from([(0, 0)]);
-    for [destination, source, length] in numbers {
-        map.insert(source, destination - source);
-        map.insert(source + length, 0);
+    let mut map = Map::new();
+    for [destination, source, length] in map_numbers(block) {
+        map.insert(source..source + length, destination - source);
     }
     map
 }
@@ -88,28 +98,67 @@ fn map_numbers(block: &str) -> Vec<[Number; 3]> {
         .collect_vec()
 }
 
-fn singleton_seed_ranges(seeds: &str) -> Vec<Range<Number>> {
+fn singleton_seed_ranges(seeds: &str) -> Seeds {
     isizes(seeds)
         .into_iter()
         .map(|number| number..number + 1)
-        .collect_vec()
+        .collect()
 }
 
-fn seed_ranges(seeds: &str) -> Vec<Range<Number>> {
+fn seed_ranges(seeds: &str) -> Seeds {
     isizes(seeds)
         .chunks(2)
         .map(|pair| pair[0]..pair[0] + pair[1])
-        .collect_vec()
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
-    use infrastructure::{Input, Puzzle};
+    use infrastructure::{Input, Puzzle, test};
 
+    use super::*;
     use crate::tests::test_on_input;
 
     const DAY: usize = 5;
 
+    fn minimum_location_seed_by_seed(input: &str, seeds: fn(&str) -> Seeds) -> String {
+        let blocks = input.split("\n\n").collect_vec();
+        let maps = blocks[1..]
+            .iter()
+            .map(|&block| map_numbers(block))
+            .collect_vec();
+        let location = |seed| {
+            maps.iter().fold(seed, |number, map| {
+                map.iter()
+                    .find(|&&[_, source, length]| (source..source + length).contains(&number))
+                    .map_or(number, |&[destination, source, _]| {
+                        number - source + destination
+                    })
+            })
+        };
+        let seeds = seeds(blocks[0]);
+        seeds
+            .intervals()
+            .iter()
+            .flat_map(Clone::clone)
+            .map(location)
+            .min()
+            .expect("there should be at least one seed")
+            .to_string()
+    }
+
+    #[test]
+    fn answers_generated() {
+        let first_answer_seed_by_seed =
+            |input: &str| minimum_location_seed_by_seed(input, singleton_seed_ranges);
+        let solutions = [first_answer, first_answer_seed_by_seed];
+        test::agree_on_generated_inputs(&generate, 8, 1000, &solutions);
+        let second_answer_seed_by_seed =
+            |input: &str| minimum_location_seed_by_seed(input, seed_ranges);
+        let solutions = [second_answer, second_answer_seed_by_seed];
+        test::agree_on_generated_inputs(&generate, 8, 1000, &solutions);
+    }
+
     #[test]
     fn first_answer_example() {
         test_on_input(DAY, Puzzle::First, Input::Example(0), 35);
@@ -1,11 +1,8 @@
-use std::ops::{Add, Sub};
+use shared::{
+    interpolation::{extrapolate_backward, extrapolate_forward},
+    string::isizes,
+};
 
-use itertools::Itertools;
-
-use shared::string::isizes;
-
-type Combination = fn(Number, Number) -> Number;
-type Prediction = fn(Number, Number) -> Number;
 type Number = isize;
 
 pub fn first_answer(input: &str) -> String {
@@ -24,32 +21,13 @@ fn sum_of_predictions(input: &str, reverse: bool) -> Number {
 }
 
 fn prediction(history: &str, reverse: bool) -> Number {
-    let mut history = isizes(history);
-    if reverse {
-        history.reverse();
-    }
-    if reverse {
-        extrapolation(&history, Number::sub, Number::sub)
+    let history = isizes(history);
+    let prediction = if reverse {
+        extrapolate_backward(&history)
     } else {
-        extrapolation(&history, |left, right| right - left, Number::add)
-    }
-}
-
-fn extrapolation(history: &[Number], combination: Combination, prediction: Prediction) -> Number {
-    if history.iter().all(|&number| number == 0) {
-        return 0;
-    }
-    let &last_number = history.last().expect("history should not be empty");
-    let succeeding_row = row(history, combination);
-    let succeeding_prediction = extrapolation(&succeeding_row, combination, prediction);
-    prediction(last_number, succeeding_prediction)
-}
-
-fn row(preceding_row: &[Number], combination: Combination) -> Vec<Number> {
-    preceding_row
-        .windows(2)
-        .map(|pair| combination(pair[0], pair[1]))
-        .collect_vec()
+        extrapolate_forward(&history)
+    };
+    prediction.expect("history should not be empty")
 }
 
 #[cfg(test)]
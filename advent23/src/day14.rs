@@ -1,7 +1,9 @@
 use ahash::AHashMap;
 use itertools::Itertools;
+use rand::rngs::SmallRng;
 
 use shared::{
+    generators,
     grid::{self, Direction, Grid, Position},
     vector::Vector,
 };
@@ -22,6 +24,12 @@ pub fn second_answer(input: &str) -> String {
     total_load(&platform).to_string()
 }
 
+pub fn generate(rng: &mut SmallRng, size: usize) -> String {
+    let dimensions = generators::dimensions(rng, size);
+    let elements = [('O', 1), ('#', 1), ('.', 2)];
+    generators::weighted_grid(rng, dimensions, &elements).to_string()
+}
+
 fn total_load(platform: &Platform) -> usize {
     (1..)
         .zip(platform.rows().rev())
@@ -35,7 +43,14 @@ fn cycle_start_and_length(platform: &mut Platform) -> (usize, usize) {
         previous.insert(platform.clone(), number_of_cycles);
         cycles(platform, 1);
         if let Some(&cycle_start) = previous.get(platform) {
-            return (cycle_start, 1 + number_of_cycles - cycle_start);
+            let cycle_length = 1 + number_of_cycles - cycle_start;
+            infrastructure::trace::event("cycle detected", |event| {
+                event
+                    .field("start", cycle_start)
+                    .field("length", cycle_length)
+                    .field("platform", &*platform)
+            });
+            return (cycle_start, cycle_length);
         }
     }
     unreachable!("cycle should occur");
@@ -81,13 +96,36 @@ fn sorted_round_rock_positions(platform: &Platform, direction: Direction) -> Vec
 
 #[cfg(test)]
 mod tests {
-    use infrastructure::{Input, Puzzle};
+    use infrastructure::{Input, Puzzle, test, trace};
 
     use super::*;
     use crate::tests::{input, test_on_input};
 
     const DAY: usize = 14;
 
+    fn first_answer_rolling_one_step_at_a_time(input: &str) -> String {
+        let mut platform = Platform::from(input);
+        let mut has_rolled = true;
+        while has_rolled {
+            has_rolled = false;
+            for (position, _) in platform.clone().iter_row_major() {
+                let next_position = position.add(grid::NORTH);
+                if platform[position] == 'O' && platform.get(next_position) == Some(&'.') {
+                    platform[position] = '.';
+                    platform[next_position] = 'O';
+                    has_rolled = true;
+                }
+            }
+        }
+        total_load(&platform).to_string()
+    }
+
+    #[test]
+    fn first_answer_generated() {
+        let solutions = [first_answer, first_answer_rolling_one_step_at_a_time];
+        test::agree_on_generated_inputs(&generate, 12, 1000, &solutions);
+    }
+
     #[test]
     fn first_answer_example() {
         test_on_input(DAY, Puzzle::First, Input::Example(0), 136);
@@ -162,6 +200,15 @@ mod tests {
         test_platform_after_cycles(Input::Example(0), 3, expected);
     }
 
+    #[test]
+    fn cycle_traced() {
+        let (_, events) = trace::capture(|| second_answer(&input(DAY, Input::Example(0))));
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].name(), "cycle detected");
+        assert_eq!(events[0].get("start"), Some("3"));
+        assert_eq!(events[0].get("length"), Some("7"));
+    }
+
     #[test]
     fn second_answer_example() {
         test_on_input(DAY, Puzzle::Second, Input::Example(0), 64);
@@ -1,89 +1,142 @@
+use anyhow::{Context, anyhow};
 use easy_cast::Cast;
 
-type HashMap<'label> = Vec<Bucket<'label>>;
 type Bucket<'label> = Vec<(&'label str, FocalLength)>;
-type FocalLength = usize;
-type Hash = usize;
+pub type FocalLength = usize;
+pub type Hash = usize;
+
+/// A step of the initialization sequence: what to do with the lens of the label.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Step<'label> {
+    pub label: &'label str,
+    pub operation: Operation,
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-enum Operation {
+pub enum Operation {
     Insertion(FocalLength),
     Removal,
 }
 
-pub fn first_answer(input: &str) -> String {
-    steps(input).map(hash).sum::<usize>().to_string()
+impl<'label> Step<'label> {
+    /// Parses a step like `rn=1` or `cm-`.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the step is neither a label followed by `=` and a focal length nor a label
+    /// followed by `-`.
+    pub fn parse(step: &'label str) -> anyhow::Result<Self> {
+        let operation_index = step
+            .find(['-', '='])
+            .with_context(|| format!("step '{step}' should contain '-' or '='"))?;
+        let (label, operation) = step.split_at(operation_index);
+        let operation = match operation.split_at(1) {
+            ("-", "") => Operation::Removal,
+            ("=", focal_length) => Operation::Insertion(
+                focal_length
+                    .parse()
+                    .map_err(|_| anyhow!("focal length '{focal_length}' should be a number"))?,
+            ),
+            _ => {
+                return Err(anyhow!(
+                    "operation of step '{step}' should be '-' or '=' and a number"
+                ));
+            }
+        };
+        Ok(Self { label, operation })
+    }
 }
 
-pub fn second_answer(input: &str) -> String {
-    sum_of_focusing_powers(&hash_map(operations(input))).to_string()
+/// The 256 boxes of lenses that the Holiday ASCII String Helper Manual Arrangement Procedure
+/// arranges, each box holding lenses in the order they were inserted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hashmap<'label> {
+    boxes: Vec<Bucket<'label>>,
 }
 
-fn sum_of_focusing_powers(hash_map: &HashMap) -> usize {
-    let mut sum = 0;
-    for (bucket_number, bucket) in (1..).zip(hash_map) {
-        for (lens_number, (_, focal_length)) in (1..).zip(bucket) {
-            sum += bucket_number * lens_number * *focal_length;
+impl Default for Hashmap<'_> {
+    fn default() -> Self {
+        Self {
+            boxes: vec![Bucket::new(); 256],
         }
     }
-    sum
 }
 
-fn hash_map<'label>(steps: impl IntoIterator<Item = (&'label str, Operation)>) -> HashMap<'label> {
-    let mut hash_map: HashMap = vec![Bucket::new(); 256];
-    for (label, operation) in steps {
-        let bucket = &mut hash_map[hash(label)];
-        match operation {
-            Operation::Insertion(focal_length) => {
-                if let Some(position) = position(bucket, label) {
-                    bucket[position].1 = focal_length;
-                } else {
-                    bucket.push((label, focal_length));
-                }
+impl<'label> Hashmap<'label> {
+    /// Inserts, replaces or removes the lens of the step in the box that its label hashes to.
+    pub fn apply(&mut self, Step { label, operation }: Step<'label>) {
+        let bucket = &mut self.boxes[hash(label)];
+        let position = bucket
+            .iter()
+            .position(|(label_in_map, _)| *label_in_map == label);
+        match (operation, position) {
+            (Operation::Insertion(focal_length), Some(position)) => {
+                bucket[position].1 = focal_length;
             }
-            Operation::Removal => {
-                if let Some(position) = position(bucket, label) {
-                    bucket.remove(position);
-                }
+            (Operation::Insertion(focal_length), None) => bucket.push((label, focal_length)),
+            (Operation::Removal, Some(position)) => {
+                bucket.remove(position);
             }
+            (Operation::Removal, None) => (),
         }
     }
-    hash_map
+
+    /// The labels and focal lengths of the lenses in the box, front to back.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `box_number` is not below 256.
+    #[must_use]
+    pub fn lenses(&self, box_number: Hash) -> &[(&'label str, FocalLength)] {
+        &self.boxes[box_number]
+    }
+
+    /// The sum of the products of the one-based box number, the one-based slot number and the
+    /// focal length of every lens.
+    #[must_use]
+    pub fn focusing_power(&self) -> usize {
+        let mut sum = 0;
+        for (box_number, bucket) in (1..).zip(&self.boxes) {
+            for (lens_number, (_, focal_length)) in (1..).zip(bucket) {
+                sum += box_number * lens_number * *focal_length;
+            }
+        }
+        sum
+    }
 }
 
-fn hash(str: &str) -> Hash {
-    str.as_bytes()
-        .iter()
-        .fold(0_u8, |hash, &char| hash.wrapping_add(char).wrapping_mul(17))
-        .cast()
+impl<'label> FromIterator<Step<'label>> for Hashmap<'label> {
+    fn from_iter<T: IntoIterator<Item = Step<'label>>>(steps: T) -> Self {
+        let mut hashmap = Self::default();
+        for step in steps {
+            hashmap.apply(step);
+        }
+        hashmap
+    }
 }
 
-fn position(bucket: &Bucket, label: &str) -> Option<usize> {
-    bucket
-        .iter()
-        .position(|(label_in_map, _)| *label_in_map == label)
+pub fn first_answer(input: &str) -> String {
+    steps(input).map(hash).sum::<usize>().to_string()
 }
 
-fn operations(sequence: &str) -> impl Iterator<Item = (&str, Operation)> {
-    steps(sequence).map(operation)
+pub fn second_answer(input: &str) -> String {
+    operations(input)
+        .collect::<Hashmap>()
+        .focusing_power()
+        .to_string()
 }
 
-fn operation(step: &str) -> (&str, Operation) {
-    let operation_index = step
-        .find(['-', '='])
-        .expect("operation should contain '-' or '='");
-    let (label, operation) = (&step[0..operation_index], &step[operation_index..]);
-    let operation = match &operation[..1] {
-        "-" => Operation::Removal,
-        "=" => {
-            let focal_length = operation[1..]
-                .parse()
-                .expect("focal length should be numerical");
-            Operation::Insertion(focal_length)
-        }
-        _ => panic!("operation should be '-' or '='"),
-    };
-    (label, operation)
+/// The Holiday ASCII String Helper algorithm, which hashes a string to a number below 256.
+#[must_use]
+pub fn hash(str: &str) -> Hash {
+    str.as_bytes()
+        .iter()
+        .fold(0_u8, |hash, &char| hash.wrapping_add(char).wrapping_mul(17))
+        .cast()
+}
+
+fn operations(sequence: &str) -> impl Iterator<Item = Step<'_>> {
+    steps(sequence).map(|step| Step::parse(step).expect("step should be valid"))
 }
 
 fn steps(sequence: &str) -> impl Iterator<Item = &str> {
@@ -94,6 +147,7 @@ fn steps(sequence: &str) -> impl Iterator<Item = &str> {
 mod tests {
     use infrastructure::{Input, Puzzle};
 
+    use super::*;
     use crate::tests::test_on_input;
 
     const DAY: usize = 15;
@@ -117,4 +171,20 @@ mod tests {
     fn second_answer_input() {
         test_on_input(DAY, Puzzle::Second, Input::PuzzleInput, 200_277);
     }
+
+    #[test]
+    fn hashmap() {
+        assert_eq!(hash("HASH"), 52);
+        let steps = ["rn=1", "cm-", "qp=3", "cm=2", "qp-", "pc=4"]
+            .map(|step| Step::parse(step).expect("step should be valid"));
+        let hashmap: Hashmap = steps.into_iter().collect();
+        assert_eq!(hashmap.lenses(0), [("rn", 1), ("cm", 2)]);
+        assert_eq!(hashmap.lenses(3), [("pc", 4)]);
+        for invalid in ["rn", "rn=", "rn=x", "cm-1"] {
+            assert!(
+                Step::parse(invalid).is_err(),
+                "step '{invalid}' should be invalid"
+            );
+        }
+    }
 }
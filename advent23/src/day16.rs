@@ -24,23 +24,9 @@ pub fn second_answer(input: &str) -> String {
 fn number_of_energized_tiles(grid: &Grid<char>, initial_beam: Beam) -> usize {
     let mut energized: BTreeSet<Beam> = BTreeSet::new();
     let mut beams: Vec<Beam> = Vec::from([initial_beam]);
-    while let Some(beam @ (direction, position)) = beams.pop() {
-        let next_directions = match grid.get(position) {
-            None => vec![],
-            Some(_) if !energized.insert(beam) => vec![],
-            Some('/') => vec![reflection_north_east_diagonal(direction)],
-            Some('\\') => vec![reflection_north_west_diagonal(direction)],
-            Some('|') if [grid::WEST, grid::EAST].contains(&direction) => {
-                vec![grid::NORTH, grid::SOUTH]
-            }
-            Some('-') if [grid::NORTH, grid::SOUTH].contains(&direction) => {
-                vec![grid::WEST, grid::EAST]
-            }
-            _ => vec![direction],
-        };
-        for next_direction in next_directions {
-            let next_position = position.add(next_direction);
-            beams.push((next_direction, next_position));
+    while let Some(beam @ (_, position)) = beams.pop() {
+        if grid.get(position).is_some() && energized.insert(beam) {
+            beams.extend(next_beams(grid, beam));
         }
     }
 
@@ -51,6 +37,19 @@ fn number_of_energized_tiles(grid: &Grid<char>, initial_beam: Beam) -> usize {
         .len()
 }
 
+fn next_beams(grid: &Grid<char>, (direction, position): Beam) -> impl Iterator<Item = Beam> {
+    let next_directions = match grid[position] {
+        '/' => vec![reflection_north_east_diagonal(direction)],
+        '\\' => vec![reflection_north_west_diagonal(direction)],
+        '|' if [grid::WEST, grid::EAST].contains(&direction) => vec![grid::NORTH, grid::SOUTH],
+        '-' if [grid::NORTH, grid::SOUTH].contains(&direction) => vec![grid::WEST, grid::EAST],
+        _ => vec![direction],
+    };
+    next_directions
+        .into_iter()
+        .map(move |next_direction| (next_direction, position.add(next_direction)))
+}
+
 fn reflection_north_east_diagonal([row, column]: Direction) -> Direction {
     [-column, -row]
 }
@@ -86,9 +85,13 @@ fn beams_from_edges(grid: &Grid<char>) -> Vec<Beam> {
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use infrastructure::{Input, Puzzle};
+    use shared::render::{self, Color};
 
-    use crate::tests::test_on_input;
+    use super::*;
+    use crate::tests::{input, test_on_input};
 
     const DAY: usize = 16;
 
@@ -111,4 +114,41 @@ mod tests {
     fn second_answer_input() {
         test_on_input(DAY, Puzzle::Second, Input::PuzzleInput, 8754);
     }
+
+    #[test]
+    #[ignore = "writes an animation to the temporary directory"]
+    fn light_beams_animation() {
+        let grid = Grid::from(input(DAY, Input::Example(0)).as_str());
+        let mut energized = BTreeSet::new();
+        let mut beams = vec![(grid::EAST, [0, 0])];
+        let mut frames = vec![];
+        while !beams.is_empty() {
+            beams.retain(|&beam @ (_, position)| {
+                grid.get(position).is_some() && energized.insert(beam)
+            });
+            let energized_positions: BTreeSet<Position> =
+                energized.iter().map(|&(_, position)| position).collect();
+            let frame = render::grid(
+                &grid.map(|position, &tile| (position, tile)),
+                |&(position, tile)| tile_color(tile, energized_positions.contains(&position)),
+            );
+            frames.push(render::scaled(&frame, 16));
+            beams = beams
+                .into_iter()
+                .flat_map(|beam| next_beams(&grid, beam))
+                .collect();
+        }
+        let path = std::env::temp_dir().join("advent23-16-light-beams.gif");
+        render::save_animation(frames, Duration::from_millis(100), path)
+            .expect("animation should be saved");
+    }
+
+    fn tile_color(tile: char, is_energized: bool) -> Color {
+        match (tile, is_energized) {
+            ('.', false) => render::BLACK,
+            ('.', true) => [u8::MAX, 200, 0],
+            (_, false) => [100, 100, 100],
+            (_, true) => render::WHITE,
+        }
+    }
 }
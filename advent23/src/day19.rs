@@ -3,7 +3,7 @@ use std::{cmp::Ordering, ops::Range};
 use ahash::AHashMap;
 use itertools::Itertools;
 
-use shared::string::usizes;
+use shared::{interval::BoxSet, string::usizes};
 
 type Workflows<'input> = AHashMap<Name<'input>, Rules<'input>>;
 type Workflow<'input> = (Name<'input>, Rules<'input>);
@@ -14,6 +14,7 @@ type Rating = usize;
 type Name<'input> = &'input str;
 type Part = [Rating; 4];
 
+type AcceptableParts = BoxSet<Rating, 4>;
 type RatingRanges = [RatingRange; 4];
 type RatingRange = Range<Rating>;
 
@@ -32,12 +33,14 @@ pub fn second_answer(input: &str) -> String {
         .split_once("\n\n")
         .expect("input should contain workflows and parts separated by an empty line");
     let acceptable_rating_ranges = acceptable_rating_ranges(workflows);
-    number_of_combinations(&acceptable_rating_ranges).to_string()
+    acceptable_rating_ranges.volume().to_string()
 }
 
-fn acceptable_rating_ranges(str: &str) -> Vec<RatingRanges> {
+fn acceptable_rating_ranges(str: &str) -> AcceptableParts {
     let workflows = workflows(str);
     acceptable_ranges(&workflows, allowed_ranges(), "in")
+        .into_iter()
+        .collect()
 }
 
 fn allowed_ranges() -> RatingRanges {
@@ -94,39 +97,18 @@ fn split_range(range: RatingRange, comparison: Ordering, limit: Rating) -> [Rang
 }
 
 fn acceptable_parts(
-    acceptable_rating_ranges: &[RatingRanges],
+    acceptable_rating_ranges: &AcceptableParts,
     parts: Vec<Part>,
 ) -> impl Iterator<Item = Part> + '_ {
     parts
         .into_iter()
-        .filter(|&part| is_part_acceptable(acceptable_rating_ranges, part))
-}
-
-fn is_part_acceptable(acceptable_rating_ranges: &[RatingRanges], part: Part) -> bool {
-    acceptable_rating_ranges.iter().any(|ranges| {
-        ranges
-            .iter()
-            .zip(part)
-            .all(|(range, rating)| range.contains(&rating))
-    })
+        .filter(|part| acceptable_rating_ranges.contains(part))
 }
 
 fn sum_of_ratings(parts: impl Iterator<Item = Part>) -> Rating {
     parts.map(|part| part.into_iter().sum::<Rating>()).sum()
 }
 
-fn number_of_combinations(ranges: &[RatingRanges]) -> usize {
-    ranges
-        .iter()
-        .map(|ranges| {
-            ranges
-                .iter()
-                .map(std::iter::ExactSizeIterator::len)
-                .product::<usize>()
-        })
-        .sum::<usize>()
-}
-
 fn workflows(str: &str) -> Workflows<'_> {
     str.lines().map(workflow).collect()
 }
@@ -2,16 +2,22 @@ use std::mem;
 
 use ahash::AHashSet;
 use easy_cast::Conv;
-use shared::grid::{self, Grid, Position};
+use infrastructure::parameters::Parameter;
+use itertools::Itertools;
+use shared::{
+    grid::{self, Grid, Position},
+    interpolation::fit_sequence,
+};
 
 type Garden = Grid<char>;
 type Parity = usize;
 
-const EVEN: Parity = 0;
-const ODD: Parity = 1;
+const FIRST_NUMBER_OF_STEPS: Parameter<usize> = Parameter::new("first_number_of_steps", 64, 6);
+pub const PARAMETERS: &[&str] = &[FIRST_NUMBER_OF_STEPS.key()];
 
 pub fn first_answer(input: &str) -> String {
-    number_of_reachable_plots_in_exactly(&Garden::from(input), 64).to_string()
+    number_of_reachable_plots_in_exactly(&Garden::from(input), FIRST_NUMBER_OF_STEPS.get())
+        .to_string()
 }
 
 pub fn second_answer(input: &str) -> String {
@@ -19,13 +25,11 @@ pub fn second_answer(input: &str) -> String {
 
     let garden = Garden::from(input);
     debug_assert_repeating_garden_properties(&garden, NUMBER_OF_STEPS);
-    let repeats = NUMBER_OF_STEPS / size(&garden);
-
-    let number_of_reachable_plots = number_of_reachable_plots_full(&garden, repeats)
-        + number_of_reachable_plots_eighths(&garden, repeats)
-        + number_of_reachable_plots_seven_eighths(&garden, repeats)
-        + number_of_reachable_plots_tips(&garden);
-    number_of_reachable_plots.to_string()
+    let [repeats, remainder] = [
+        NUMBER_OF_STEPS / size(&garden),
+        NUMBER_OF_STEPS % size(&garden),
+    ];
+    number_of_reachable_plots_after_repeats(&garden, remainder, repeats).to_string()
 }
 
 fn debug_assert_repeating_garden_properties(garden: &Garden, number_of_steps: usize) {
@@ -111,45 +115,62 @@ fn number_of_reachable_plots(
     number_of_reachable_garden_plots
 }
 
-fn number_of_reachable_plots_full(garden: &Garden, repeats: usize) -> usize {
-    let even = number_of_reachable_plots(garden, [0, 0], EVEN, size(garden) * 2);
-    let even = repeats.pow(2) * even;
-
-    let odd = number_of_reachable_plots(garden, [0, 0], ODD, size(garden) * 2);
-    let odd = (repeats - 1).pow(2) * odd;
-
-    even + odd
-}
-
-fn number_of_reachable_plots_eighths(garden: &Garden, repeats: usize) -> usize {
-    let sum_of_four_distinct_eights: usize = garden
-        .corner_positions_clockwise()
-        .into_iter()
-        .map(|starting_plot| {
-            number_of_reachable_plots(garden, starting_plot, EVEN, size(garden) / 2)
-        })
-        .sum();
-    repeats * sum_of_four_distinct_eights
-}
+/// The number of plots reachable in exactly `remainder + repeats * size` steps on the garden
+/// repeated infinitely in every direction.
+///
+/// Every additional `size` steps reach one more ring of repeated gardens around those reached
+/// before. The number of gardens in a ring grows linearly, so the number of reachable plots grows
+/// quadratically in `repeats`. It is fitted to a few small numbers of repeats, verified, and then
+/// evaluated at `repeats`.
+fn number_of_reachable_plots_after_repeats(
+    garden: &Garden,
+    remainder: usize,
+    repeats: usize,
+) -> usize {
+    const NUMBER_OF_SAMPLES: usize = 4;
 
-fn number_of_reachable_plots_seven_eighths(garden: &Garden, repeats: usize) -> usize {
-    let number_of_steps = size(garden) - 1 + size(garden) / 2;
-    let sum_of_four_distinct_seven_eighths: usize = garden
-        .corner_positions_clockwise()
+    let numbers_of_steps = (0..NUMBER_OF_SAMPLES)
+        .map(|sample| remainder + sample * size(garden))
+        .collect_vec();
+    let samples = numbers_of_reachable_plots_on_repeating_garden(garden, &numbers_of_steps)
         .into_iter()
-        .map(|starting_plot| number_of_reachable_plots(garden, starting_plot, ODD, number_of_steps))
-        .sum();
-    (repeats - 1) * sum_of_four_distinct_seven_eighths
+        .map(i64::conv)
+        .collect_vec();
+    let sequence =
+        fit_sequence(&samples).expect("number of reachable plots should grow polynomially");
+    debug_assert_eq!(sequence.degree(), 2, "growth should be quadratic");
+    usize::conv(sequence.value(&i64::conv(repeats)))
 }
 
-fn number_of_reachable_plots_tips(garden: &Garden) -> usize {
-    garden
-        .edge_midpoints_clockwise()
-        .into_iter()
-        .map(|starting_plot| {
-            number_of_reachable_plots(garden, starting_plot, ODD, size(garden) - 1)
-        })
-        .sum()
+/// The numbers of plots reachable in exactly each of the increasing `numbers_of_steps` on the
+/// garden repeated infinitely in every direction.
+fn numbers_of_reachable_plots_on_repeating_garden(
+    garden: &Garden,
+    numbers_of_steps: &[usize],
+) -> Vec<usize> {
+    let bounds = garden.bounds();
+    let is_plot = |position| {
+        let position = bounds.wrap_along(bounds.wrap_along(position, 0), 1);
+        ['.', 'S'].contains(&garden[position])
+    };
+    let starting_plot = center(garden);
+    let mut explored = AHashSet::from([starting_plot]);
+    let mut frontier = vec![starting_plot];
+    let mut numbers_of_reachable_plots_by_parity = [0; 2];
+    let mut numbers_of_reachable_plots = vec![];
+    for number_of_steps in 0..=numbers_of_steps.last().copied().unwrap_or_default() {
+        numbers_of_reachable_plots_by_parity[number_of_steps % 2] += frontier.len();
+        if numbers_of_steps.contains(&number_of_steps) {
+            numbers_of_reachable_plots
+                .push(numbers_of_reachable_plots_by_parity[number_of_steps % 2]);
+        }
+        frontier = frontier
+            .into_iter()
+            .flat_map(grid::orthogonal_neighbors)
+            .filter(|&neighbor| is_plot(neighbor) && explored.insert(neighbor))
+            .collect();
+    }
+    numbers_of_reachable_plots
 }
 
 fn size(garden: &Garden) -> usize {
@@ -159,7 +180,7 @@ fn size(garden: &Garden) -> usize {
 
 #[cfg(test)]
 mod tests {
-    use infrastructure::{Input, Puzzle};
+    use infrastructure::{Input, Puzzle, test};
 
     use super::*;
     use crate::tests::{input, test_on_input};
@@ -168,10 +189,7 @@ mod tests {
 
     #[test]
     fn first_answer_example() {
-        let garden = Garden::from(input(DAY, Input::Example(0)));
-        let actual = number_of_reachable_plots_in_exactly(&garden, 6);
-        let expected = 16;
-        assert_eq!(actual, expected);
+        test_on_input(DAY, Puzzle::First, Input::Example(0), 16);
     }
 
     #[test]
@@ -198,34 +216,20 @@ mod tests {
     }
 
     #[test]
-    fn number_of_reachable_plots_full() {
-        let garden = Garden::from(input(DAY, Input::Example(1)));
-        let actual = super::number_of_reachable_plots_full(&garden, 2);
-        let expected = 24;
-        assert_eq!(actual, expected);
-    }
-
-    #[test]
-    fn number_of_reachable_plots_eighths() {
-        let garden = Garden::from(input(DAY, Input::Example(1)));
-        let actual = super::number_of_reachable_plots_eighths(&garden, 2);
-        let expected = 8;
-        assert_eq!(actual, expected);
-    }
-
-    #[test]
-    fn number_of_reachable_plots_seven_eighths() {
-        let garden = Garden::from(input(DAY, Input::Example(1)));
-        let actual = super::number_of_reachable_plots_seven_eighths(&garden, 2);
-        let expected = 16;
+    fn numbers_of_reachable_plots_on_repeating_garden() {
+        let garden = Garden::from(input(DAY, Input::Example(0)));
+        let actual =
+            super::numbers_of_reachable_plots_on_repeating_garden(&garden, &[6, 10, 50, 100, 500]);
+        let expected = [16, 50, 1594, 6536, 167_004];
         assert_eq!(actual, expected);
     }
 
     #[test]
-    fn number_of_reachable_plots_tips() {
+    fn number_of_reachable_plots_after_repeats() {
         let garden = Garden::from(input(DAY, Input::Example(1)));
-        let actual = super::number_of_reachable_plots_tips(&garden);
-        let expected = 16;
-        assert_eq!(actual, expected);
+        let function =
+            |repeats| super::number_of_reachable_plots_after_repeats(&garden, 1, repeats);
+        let cases = [(0, 4), (2, 64), (100, 302 * 302)];
+        test::cases(function, cases);
     }
 }
@@ -1,24 +1,36 @@
 use std::ops::RangeInclusive;
 
+use infrastructure::parameters::Parameter;
 use itertools::Itertools;
+use num_bigint::BigInt;
+use num_traits::Zero;
 
-use shared::{linear_equations::solution_set, vector::Vector};
+use shared::{
+    linear_equations::{integer_solution, solution_set},
+    rational::Rational,
+    vector::Vector,
+};
 
 type Hailstone = (Position, Velocity);
 type Position = [Coordinate; 3];
 type Velocity = [Coordinate; 3];
-type Coordinate = f64;
+type Coordinate = i128;
+type Number = Rational<BigInt>;
+
+const AREA_MIN: Parameter<Coordinate> = Parameter::new("area_min", 200_000_000_000_000, 7);
+const AREA_MAX: Parameter<Coordinate> = Parameter::new("area_max", 400_000_000_000_000, 27);
+pub const PARAMETERS: &[&str] = &[AREA_MIN.key(), AREA_MAX.key()];
 
 pub fn first_answer(input: &str) -> String {
     let hailstones = hailstones(input);
-    let area = 200_000_000_000_000.0..=400_000_000_000_000.0;
+    let area = AREA_MIN.get()..=AREA_MAX.get();
     number_of_intersections_in_test_area(area, &hailstones).to_string()
 }
 
 pub fn second_answer(input: &str) -> String {
     let hailstones = hailstones(input);
     let (position, _) = initial_position_and_velocity(&hailstones);
-    position.into_iter().sum::<Coordinate>().round().to_string()
+    position.into_iter().sum::<Coordinate>().to_string()
 }
 
 fn number_of_intersections_in_test_area(
@@ -29,33 +41,34 @@ fn number_of_intersections_in_test_area(
         .iter()
         .combinations(2)
         .filter_map(|pair| path_intersection(*pair[0], *pair[1]))
-        .filter(|&intersection| is_inside_area(area.clone(), intersection))
+        .filter(|intersection| is_inside_area(area.clone(), intersection))
         .count()
 }
 
-fn path_intersection((s0, v0): Hailstone, (s1, v1): Hailstone) -> Option<[Coordinate; 2]> {
+fn path_intersection((s0, v0): Hailstone, (s1, v1): Hailstone) -> Option<[Number; 2]> {
     const X: usize = 0;
     const Y: usize = 1;
-    let augmented_matrix: [[Coordinate; 3]; 2] = [
+    let augmented_matrix = [
         [v0[X], -v1[X], s1[X] - s0[X]],
         [v0[Y], -v1[Y], s1[Y] - s0[Y]],
-    ];
+    ]
+    .map(|row| row.map(number));
     match solution_set(augmented_matrix) {
         None => None,
         Some((origin, directions)) if directions.is_empty() => {
             let time = origin;
-            let collision_in_future_for_both_hailstones = time[0] >= 0.0 && time[1] >= 0.0;
+            let collision_in_future_for_both_hailstones =
+                time[0] >= Number::zero() && time[1] >= Number::zero();
             collision_in_future_for_both_hailstones
-                .then(|| [s0[X] + v0[X] * time[0], s0[Y] + v0[Y] * time[0]])
+                .then(|| [X, Y].map(|axis| number(s0[axis]) + number(v0[axis]) * time[0].clone()))
         }
         _ => panic!("hailstones should not have identical paths"),
     }
 }
 
-fn is_inside_area(range: RangeInclusive<Coordinate>, point: [Coordinate; 2]) -> bool {
-    point
-        .into_iter()
-        .all(|coordinate| range.contains(&coordinate))
+fn is_inside_area(range: RangeInclusive<Coordinate>, point: &[Number; 2]) -> bool {
+    let range = number(*range.start())..=number(*range.end());
+    point.iter().all(|coordinate| range.contains(coordinate))
 }
 
 /// Returns the initial position and velocity needed for the rock to hit every hailstone.
@@ -103,11 +116,20 @@ fn initial_position_and_velocity(hailstones: &[Hailstone]) -> (Position, Velocit
     let mut augmented_matrix: [[Coordinate; 7]; 6] = Default::default();
     augmented_matrix[0..3].copy_from_slice(&equations(hailstones[0], hailstones[1]));
     augmented_matrix[3..6].copy_from_slice(&equations(hailstones[0], hailstones[2]));
-    match solution_set(augmented_matrix) {
-        Some((origin, directions)) if directions.is_empty() => (
-            [origin[0], origin[1], origin[2]],
-            [origin[3], origin[4], origin[5]],
-        ),
+    match solution_set(augmented_matrix.map(|row| row.map(number))) {
+        Some((origin, directions)) if directions.is_empty() => {
+            let origin = integer_solution(&origin)
+                .expect("rock should have integer position and velocity")
+                .iter()
+                .map(|coordinate| {
+                    Coordinate::try_from(coordinate).expect("coordinate should fit in an i128")
+                })
+                .collect_vec();
+            (
+                [origin[0], origin[1], origin[2]],
+                [origin[3], origin[4], origin[5]],
+            )
+        }
         _ => panic!("linear equations should have exactly one solution"),
     }
 }
@@ -146,12 +168,17 @@ fn equations(left: Hailstone, right: Hailstone) -> [[Coordinate; 7]; 3] {
 /// ```
 fn coefficients(([sx, sy, sz], [vx, vy, vz]): Hailstone) -> [[Coordinate; 7]; 3] {
     [
-        [0.0, vz, -vy, 0.0, -sz, sy, vy * sz - vz * sy],
-        [-vz, 0.0, vx, sz, 0.0, -sx, vz * sx - vx * sz],
-        [vy, -vx, 0.0, -sy, sx, 0.0, vx * sy - vy * sx],
+        [0, vz, -vy, 0, -sz, sy, vy * sz - vz * sy],
+        [-vz, 0, vx, sz, 0, -sx, vz * sx - vx * sz],
+        [vy, -vx, 0, -sy, sx, 0, vx * sy - vy * sx],
     ]
 }
 
+/// Intermediate results of exact elimination outgrow any primitive integer.
+fn number(coordinate: Coordinate) -> Number {
+    Number::from(BigInt::from(coordinate))
+}
+
 fn hailstones(input: &str) -> Vec<Hailstone> {
     input.lines().map(hailstone).collect_vec()
 }
@@ -177,16 +204,13 @@ fn coordinates(str: &str) -> [Coordinate; 3] {
 mod tests {
     use infrastructure::{Input, Puzzle};
 
-    use super::*;
-    use crate::tests::{input, test_on_input};
+    use crate::tests::test_on_input;
 
     const DAY: usize = 24;
 
     #[test]
     fn first_answer_example() {
-        let hailstones = hailstones(&input(DAY, Input::Example(0)));
-        let number_of_intersections = number_of_intersections_in_test_area(7.0..=27.0, &hailstones);
-        assert_eq!(number_of_intersections, 2);
+        test_on_input(DAY, Puzzle::First, Input::Example(0), 2);
     }
 
     #[test]
@@ -211,12 +235,12 @@ mod tests {
 
     #[test]
     fn coefficients() {
-        let hailstone = ([20.0, 19.0, 15.0], [1.0, -5.0, -3.0]);
+        let hailstone = ([20, 19, 15], [1, -5, -3]);
         let actual = super::coefficients(hailstone);
         let expected = [
-            [0.0, -3.0, 5.0, 0.0, -15.0, 19.0, -18.0],
-            [3.0, 0.0, 1.0, 15.0, 0.0, -20.0, -75.0],
-            [-5.0, -1.0, 0.0, -19.0, 20.0, 0.0, 119.0],
+            [0, -3, 5, 0, -15, 19, -18],
+            [3, 0, 1, 15, 0, -20, -75],
+            [-5, -1, 0, -19, 20, 0, 119],
         ];
         assert_eq!(actual, expected);
     }
@@ -1,7 +1,17 @@
+use std::{collections::BinaryHeap, mem};
+
 use ahash::{AHashMap, AHashSet};
-use rand::{SeedableRng, rngs::SmallRng, seq::IteratorRandom};
+use itertools::Itertools;
+use rand::{
+    SeedableRng,
+    rngs::SmallRng,
+    seq::{IteratorRandom, SliceRandom},
+};
 
-use shared::search::{Exploration, shortest_path};
+use shared::{
+    graph::DisjointSets,
+    search::{Exploration, shortest_path},
+};
 
 type Apparatus<'input> = AHashMap<Component<'input>, AHashSet<Component<'input>>>;
 type Connection<'input> = [Component<'input>; 2];
@@ -19,6 +29,39 @@ pub fn second_answer(_input: &str) -> String {
     "There is no second puzzle on the 25th. Merry Christmas!".to_owned()
 }
 
+/// Contracts randomly shuffled connections as Kruskal's algorithm would, until two groups remain,
+/// and tries again until only three connections run between them.
+pub fn first_answer_by_kruskal(input: &str) -> String {
+    let apparatus = apparatus(input);
+    let (number_of_components, mut connections) = numbered_connections(&apparatus);
+    let mut rng = SmallRng::from_seed([0; 32]);
+    loop {
+        connections.shuffle(&mut rng);
+        let mut groups = DisjointSets::new(number_of_components);
+        for &[from, to] in &connections {
+            if groups.number_of_sets() == 2 {
+                break;
+            }
+            groups.union(from, to);
+        }
+        let number_of_cut_connections = connections
+            .iter()
+            .filter(|&&[from, to]| groups.find(from) != groups.find(to))
+            .count();
+        if number_of_cut_connections == 3 {
+            let group_size = groups.size(0);
+            return (group_size * (number_of_components - group_size)).to_string();
+        }
+    }
+}
+
+pub fn first_answer_by_stoer_wagner(input: &str) -> String {
+    let apparatus = apparatus(input);
+    let (number_of_components, connections) = numbered_connections(&apparatus);
+    let group_size = minimum_cut_group_size(number_of_components, &connections);
+    (group_size * (number_of_components - group_size)).to_string()
+}
+
 fn disconnected_group_sizes(apparatus: &Apparatus) -> [usize; 2] {
     let mut rng = SmallRng::from_seed([0; 32]);
     loop {
@@ -65,6 +108,76 @@ fn group_size(apparatus: &Apparatus, paths: &AHashSet<Connection>, component: Co
     exploration.explored().len()
 }
 
+/// The number of components and the connections between them, with the components numbered in
+/// sorted order.
+fn numbered_connections(apparatus: &Apparatus) -> (usize, Vec<[usize; 2]>) {
+    let numbers: AHashMap<_, _> = apparatus
+        .keys()
+        .sorted_unstable()
+        .enumerate()
+        .map(|(number, &component)| (component, number))
+        .collect();
+    let connections = apparatus
+        .iter()
+        .flat_map(|(from, tos)| tos.iter().map(|to| [numbers[from], numbers[to]]))
+        .filter(|[from, to]| from < to)
+        .sorted_unstable()
+        .collect_vec();
+    (numbers.len(), connections)
+}
+
+/// The size of one of the two groups that a minimum cut separates, found with the Stoer–Wagner
+/// algorithm. Each phase adds the vertices in order of how strongly they are connected to the ones
+/// added before, so that no cut separating the last two vertices is lighter than the one around
+/// the last vertex alone. Merging the last two vertices then keeps all other cuts.
+fn minimum_cut_group_size(number_of_vertices: usize, edges: &[[usize; 2]]) -> usize {
+    let mut weights = vec![AHashMap::new(); number_of_vertices];
+    for &[from, to] in edges {
+        *weights[from].entry(to).or_insert(0) += 1;
+        *weights[to].entry(from).or_insert(0) += 1;
+    }
+    let mut group_sizes = vec![1; number_of_vertices];
+    let mut is_merged = vec![false; number_of_vertices];
+    let mut minimum_cut = (usize::MAX, 0);
+    for _ in 1..number_of_vertices {
+        let start = is_merged
+            .iter()
+            .position(|&is_merged| !is_merged)
+            .expect("at least two vertices should be left");
+        let mut connectivities = vec![0; number_of_vertices];
+        let mut is_added = is_merged.clone();
+        let mut frontier = BinaryHeap::from([(0, start)]);
+        let [mut second_to_last, mut last] = [start; 2];
+        while let Some((connectivity, vertex)) = frontier.pop() {
+            if is_added[vertex] || connectivity < connectivities[vertex] {
+                continue;
+            }
+            is_added[vertex] = true;
+            [second_to_last, last] = [last, vertex];
+            for (&neighbor, &weight) in &weights[vertex] {
+                if !is_added[neighbor] {
+                    connectivities[neighbor] += weight;
+                    frontier.push((connectivities[neighbor], neighbor));
+                }
+            }
+        }
+        if connectivities[last] < minimum_cut.0 {
+            minimum_cut = (connectivities[last], group_sizes[last]);
+        }
+
+        for (neighbor, weight) in mem::take(&mut weights[last]) {
+            weights[neighbor].remove(&last);
+            if neighbor != second_to_last {
+                *weights[neighbor].entry(second_to_last).or_insert(0) += weight;
+                *weights[second_to_last].entry(neighbor).or_insert(0) += weight;
+            }
+        }
+        group_sizes[second_to_last] += group_sizes[last];
+        is_merged[last] = true;
+    }
+    minimum_cut.1
+}
+
 fn apparatus(input: &str) -> Apparatus<'_> {
     let mut apparatus = AHashMap::new();
     let mut add_connection = |from, to| {
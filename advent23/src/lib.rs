@@ -1,3 +1,5 @@
+use infrastructure::{Puzzle, variants::Variant};
+
 mod day01;
 mod day02;
 mod day03;
@@ -24,40 +26,67 @@ mod day23;
 mod day24;
 mod day25;
 
-const SOLUTIONS: &[[infrastructure::Solution; 2]] = &[
-    [day01::first_answer, day01::second_answer],
-    [day02::first_answer, day02::second_answer],
-    [day03::first_answer, day03::second_answer],
-    [day04::first_answer, day04::second_answer],
-    [day05::first_answer, day05::second_answer],
-    [day06::first_answer, day06::second_answer],
-    [day07::first_answer, day07::second_answer],
-    [day08::first_answer, day08::second_answer],
-    [day09::first_answer, day09::second_answer],
-    [day10::first_answer, day10::second_answer],
-    [day11::first_answer, day11::second_answer],
-    [day12::first_answer, day12::second_answer],
-    [day13::first_answer, day13::second_answer],
-    [day14::first_answer, day14::second_answer],
-    [day15::first_answer, day15::second_answer],
-    [day16::first_answer, day16::second_answer],
-    [day17::first_answer, day17::second_answer],
-    [day18::first_answer, day18::second_answer],
-    [day19::first_answer, day19::second_answer],
-    [day20::first_answer, day20::second_answer],
-    [day21::first_answer, day21::second_answer],
-    [day22::first_answer, day22::second_answer],
-    [day23::first_answer, day23::second_answer],
-    [day24::first_answer, day24::second_answer],
-    [day25::first_answer, day25::second_answer],
+/// The Holiday ASCII String Helper and the steps of its manual arrangement procedure.
+pub mod models {
+    /// The Holiday ASCII String Helper and its manual arrangement procedure of day 15.
+    pub mod hashmap {
+        pub use crate::day15::{FocalLength, Hash, Hashmap, Operation, Step, hash};
+    }
+}
+
+/// The solutions to both puzzles of each day, or `None` for the days that are not solved yet.
+const SOLUTIONS: &[Option<[infrastructure::Solution; 2]>] = &[
+    Some([day01::first_answer, day01::second_answer]),
+    Some([day02::first_answer, day02::second_answer]),
+    Some([day03::first_answer, day03::second_answer]),
+    Some([day04::first_answer, day04::second_answer]),
+    Some([day05::first_answer, day05::second_answer]),
+    Some([day06::first_answer, day06::second_answer]),
+    Some([day07::first_answer, day07::second_answer]),
+    Some([day08::first_answer, day08::second_answer]),
+    Some([day09::first_answer, day09::second_answer]),
+    Some([day10::first_answer, day10::second_answer]),
+    Some([day11::first_answer, day11::second_answer]),
+    Some([day12::first_answer, day12::second_answer]),
+    Some([day13::first_answer, day13::second_answer]),
+    Some([day14::first_answer, day14::second_answer]),
+    Some([day15::first_answer, day15::second_answer]),
+    Some([day16::first_answer, day16::second_answer]),
+    Some([day17::first_answer, day17::second_answer]),
+    Some([day18::first_answer, day18::second_answer]),
+    Some([day19::first_answer, day19::second_answer]),
+    Some([day20::first_answer, day20::second_answer]),
+    Some([day21::first_answer, day21::second_answer]),
+    Some([day22::first_answer, day22::second_answer]),
+    Some([day23::first_answer, day23::second_answer]),
+    Some([day24::first_answer, day24::second_answer]),
+    Some([day25::first_answer, day25::second_answer]),
 ];
 
-/// # Errors
-///
-/// Errors if the wanted puzzle input cannot be read or the wanted solution does not exist.
-pub fn answer(day: infrastructure::Day, puzzle: infrastructure::Puzzle) -> anyhow::Result<String> {
-    infrastructure::answer(env!("CARGO_MANIFEST_DIR"), day, puzzle, SOLUTIONS)
-}
+const VARIANTS: &[Variant] = &[
+    Variant::new(25, Puzzle::First, "kruskal", day25::first_answer_by_kruskal),
+    Variant::new(
+        25,
+        Puzzle::First,
+        "stoer-wagner",
+        day25::first_answer_by_stoer_wagner,
+    ),
+];
+
+const PARAMETERS: &infrastructure::parameters::Keys =
+    &[(21, day21::PARAMETERS), (24, day24::PARAMETERS)];
+
+const GENERATORS: &infrastructure::generate::Generators =
+    &[(5, &day05::generate), (14, &day14::generate)];
+
+pub const YEAR: infrastructure::year::Year = infrastructure::year::Year {
+    year: 2023,
+    manifest_directory: env!("CARGO_MANIFEST_DIR"),
+    solutions: SOLUTIONS,
+    variants: VARIANTS,
+    parameters: PARAMETERS,
+    generators: GENERATORS,
+};
 
 #[cfg(test)]
 mod tests {
@@ -67,13 +96,13 @@ mod tests {
 
     /// # Panics
     ///
-    /// Panics if the return value of the solution applied to the input does not equal
-    /// `expected.to_string()`.
+    /// Panics if the return value of the solution or one of its variants applied to the input
+    /// does not equal `expected.to_string()`.
     pub fn test_on_input(day: Day, puzzle: Puzzle, input: Input, expected: impl Display) {
-        infrastructure::test::on_input(self::input, super::SOLUTIONS, day, puzzle, input, expected);
+        infrastructure::test::on_input(&super::YEAR, day, puzzle, input, expected);
     }
 
-    pub fn input(day: infrastructure::Day, input: infrastructure::Input) -> String {
-        infrastructure::input(env!("CARGO_MANIFEST_DIR"), day, input).expect("input should exist")
+    pub fn input(day: Day, input: Input) -> String {
+        super::YEAR.input(day, input).expect("input should exist")
     }
 }
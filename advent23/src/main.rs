@@ -1,3 +1,3 @@
 fn main() -> anyhow::Result<()> {
-    infrastructure::cli::main(advent23::answer)
+    infrastructure::cli::main(&advent23::YEAR)
 }
@@ -3,7 +3,12 @@ use std::array;
 use easy_cast::{Cast, Conv};
 use itertools::Itertools;
 use num_traits::CheckedEuclid;
-use shared::{string::isizes, vector::Vector};
+use shared::{
+    linear_equations::{integer_solution, solution_set},
+    rational::Rational,
+    string::isizes,
+    vector::Vector,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 struct Machine {
@@ -14,6 +19,8 @@ struct Machine {
 
 const COST: [usize; 2] = [3, 1];
 
+type Number = Rational<i128>;
+
 pub fn first_answer(input: &str) -> String {
     total_number_of_tokens(machines(input)).to_string()
 }
@@ -30,47 +37,34 @@ fn number_of_tokens(machine: Machine) -> Option<usize> {
     numbers_of_button_presses(machine).map(|n| n.dot(COST))
 }
 
-fn numbers_of_button_presses(
-    machine @ Machine {
-        a: a @ [ax, _],
-        b: b @ [bx, _],
-        prize: p @ [px, py],
-    }: Machine,
-) -> Option<[usize; 2]> {
-    let an = determinant(p, b).checked_div_rem_euclid(&determinant(a, b));
-    let an = match an {
-        None if determinant(p, b) == 0 => {
-            return [a, b]
-                .into_iter()
-                .enumerate()
-                .filter_map(|(index, [vx, vy])| {
-                    (px + py)
-                        .checked_div_rem_euclid(&(vx + vy))
-                        .and_then(|(div, rem)| (rem == 0).then_some((index, div)))
-                })
-                .min_by_key(|&(index, n)| COST[index] * usize::conv(n))
-                .map(|(index, n)| array::from_fn(|i| if i == index { n.cast() } else { 0 }));
-        }
-        Some((an, 0)) => an,
-        _ => return None,
-    };
-    let bn = (px - an * ax).checked_div_rem_euclid(&bx);
-    let bn = match bn {
-        None => {
-            return numbers_of_button_presses(swapped(machine)).map(|[b, a]| [a, b]);
-        }
-        Some((bn, 0)) => bn,
-        Some(_) => return None,
-    };
-    Some([an, bn].cast())
-}
-
-fn determinant(lhs: [isize; 2], rhs: [isize; 2]) -> isize {
-    lhs[0] * rhs[1] - lhs[1] * rhs[0]
+fn numbers_of_button_presses(Machine { a, b, prize }: Machine) -> Option<[usize; 2]> {
+    let augmented_matrix = [0, 1].map(|axis| {
+        [a[axis], b[axis], prize[axis]].map(|coefficient| Number::from(i128::conv(coefficient)))
+    });
+    let (origin, directions) = solution_set(augmented_matrix)?;
+    if !directions.is_empty() {
+        return numbers_of_button_presses_with_parallel_basis([a, b], prize);
+    }
+    let [an, bn]: [i128; 2] = integer_solution(&origin)?
+        .try_into()
+        .expect("solution should consist of two numbers of button presses");
+    Some([usize::try_from(an).ok()?, usize::try_from(bn).ok()?])
 }
 
-fn swapped(Machine { a, b, prize }: Machine) -> Machine {
-    Machine { a: b, b: a, prize }
+fn numbers_of_button_presses_with_parallel_basis(
+    basis: [[isize; 2]; 2],
+    [px, py]: [isize; 2],
+) -> Option<[usize; 2]> {
+    basis
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, [vx, vy])| {
+            (px + py)
+                .checked_div_rem_euclid(&(vx + vy))
+                .and_then(|(div, rem)| (rem == 0).then_some((index, div)))
+        })
+        .min_by_key(|&(index, n)| COST[index] * usize::conv(n))
+        .map(|(index, n)| array::from_fn(|i| if i == index { n.cast() } else { 0 }))
 }
 
 fn corrected_machines(input: &str) -> impl Iterator<Item = Machine> + use<'_> {
@@ -1,9 +1,14 @@
 use std::{cmp::Ordering, path::Path};
 
 use easy_cast::Cast;
-use image::ImageBuffer;
+use infrastructure::parameters::Parameter;
 use itertools::Itertools;
-use shared::{string::isizes, vector::Vector};
+use shared::{
+    grid::{Rectangle, Topology, Torus},
+    render::{self, BLACK, Image, WHITE},
+    string::isizes,
+    vector::Vector,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 struct Robot {
@@ -14,10 +19,12 @@ type Position = [Coordinate; 2];
 type Velocity = [Coordinate; 2];
 type Coordinate = isize;
 
-const SPACE_DIMENSIONS: [Coordinate; 2] = [101, 103];
+const WIDTH: Parameter<Coordinate> = Parameter::new("width", 101, 11);
+const HEIGHT: Parameter<Coordinate> = Parameter::new("height", 103, 7);
+pub const PARAMETERS: &[&str] = &[WIDTH.key(), HEIGHT.key()];
 
 pub fn first_answer(input: &str) -> String {
-    safety_factor(robots(input), 100).to_string()
+    safety_factor(robots(input), 100, space_dimensions()).to_string()
 }
 
 pub fn second_answer(input: &str) -> String {
@@ -25,8 +32,9 @@ pub fn second_answer(input: &str) -> String {
     std::fs::create_dir_all(&image_directory).expect("image directory should be created");
 
     let robots = robots(input).collect_vec();
+    let space_dimensions = space_dimensions();
     for seconds in 0..9999 {
-        save_image(&robots, seconds, &image_directory);
+        save_image(&robots, seconds, space_dimensions, &image_directory);
     }
     format!(
         "The first 10,000 images are saved to '{}'.",
@@ -42,16 +50,25 @@ enum Quadrant {
     TopLeft,
 }
 
-const QUADRANT_BORDERS: [Coordinate; 2] = [SPACE_DIMENSIONS[0] / 2, SPACE_DIMENSIONS[1] / 2];
+fn space_dimensions() -> [Coordinate; 2] {
+    [WIDTH.get(), HEIGHT.get()]
+}
 
-fn safety_factor(robots: impl Iterator<Item = Robot>, seconds: usize) -> usize {
-    let counts = robots.filter_map(|robot| quadrant(robot, seconds)).counts();
+fn safety_factor(
+    robots: impl Iterator<Item = Robot>,
+    seconds: usize,
+    space_dimensions: [Coordinate; 2],
+) -> usize {
+    let counts = robots
+        .filter_map(|robot| quadrant(robot, seconds, space_dimensions))
+        .counts();
     counts.values().copied().product()
 }
 
-fn quadrant(robot: Robot, seconds: usize) -> Option<Quadrant> {
-    let [x, y] = position(robot, seconds);
-    match [x.cmp(&QUADRANT_BORDERS[0]), y.cmp(&QUADRANT_BORDERS[1])] {
+fn quadrant(robot: Robot, seconds: usize, space_dimensions: [Coordinate; 2]) -> Option<Quadrant> {
+    let [x, y] = position(robot, seconds, space_dimensions);
+    let [x_border, y_border] = space_dimensions.map(|dimension| dimension / 2);
+    match [x.cmp(&x_border), y.cmp(&y_border)] {
         [Ordering::Less, Ordering::Less] => Some(Quadrant::TopLeft),
         [Ordering::Less, Ordering::Greater] => Some(Quadrant::BottomLeft),
         [Ordering::Greater, Ordering::Less] => Some(Quadrant::TopRight),
@@ -60,31 +77,42 @@ fn quadrant(robot: Robot, seconds: usize) -> Option<Quadrant> {
     }
 }
 
-fn save_image(robots: &[Robot], seconds: usize, directory: impl AsRef<Path>) {
+fn save_image(
+    robots: &[Robot],
+    seconds: usize,
+    space_dimensions: [Coordinate; 2],
+    directory: impl AsRef<Path>,
+) {
     let path = directory.as_ref().join(format!("{seconds}.png"));
-    image(robots, seconds)
-        .save(path)
-        .expect("image should be save");
+    render::save(&image(robots, seconds, space_dimensions), path).expect("image should be saved");
 }
 
-fn image(robots: &[Robot], seconds: usize) -> ImageBuffer<image::Luma<u8>, Vec<u8>> {
-    let mut image = ImageBuffer::from_pixel(
-        SPACE_DIMENSIONS[0].cast(),
-        SPACE_DIMENSIONS[1].cast(),
-        image::Luma([u8::MAX]),
-    );
-    for [x, y] in robots.iter().map(|&robot| position(robot, seconds)) {
-        image.put_pixel(x.cast(), y.cast(), image::Luma([0]));
-    }
-    image
+fn image(robots: &[Robot], seconds: usize, space_dimensions: [Coordinate; 2]) -> Image {
+    let [width, height] = space_dimensions;
+    let bounds = Rectangle {
+        top_left: [0, 0],
+        dimensions: [height, width],
+    };
+    let positions = robots.iter().map(|&robot| {
+        let [x, y] = position(robot, seconds, space_dimensions);
+        [y, x]
+    });
+    render::positions(positions, bounds, [WHITE, BLACK])
 }
 
-fn position(Robot { position, velocity }: Robot, seconds: usize) -> Position {
-    let [x, y] = position.add(velocity.mul(seconds.cast()));
-    [
-        x.rem_euclid(SPACE_DIMENSIONS[0]),
-        y.rem_euclid(SPACE_DIMENSIONS[1]),
-    ]
+fn position(
+    Robot { position, velocity }: Robot,
+    seconds: usize,
+    space_dimensions: [Coordinate; 2],
+) -> Position {
+    let space = Torus(Rectangle {
+        top_left: [0, 0],
+        dimensions: space_dimensions,
+    });
+    let (position, _) = space
+        .step(position, velocity.mul(seconds.cast()))
+        .expect("space should have no edges");
+    position
 }
 
 fn robots(input: &str) -> impl Iterator<Item = Robot> + use<'_> {
@@ -107,6 +135,11 @@ mod tests {
 
     const DAY: usize = 14;
 
+    #[test]
+    fn first_answer_example() {
+        test_on_input(DAY, Puzzle::First, Input::Example(0), 12);
+    }
+
     #[test]
     fn first_answer_input() {
         test_on_input(DAY, Puzzle::First, Input::PuzzleInput, 211_692_000);
@@ -161,9 +161,13 @@ fn wide_warehouse(warehouse: &Warehouse) -> Warehouse {
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use infrastructure::{Input, Puzzle};
+    use shared::render::{self, Color};
 
-    use crate::tests::test_on_input;
+    use super::*;
+    use crate::tests::{input, test_on_input};
 
     const DAY: usize = 15;
 
@@ -188,4 +192,39 @@ mod tests {
     fn second_answer_input() {
         test_on_input(DAY, Puzzle::Second, Input::PuzzleInput, 1_554_058);
     }
+
+    #[test]
+    #[ignore = "writes an animation to the temporary directory"]
+    fn robot_animation() {
+        let input = input(DAY, Input::Example(0));
+        let (warehouse, directions) = warehouse_and_directions(&input);
+        let mut warehouse = wide_warehouse(&warehouse);
+        let mut robot = robot_position(&mut warehouse);
+        let mut frames = vec![frame(&warehouse)];
+        for direction in directions {
+            if r#move(&mut warehouse, robot, direction) {
+                robot = robot.add(direction);
+            }
+            frames.push(frame(&warehouse));
+        }
+        let path = std::env::temp_dir().join("advent24-15-robot.gif");
+        render::save_animation(frames, Duration::from_millis(50), path)
+            .expect("animation should be saved");
+    }
+
+    fn frame(warehouse: &Warehouse) -> render::Image {
+        render::scaled(
+            &render::grid(warehouse, |&element| element_color(element)),
+            8,
+        )
+    }
+
+    fn element_color(element: u8) -> Color {
+        match element {
+            b'#' => [120, 120, 120],
+            b'O' | b'[' | b']' => [180, 120, 60],
+            b'@' => [220, 40, 40],
+            _ => render::BLACK,
+        }
+    }
 }
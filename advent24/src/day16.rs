@@ -4,7 +4,9 @@ use std::{
 };
 
 use ahash::{AHashMap, AHashSet};
+use rand::rngs::SmallRng;
 use shared::{
+    generators,
     grid::{Direction, EAST, Grid, ORTHOGONAL_DIRECTIONS, Position},
     search,
     vector::{RotationInTwoDimensions, Vector},
@@ -25,6 +27,17 @@ pub fn second_answer(input: &str) -> String {
     best_paths(&maze).len().to_string()
 }
 
+/// A maze with `size` by `size` cells, the start tile in the bottom-left corner and the end tile
+/// in the top-right corner.
+pub fn generate(rng: &mut SmallRng, size: usize) -> String {
+    let mut maze = generators::maze(rng, size.max(2), 0.2);
+    let [_, _, bottom_right, _] = maze.corner_positions_clockwise();
+    let [bottom, right] = bottom_right;
+    maze[[bottom - 1, 1]] = b'S';
+    maze[[1, right - 1]] = b'E';
+    maze.to_string()
+}
+
 fn best_path_cost(maze: &Maze) -> usize {
     search::minimum_path_cost(source(maze), successors(maze), target(maze))
         .expect("path from start tile to end tile should exist")
@@ -114,12 +127,96 @@ fn end_tile(maze: &Grid<u8>) -> [isize; 2] {
 
 #[cfg(test)]
 mod tests {
-    use infrastructure::{Input, Puzzle};
+    use infrastructure::{Input, Puzzle, test};
+    use itertools::Itertools;
 
+    use super::*;
     use crate::tests::test_on_input;
 
     const DAY: usize = 16;
 
+    /// The minimum costs of reaching states from `sources`, relaxed until none of them decreases.
+    fn relaxed_costs(
+        sources: impl IntoIterator<Item = State>,
+        successors: impl Fn(State) -> Vec<(State, Cost)>,
+    ) -> AHashMap<State, Cost> {
+        let mut costs: AHashMap<_, _> = sources.into_iter().map(|source| (source, 0)).collect();
+        let mut decreased = costs.keys().copied().collect_vec();
+        while let Some(state) = decreased.pop() {
+            let cost = costs[&state];
+            for (successor, step_cost) in successors(state) {
+                if costs
+                    .get(&successor)
+                    .is_none_or(|&successor_cost| cost + step_cost < successor_cost)
+                {
+                    costs.insert(successor, cost + step_cost);
+                    decreased.push(successor);
+                }
+            }
+        }
+        costs
+    }
+
+    fn costs_from_start_and_to_end(maze: &Maze) -> [AHashMap<State, Cost>; 2] {
+        let end_states = ORTHOGONAL_DIRECTIONS.map(|direction| (end_tile(maze), direction));
+        let predecessors = |(tile, direction): State| {
+            let mut predecessors = vec![
+                ((tile, direction.left()), 1000),
+                ((tile, direction.right()), 1000),
+            ];
+            let previous_tile = tile.sub(direction);
+            if maze[previous_tile] != b'#' {
+                predecessors.push(((previous_tile, direction), 1));
+            }
+            predecessors
+        };
+        [
+            relaxed_costs([source(maze)], successors(maze)),
+            relaxed_costs(end_states, predecessors),
+        ]
+    }
+
+    fn best_path_cost_by_relaxation(costs_from_start: &AHashMap<State, Cost>, maze: &Maze) -> Cost {
+        ORTHOGONAL_DIRECTIONS
+            .iter()
+            .filter_map(|&direction| costs_from_start.get(&(end_tile(maze), direction)))
+            .copied()
+            .min()
+            .expect("path from start tile to end tile should exist")
+    }
+
+    fn first_answer_by_relaxation(input: &str) -> String {
+        let maze = Maze::from(input);
+        let [costs_from_start, _] = costs_from_start_and_to_end(&maze);
+        best_path_cost_by_relaxation(&costs_from_start, &maze).to_string()
+    }
+
+    fn second_answer_by_relaxation(input: &str) -> String {
+        let maze = Maze::from(input);
+        let [costs_from_start, costs_to_end] = costs_from_start_and_to_end(&maze);
+        let best_path_cost = best_path_cost_by_relaxation(&costs_from_start, &maze);
+        costs_from_start
+            .iter()
+            .filter(|&(state, cost)| {
+                costs_to_end
+                    .get(state)
+                    .map(|cost_to_end| cost + cost_to_end)
+                    == Some(best_path_cost)
+            })
+            .map(|((tile, _), _)| tile)
+            .unique()
+            .count()
+            .to_string()
+    }
+
+    #[test]
+    fn answers_generated() {
+        let solutions = [first_answer, first_answer_by_relaxation];
+        test::agree_on_generated_inputs(&generate, 6, 1000, &solutions);
+        let solutions = [second_answer, second_answer_by_relaxation];
+        test::agree_on_generated_inputs(&generate, 6, 1000, &solutions);
+    }
+
     #[test]
     fn first_answer_example() {
         test_on_input(DAY, Puzzle::First, Input::Example(0), 7036);
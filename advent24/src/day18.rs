@@ -1,4 +1,5 @@
 use easy_cast::Cast;
+use infrastructure::parameters::Parameter;
 use shared::{
     grid::{Grid, Position, orthogonal_neighbors},
     search,
@@ -7,11 +8,13 @@ use shared::{
 
 type MemorySpace = Grid<u8>;
 
-const MEMORY_SPACE_SIZE: usize = 70 + 1;
+const MEMORY_SPACE_SIZE: Parameter<usize> = Parameter::new("memory_space_size", 70 + 1, 6 + 1);
+const NUMBER_OF_FALLEN_BYTES: Parameter<usize> = Parameter::new("number_of_fallen_bytes", 1024, 12);
+pub const PARAMETERS: &[&str] = &[MEMORY_SPACE_SIZE.key(), NUMBER_OF_FALLEN_BYTES.key()];
 
 pub fn first_answer(input: &str) -> String {
     let mut memory_space = uncorrupted_memory_space();
-    for position in falling_byte_positions(input).take(1024) {
+    for position in falling_byte_positions(input).take(NUMBER_OF_FALLEN_BYTES.get()) {
         memory_space[position] = b'#';
     }
     shortest_path_length(&memory_space)
@@ -41,7 +44,9 @@ fn shortest_path_length(memory_space: &MemorySpace) -> Option<usize> {
                     .is_some_and(|&element| element == b'.')
             })
     };
-    let target = |position| position == [(memory_space.width() - 1).cast(); 2];
+    // Annotated, as the comparisons of serde_json's `Value` with integers make the cast ambiguous.
+    let bottom_right: isize = (memory_space.width() - 1).cast();
+    let target = |position| position == [bottom_right; 2];
     search::shortest_path_length(source, successors, target)
 }
 
@@ -53,7 +58,8 @@ fn falling_byte_positions(input: &str) -> impl Iterator<Item = Position> {
 }
 
 fn uncorrupted_memory_space() -> MemorySpace {
-    MemorySpace::new(MEMORY_SPACE_SIZE, MEMORY_SPACE_SIZE, |_| b'.')
+    let size = MEMORY_SPACE_SIZE.get();
+    MemorySpace::new(size, size, |_| b'.')
 }
 
 #[cfg(test)]
@@ -64,11 +70,21 @@ mod tests {
 
     const DAY: usize = 18;
 
+    #[test]
+    fn first_answer_example() {
+        test_on_input(DAY, Puzzle::First, Input::Example(0), 22);
+    }
+
     #[test]
     fn first_answer_input() {
         test_on_input(DAY, Puzzle::First, Input::PuzzleInput, 372);
     }
 
+    #[test]
+    fn second_answer_example() {
+        test_on_input(DAY, Puzzle::Second, Input::Example(0), "6,1");
+    }
+
     #[test]
     fn second_answer_input() {
         test_on_input(DAY, Puzzle::Second, Input::PuzzleInput, "25,6");
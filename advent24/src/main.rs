@@ -1,3 +1,3 @@
 fn main() -> anyhow::Result<()> {
-    infrastructure::cli::main(advent24::answer)
+    infrastructure::cli::main(&advent24::YEAR)
 }
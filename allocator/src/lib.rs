@@ -0,0 +1,123 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// The system allocator, counting what each thread allocates. A program counts its allocations by
+/// making it the global allocator.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Counting;
+
+/// What the current thread has allocated so far. A reallocation counts as an allocation of the
+/// new size.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Counters {
+    /// The bytes allocated and not yet deallocated, which is negative if the thread deallocates
+    /// memory that another thread allocated.
+    pub live: isize,
+
+    /// The most live bytes so far, which may be reset to measure a part of a run.
+    pub peak: isize,
+
+    /// The bytes allocated in total.
+    pub total: usize,
+
+    /// The number of allocations.
+    pub allocations: usize,
+}
+
+/// Whether the counting allocator has served an allocation, which tells whether it is the global
+/// allocator.
+static COUNTING: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    static COUNTERS: Cell<Counters> = const {
+        Cell::new(Counters {
+            live: 0,
+            peak: 0,
+            total: 0,
+            allocations: 0,
+        })
+    };
+}
+
+/// Whether the counting allocator is the global allocator, so that the counters mean anything.
+#[must_use]
+pub fn counting() -> bool {
+    COUNTING.load(Ordering::Relaxed)
+}
+
+/// The counters of the current thread.
+#[must_use]
+pub fn counters() -> Counters {
+    COUNTERS.with(Cell::get)
+}
+
+/// Replaces the counters of the current thread, such as to reset the peak.
+pub fn set_counters(counters: Counters) {
+    COUNTERS.with(|cell| cell.set(counters));
+}
+
+/// Records an allocation of `allocated` bytes that replaces `deallocated` bytes, either of which
+/// may be zero.
+fn record(allocated: usize, deallocated: usize) {
+    if !COUNTING.load(Ordering::Relaxed) {
+        COUNTING.store(true, Ordering::Relaxed);
+    }
+    COUNTERS.with(|counters| {
+        let mut current = counters.get();
+        current.live += allocated.cast_signed() - deallocated.cast_signed();
+        current.peak = current.peak.max(current.live);
+        if allocated > 0 {
+            current.total += allocated;
+            current.allocations += 1;
+        }
+        counters.set(current);
+    });
+}
+
+#[expect(
+    unsafe_code,
+    reason = "a global allocator cannot be implemented without unsafe code"
+)]
+// SAFETY: Every method defers to the system allocator and only adds bookkeeping, which neither
+// allocates nor panics.
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        // SAFETY: The caller upholds the contract of `GlobalAlloc::alloc`.
+        let pointer = unsafe { System.alloc(layout) };
+        if !pointer.is_null() {
+            record(layout.size(), 0);
+        }
+        pointer
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        // SAFETY: The caller upholds the contract of `GlobalAlloc::alloc_zeroed`.
+        let pointer = unsafe { System.alloc_zeroed(layout) };
+        if !pointer.is_null() {
+            record(layout.size(), 0);
+        }
+        pointer
+    }
+
+    unsafe fn dealloc(&self, pointer: *mut u8, layout: Layout) {
+        // SAFETY: The caller upholds the contract of `GlobalAlloc::dealloc`, and every pointer that
+        // this allocator hands out comes from the system allocator.
+        unsafe {
+            System.dealloc(pointer, layout);
+        }
+        record(0, layout.size());
+    }
+
+    unsafe fn realloc(&self, pointer: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        // SAFETY: The caller upholds the contract of `GlobalAlloc::realloc`, and every pointer that
+        // this allocator hands out comes from the system allocator.
+        let new_pointer = unsafe { System.realloc(pointer, layout, new_size) };
+        if !new_pointer.is_null() {
+            record(new_size, layout.size());
+        }
+        new_pointer
+    }
+}
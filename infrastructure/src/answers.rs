@@ -0,0 +1,175 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::{ErrorKind, Write},
+    path::Path,
+};
+
+use anyhow::{Context, anyhow};
+
+use crate::{Answer, Day, Puzzle, submit::Hint};
+
+/// The answer to the puzzle that Advent of Code has accepted, if any. Accepted answers are kept in
+/// `puzzle-answers/<day>.txt` with a line `<puzzle>\t<answer>` for each puzzle.
+///
+/// # Errors
+///
+/// Errors if the registry of the day cannot be read or is malformed.
+pub fn registered(
+    manifest_directory: &str,
+    day: Day,
+    puzzle: Puzzle,
+) -> anyhow::Result<Option<Answer>> {
+    let lines = lines(&accepted_path(manifest_directory, day))?;
+    for line in lines {
+        let [registered_puzzle, answer] = fields(&line)?;
+        if registered_puzzle.parse::<Puzzle>()? == puzzle {
+            return Ok(Some(answer.to_owned()));
+        }
+    }
+    Ok(None)
+}
+
+/// Adds the accepted answer to the registry.
+///
+/// # Errors
+///
+/// Errors if the registry of the day cannot be written.
+pub fn register(
+    manifest_directory: &str,
+    day: Day,
+    puzzle: Puzzle,
+    answer: &str,
+) -> anyhow::Result<()> {
+    append(
+        &accepted_path(manifest_directory, day),
+        &format!("{puzzle}\t{answer}"),
+    )
+}
+
+/// The answers to the puzzle that Advent of Code has rejected, each with the hint it gave. They
+/// are kept in `puzzle-answers/<day>-rejected.txt` with a line `<puzzle>\t<answer>\t<hint>` for
+/// each answer, where the hint is `too-high`, `too-low` or `wrong`.
+///
+/// # Errors
+///
+/// Errors if the rejected answers of the day cannot be read or are malformed.
+pub fn rejected(
+    manifest_directory: &str,
+    day: Day,
+    puzzle: Puzzle,
+) -> anyhow::Result<Vec<(Answer, Option<Hint>)>> {
+    let mut rejected = vec![];
+    for line in lines(&rejected_path(manifest_directory, day))? {
+        let [rejected_puzzle, answer, hint] = fields(&line)?;
+        if rejected_puzzle.parse::<Puzzle>()? == puzzle {
+            let hint = match hint {
+                WRONG => None,
+                hint => Some(hint.parse()?),
+            };
+            rejected.push((answer.to_owned(), hint));
+        }
+    }
+    Ok(rejected)
+}
+
+/// Adds the rejected answer to the ones that should never be submitted again.
+///
+/// # Errors
+///
+/// Errors if the rejected answers of the day cannot be written.
+pub fn reject(
+    manifest_directory: &str,
+    day: Day,
+    puzzle: Puzzle,
+    answer: &str,
+    hint: Option<Hint>,
+) -> anyhow::Result<()> {
+    let hint = hint.map_or_else(|| WRONG.to_owned(), |hint| hint.to_string());
+    append(
+        &rejected_path(manifest_directory, day),
+        &format!("{puzzle}\t{answer}\t{hint}"),
+    )
+}
+
+/// The rejected answer that shows `answer` to be wrong, either by being equal to it or by being
+/// too high while being at most `answer` or too low while being at least `answer`.
+#[must_use]
+pub fn known_wrong<'rejected>(
+    rejected: &'rejected [(Answer, Option<Hint>)],
+    answer: &str,
+) -> Option<&'rejected (Answer, Option<Hint>)> {
+    rejected.iter().find(|(rejected, hint)| {
+        if rejected == answer {
+            return true;
+        }
+        let (Ok(rejected), Ok(answer)) = (rejected.parse::<i128>(), answer.parse::<i128>()) else {
+            return false;
+        };
+        match hint {
+            Some(Hint::TooHigh) => answer >= rejected,
+            Some(Hint::TooLow) => answer <= rejected,
+            None => false,
+        }
+    })
+}
+
+const WRONG: &str = "wrong";
+
+fn accepted_path(manifest_directory: &str, day: Day) -> String {
+    format!("{manifest_directory}/puzzle-answers/{day:02}.txt")
+}
+
+fn rejected_path(manifest_directory: &str, day: Day) -> String {
+    format!("{manifest_directory}/puzzle-answers/{day:02}-rejected.txt")
+}
+
+fn lines(path: &str) -> anyhow::Result<Vec<String>> {
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(contents.lines().map(str::to_owned).collect()),
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(vec![]),
+        Err(error) => Err(error).with_context(|| format!("path '{path}' should be readable")),
+    }
+}
+
+fn fields<const N: usize>(line: &str) -> anyhow::Result<[&str; N]> {
+    let fields: Vec<_> = line.split('\t').collect();
+    fields
+        .try_into()
+        .map_err(|_| anyhow!("line '{line}' should have {N} tab-separated fields"))
+}
+
+fn append(path: &str, line: &str) -> anyhow::Result<()> {
+    if let Some(directory) = Path::new(path).parent() {
+        fs::create_dir_all(directory)
+            .with_context(|| format!("directory of path '{path}' should be creatable"))?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("path '{path}' should be writable"))?;
+    writeln!(file, "{line}").with_context(|| format!("path '{path}' should be writable"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_wrong() {
+        let rejected = [
+            ("abc".to_owned(), None),
+            ("100".to_owned(), Some(Hint::TooHigh)),
+            ("10".to_owned(), Some(Hint::TooLow)),
+        ];
+        let known_wrong =
+            |answer| super::known_wrong(&rejected, answer).map(|(answer, _)| answer.as_str());
+        assert_eq!(known_wrong("abc"), Some("abc"));
+        assert_eq!(known_wrong("abd"), None);
+        assert_eq!(known_wrong("150"), Some("100"));
+        assert_eq!(known_wrong("100"), Some("100"));
+        assert_eq!(known_wrong("99"), None);
+        assert_eq!(known_wrong("11"), None);
+        assert_eq!(known_wrong("-5"), Some("10"));
+    }
+}
@@ -1,31 +1,389 @@
-use std::ops::RangeInclusive;
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    fs,
+    net::{Ipv4Addr, TcpListener},
+    num::NonZero,
+    ops::RangeInclusive,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
 
-use anyhow::anyhow;
-use clap::Parser;
+use anyhow::{Context, anyhow, bail};
+use clap::{Args, Parser, Subcommand};
 
-use crate::{Day, Puzzle};
+use crate::{
+    Day, Input, Puzzle,
+    leaderboard::{self, Leaderboard},
+    parameters::Parameters,
+    serve,
+    submit::{self, Client},
+    suite::{self, Job, Outcome},
+    trace,
+    variants::{self, Timing},
+    year::Year,
+};
 
-#[derive(Debug, Clone, Copy, Parser)]
+#[derive(Debug, Clone, Parser)]
 #[command(about, long_about = None)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct CommandLineArguments {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[command(flatten)]
+    solve: Option<SolveArguments>,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum Command {
+    /// Prints a random input that is valid under the puzzle description.
+    Generate(GenerateArguments),
+
+    /// Runs the solution and all its variants, checks that they agree and prints their timings.
+    Compare(PuzzleArguments),
+
+    /// Solves all puzzle inputs concurrently and prints the answers in order.
+    All(SuiteArguments),
+
+    /// Submits the answer to the puzzle input, unless it is known to be wrong, and prints the
+    /// verdict.
+    Submit(SubmitArguments),
+
+    /// Prints scores, star times and timelines of an exported private leaderboard.
+    Leaderboard(LeaderboardArguments),
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct PuzzleArguments {
+    /// Which day?
+    #[clap(value_parser=day)]
+    pub day: Day,
+
+    /// First or second puzzle?
+    pub puzzle: Puzzle,
+
+    /// Overrides a puzzle parameter, such as the size of a space.
+    #[clap(long = "param", value_name = "KEY=VALUE", value_parser = assignment)]
+    pub parameters: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone, Args)]
+// The derive leaves the group of a struct with flattened fields empty, which would make an
+// optional `SolveArguments` always absent, so the members that tell its presence are named here.
+#[group(args = ["day", "puzzle"])]
+pub struct SolveArguments {
+    #[command(flatten)]
+    pub puzzle: PuzzleArguments,
+
+    /// Which variant of the solution?
+    #[clap(long, default_value = variants::DEFAULT)]
+    pub variant: String,
+
+    /// Print the events that the solution traces, such as detected cycles, to standard error.
+    #[clap(long)]
+    pub trace: bool,
+}
+
+#[derive(Debug, Clone, Copy, Args)]
+pub struct GenerateArguments {
+    /// Which day?
+    #[clap(value_parser=day)]
+    pub day: Day,
+
+    /// Which input? The same seed always gives the same input.
+    #[clap(long, default_value_t = 0)]
+    pub seed: u64,
+
+    /// How large an input? What this bounds depends on the day.
+    #[clap(long, default_value_t = 10)]
+    pub size: usize,
+}
+
+#[derive(Debug, Clone, Copy, Args)]
+pub struct SuiteArguments {
+    /// How many seconds may a solution take?
+    #[clap(long, default_value_t = 60)]
+    pub timeout: u64,
+
+    /// How many puzzles at once? By default as many as the machine can run in parallel.
+    #[clap(long)]
+    pub workers: Option<NonZero<usize>>,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct SubmitArguments {
     /// Which day?
     #[clap(value_parser=day)]
-    day: Day,
+    pub day: Day,
 
     /// First or second puzzle?
-    puzzle: Puzzle,
+    pub puzzle: Puzzle,
+
+    /// Which answer? By default the one that the solution gives.
+    #[clap(long)]
+    pub answer: Option<String>,
+
+    /// Where to submit it? The session cookie is read from the environment variable `AOC_SESSION`.
+    #[clap(long, default_value = submit::DEFAULT_URL)]
+    pub url: String,
+}
+
+#[derive(Debug, Clone, Copy, Args)]
+pub struct ServeArguments {
+    /// Which port on localhost?
+    #[clap(long, default_value_t = 8025)]
+    pub port: u16,
+
+    /// How many seconds may a solution take?
+    #[clap(long, default_value_t = 60)]
+    pub timeout: u64,
+
+    /// How many solutions at once? By default as many as the machine can run in parallel.
+    #[clap(long)]
+    pub workers: Option<NonZero<usize>>,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct LeaderboardArguments {
+    /// Which leaderboard? A JSON file as exported from Advent of Code.
+    pub path: PathBuf,
+
+    /// Also run the solutions and show how long they take next to the times of the members.
+    #[clap(long)]
+    pub timings: bool,
+}
+
+/// # Errors
+///
+/// Errors if the command or solving the puzzle fails.
+///
+/// # Panics
+///
+/// Panics if neither a command nor a puzzle is given, which the parser rules out.
+pub fn main(year: &Year) -> anyhow::Result<()> {
+    let CommandLineArguments { command, solve } = CommandLineArguments::parse();
+    match command {
+        Some(command) => execute(year, command),
+        None => self::solve(
+            year,
+            solve.expect("puzzle should be given if no command is"),
+        ),
+    }
 }
 
+/// Prints the answer to the puzzle input.
+///
 /// # Errors
 ///
-/// Errors if the wanted puzzle-input file cannot be read.
-pub fn main(answer: fn(Day, Puzzle) -> anyhow::Result<String>) -> anyhow::Result<()> {
-    let CommandLineArguments { day, puzzle } = CommandLineArguments::parse();
-    let answer = answer(day, puzzle)?;
+/// Errors if the puzzle-input file cannot be read or the wanted solution or variant does not
+/// exist.
+pub fn solve(year: &Year, arguments: SolveArguments) -> anyhow::Result<()> {
+    let SolveArguments {
+        puzzle:
+            PuzzleArguments {
+                day,
+                puzzle,
+                parameters,
+            },
+        variant,
+        trace,
+    } = arguments;
+    let parameters = puzzle_input_parameters(parameters);
+    let answer = || year.answer(day, puzzle, &variant, &parameters);
+    let answer = if trace {
+        trace::scope(|event| eprintln!("{event}"), answer)?
+    } else {
+        answer()?
+    };
     println!("{answer}");
     Ok(())
 }
 
+/// # Errors
+///
+/// Errors if the command fails, or if the compared variants do not agree.
+pub fn execute(year: &Year, command: Command) -> anyhow::Result<()> {
+    match command {
+        Command::Generate(GenerateArguments { day, seed, size }) => {
+            print!("{}", year.generate(day, seed, size)?);
+        }
+        Command::Compare(PuzzleArguments {
+            day,
+            puzzle,
+            parameters,
+        }) => {
+            let parameters = puzzle_input_parameters(parameters);
+            let timings = year.compare(day, puzzle, &parameters)?;
+            let name_width = timings
+                .iter()
+                .map(|timing| timing.name.len())
+                .max()
+                .unwrap_or_default();
+            for Timing {
+                name,
+                answer,
+                duration,
+                memory,
+            } in &timings
+            {
+                let milliseconds = duration.as_secs_f64() * 1000.0;
+                let memory = memory
+                    .map(|memory| format!("{memory}  "))
+                    .unwrap_or_default();
+                println!("{name:name_width$}  {milliseconds:>12.3} ms  {memory}{answer}");
+            }
+            if !timings
+                .iter()
+                .all(|timing| timing.answer == timings[0].answer)
+            {
+                bail!("answers of all variants should agree");
+            }
+        }
+        Command::All(arguments) => {
+            let jobs = puzzle_input_jobs(year)
+                .into_iter()
+                .map(|((day, puzzle), job)| (format!("{day:02} {puzzle:6}"), job))
+                .collect();
+            run_suite(jobs, arguments);
+        }
+        Command::Submit(SubmitArguments {
+            day,
+            puzzle,
+            answer,
+            url,
+        }) => {
+            let answer = match answer {
+                Some(answer) => answer,
+                None => year.answer(day, puzzle, variants::DEFAULT, &Parameters::default())?,
+            };
+            let client = Client::from_environment(url)?;
+            let verdict = year.submit(day, puzzle, &answer, &client)?;
+            println!("{answer}  {verdict}");
+        }
+        Command::Leaderboard(LeaderboardArguments { path, timings }) => {
+            let json = fs::read_to_string(&path)
+                .with_context(|| format!("path '{}' should exist", path.display()))?;
+            let leaderboard = Leaderboard::parse(&json)?;
+            let event = leaderboard.year()?;
+            if usize::try_from(event).ok() != Some(year.year) {
+                bail!("leaderboard should be of {}, but is of {event}", year.year);
+            }
+            let timings = if timings {
+                solution_timings(year)
+            } else {
+                BTreeMap::new()
+            };
+            print!("{}", leaderboard::report(&leaderboard, &timings)?);
+        }
+    }
+    Ok(())
+}
+
+/// How long the default solution to each puzzle takes on the puzzle input, for the puzzles it
+/// answers within a minute.
+fn solution_timings(year: &Year) -> BTreeMap<(Day, Puzzle), Duration> {
+    let outcomes = suite::run(
+        puzzle_input_jobs(year),
+        suite::default_number_of_workers(),
+        Duration::from_mins(1),
+    );
+    outcomes
+        .into_iter()
+        .filter_map(|(puzzle, outcome)| match outcome {
+            Outcome::Answer(_, duration, _) => Some((puzzle, duration)),
+            Outcome::Error(_) | Outcome::Panic(_) | Outcome::Timeout(_) => None,
+        })
+        .collect()
+}
+
+/// The jobs that solve both puzzles of every day on the puzzle input with the default solutions,
+/// in order of day.
+#[must_use]
+pub fn puzzle_input_jobs(year: &Year) -> Vec<((Day, Puzzle), Job)> {
+    let year = *year;
+    (1..=25)
+        .flat_map(|day| [Puzzle::First, Puzzle::Second].map(|puzzle| (day, puzzle)))
+        .map(|(day, puzzle)| {
+            let job: Job = Box::new(move || {
+                year.answer(day, puzzle, variants::DEFAULT, &Parameters::default())
+            });
+            ((day, puzzle), job)
+        })
+        .collect()
+}
+
+/// Runs the jobs concurrently and prints their outcomes in order, followed by how many jobs had
+/// which kind of outcome.
+pub fn run_suite(jobs: Vec<(impl Display, Job)>, arguments: SuiteArguments) {
+    let SuiteArguments { timeout, workers } = arguments;
+    let start = Instant::now();
+    let outcomes = suite::run(
+        jobs,
+        workers.unwrap_or_else(suite::default_number_of_workers),
+        Duration::from_secs(timeout),
+    );
+    let mut counts = [0; 4];
+    for (label, outcome) in &outcomes {
+        println!("{label}  {outcome}");
+        let kind = match outcome {
+            Outcome::Answer(..) => 0,
+            Outcome::Error(_) => 1,
+            Outcome::Panic(_) => 2,
+            Outcome::Timeout(_) => 3,
+        };
+        counts[kind] += 1;
+    }
+    let [answers, errors, panics, timeouts] = counts;
+    println!(
+        "{answers} answers, {errors} errors, {panics} panics and {timeouts} timeouts in {:.3} s",
+        start.elapsed().as_secs_f64()
+    );
+}
+
+/// Answers puzzles of the given years over HTTP on localhost until the process ends.
+///
+/// # Errors
+///
+/// Errors if the port cannot be bound or a connection cannot be accepted.
+pub fn serve(years: serve::Years, arguments: ServeArguments) -> anyhow::Result<()> {
+    let ServeArguments {
+        port,
+        timeout,
+        workers,
+    } = arguments;
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))
+        .with_context(|| format!("port {port} on localhost should be available"))?;
+    eprintln!("serving on http://{}", listener.local_addr()?);
+    serve::serve(
+        listener,
+        years,
+        workers.unwrap_or_else(suite::default_number_of_workers),
+        Duration::from_secs(timeout),
+    )
+}
+
+/// The parameters for solving the puzzle input with the given overrides.
+#[must_use]
+pub fn puzzle_input_parameters(
+    overrides: impl IntoIterator<Item = (String, String)>,
+) -> Parameters {
+    overrides.into_iter().fold(
+        Parameters::new(Input::PuzzleInput),
+        |parameters, (key, value)| parameters.with(key, value),
+    )
+}
+
+/// # Errors
+///
+/// Errors if the given `str` is not of the form `key=value` with a non-empty key.
+pub fn assignment(str: &str) -> anyhow::Result<(String, String)> {
+    match str.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key.to_owned(), value.to_owned())),
+        _ => Err(anyhow!("parameter should be given as 'key=value'")),
+    }
+}
+
 /// # Errors
 ///
 /// Errors if the given day is outside `1..=25`.
@@ -0,0 +1,66 @@
+use anyhow::Context;
+use rand::{SeedableRng, rngs::SmallRng};
+
+use crate::Day;
+
+/// Generates random inputs that are valid under the description of a puzzle, so that solutions
+/// can be checked on more than the examples and the puzzle input. The inputs grow with `size`,
+/// while what exactly `size` bounds is up to the day.
+pub trait Generator {
+    fn generate(&self, rng: &mut SmallRng, size: usize) -> String;
+}
+
+impl<F: Fn(&mut SmallRng, usize) -> String> Generator for F {
+    fn generate(&self, rng: &mut SmallRng, size: usize) -> String {
+        self(rng, size)
+    }
+}
+
+pub type Generators = [(Day, &'static (dyn Generator + Sync))];
+
+/// The input that `generator` generates from `seed`, which is the same on every run.
+#[must_use]
+pub fn generated(generator: &dyn Generator, seed: u64, size: usize) -> String {
+    generator.generate(&mut SmallRng::seed_from_u64(seed), size)
+}
+
+/// # Errors
+///
+/// Errors if there is no generator for the wanted day.
+pub fn generate(
+    generators: &Generators,
+    day: Day,
+    seed: u64,
+    size: usize,
+) -> anyhow::Result<String> {
+    let &(_, generator) = generators
+        .iter()
+        .find(|&&(generator_day, _)| generator_day == day)
+        .context("generator for day should exist")?;
+    Ok(generated(generator, seed, size))
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::RngExt;
+
+    use super::*;
+
+    fn numbers(rng: &mut SmallRng, size: usize) -> String {
+        (0..size)
+            .map(|_| rng.random_range(0..100).to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    const GENERATORS: &Generators = &[(3, &numbers)];
+
+    #[test]
+    fn generate() {
+        let input = super::generate(GENERATORS, 3, 7, 5).expect("generator should exist");
+        assert_eq!(input.lines().count(), 5);
+        assert_eq!(generated(&numbers, 7, 5), input);
+        assert_ne!(generated(&numbers, 8, 5), input);
+        assert!(super::generate(GENERATORS, 4, 7, 5).is_err());
+    }
+}
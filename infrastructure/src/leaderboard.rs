@@ -0,0 +1,461 @@
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, HashMap},
+    fmt::Write,
+    time::Duration,
+};
+
+use anyhow::{Context, anyhow};
+use serde::Deserialize;
+
+use crate::{Day, Puzzle};
+
+/// A private leaderboard as exported from Advent of Code.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Leaderboard {
+    pub event: String,
+    pub members: BTreeMap<String, Member>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    pub name: Option<String>,
+    pub stars: usize,
+    pub local_score: usize,
+    pub completion_day_level: BTreeMap<String, BTreeMap<String, Completion>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct Completion {
+    pub get_star_ts: i64,
+    pub star_index: u64,
+}
+
+/// A star that a member got, with the Unix time at which they got it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Star {
+    pub day: Day,
+    pub puzzle: Puzzle,
+    pub timestamp: i64,
+    pub index: u64,
+}
+
+impl Member {
+    /// The name that Advent of Code shows for the member.
+    #[must_use]
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+
+    /// The stars of the member in the order they got them.
+    ///
+    /// # Errors
+    ///
+    /// Errors if a day or a level is not a number within range.
+    pub fn timeline(&self) -> anyhow::Result<Vec<Star>> {
+        let mut stars = vec![];
+        for (day, levels) in &self.completion_day_level {
+            let day: Day = day
+                .parse()
+                .ok()
+                .filter(|day| (1..=25).contains(day))
+                .with_context(|| format!("day '{day}' should be between 1 and 25"))?;
+            for (level, completion) in levels {
+                let puzzle = match level.as_str() {
+                    "1" => Puzzle::First,
+                    "2" => Puzzle::Second,
+                    _ => return Err(anyhow!("level '{level}' should be '1' or '2'")),
+                };
+                stars.push(Star {
+                    day,
+                    puzzle,
+                    timestamp: completion.get_star_ts,
+                    index: completion.star_index,
+                });
+            }
+        }
+        stars.sort_unstable_by_key(|star| (star.timestamp, star.index));
+        Ok(stars)
+    }
+
+    /// How long after the first star of each day the member got the second.
+    ///
+    /// # Errors
+    ///
+    /// Errors if a day or a level is not a number within range.
+    pub fn deltas(&self) -> anyhow::Result<BTreeMap<Day, Duration>> {
+        let stars: HashMap<_, _> = self
+            .timeline()?
+            .into_iter()
+            .map(|star| ((star.day, star.puzzle), star.timestamp))
+            .collect();
+        Ok((1..=25)
+            .filter_map(|day| {
+                let first = stars.get(&(day, Puzzle::First))?;
+                let second = stars.get(&(day, Puzzle::Second))?;
+                Some((day, seconds(second - first)))
+            })
+            .collect())
+    }
+}
+
+impl Leaderboard {
+    /// # Errors
+    ///
+    /// Errors if `json` does not follow the schema of an exported leaderboard.
+    pub fn parse(json: &str) -> anyhow::Result<Self> {
+        serde_json::from_str(json).context("leaderboard should follow the export schema")
+    }
+
+    /// # Errors
+    ///
+    /// Errors if the event is not a year.
+    pub fn year(&self) -> anyhow::Result<i64> {
+        self.event
+            .parse()
+            .with_context(|| format!("event '{}' should be a year", self.event))
+    }
+
+    /// The local score of every member by their ID, computed from the stars: For every puzzle, the
+    /// first member to solve it gets as many points as there are members, the second one point
+    /// less and so on.
+    ///
+    /// # Errors
+    ///
+    /// Errors if a day or a level is not a number within range.
+    pub fn local_scores(&self) -> anyhow::Result<BTreeMap<u64, usize>> {
+        let mut stars = vec![];
+        for member in self.members.values() {
+            for star in member.timeline()? {
+                stars.push((star.day, star.puzzle, star.timestamp, star.index, member.id));
+            }
+        }
+        stars.sort_unstable();
+        let mut scores: BTreeMap<u64, usize> =
+            self.members.values().map(|member| (member.id, 0)).collect();
+        for solvers in stars.chunk_by(|left, right| (left.0, left.1) == (right.0, right.1)) {
+            for (rank, &(.., id)) in solvers.iter().enumerate() {
+                *scores.entry(id).or_default() += self.members.len() - rank;
+            }
+        }
+        Ok(scores)
+    }
+}
+
+/// A text report of the leaderboard with the scores, the times at which members got their stars
+/// relative to the release of each puzzle along with the deltas between the two puzzles of a day,
+/// and the timeline of each member. Where `timings` has how long our own solution to a puzzle
+/// takes, the report shows it next to the times of the members.
+///
+/// # Errors
+///
+/// Errors if the event is not a year or a day or a level is not a number within range.
+pub fn report(
+    leaderboard: &Leaderboard,
+    timings: &BTreeMap<(Day, Puzzle), Duration>,
+) -> anyhow::Result<String> {
+    let year = leaderboard.year()?;
+    let scores = leaderboard.local_scores()?;
+    let mut members: Vec<_> = leaderboard.members.values().collect();
+    members.sort_by_key(|member| (Reverse(scores[&member.id]), member.display_name()));
+    let name_width = members
+        .iter()
+        .map(|member| member.display_name().chars().count())
+        .chain(["solutions".chars().count()])
+        .max()
+        .unwrap_or_default();
+
+    let mut report = String::new();
+    writeln!(report, "Leaderboard {year}, {} members", members.len())?;
+    writeln!(report)?;
+    writeln!(
+        report,
+        "{:name_width$}  {:>5}  {:>11}  {:>10}",
+        "member", "stars", "local score", "recomputed"
+    )?;
+    for member in &members {
+        writeln!(
+            report,
+            "{:name_width$}  {:>5}  {:>11}  {:>10}",
+            member.display_name(),
+            member.stars,
+            member.local_score,
+            scores[&member.id]
+        )?;
+    }
+    for day in 1..=25 {
+        write_day(&mut report, &members, timings, name_width, year, day)?;
+    }
+    for member in &members {
+        writeln!(report)?;
+        writeln!(report, "{}", member.display_name())?;
+        for Star {
+            day,
+            puzzle,
+            timestamp,
+            ..
+        } in member.timeline()?
+        {
+            writeln!(
+                report,
+                "  {}  day {day:>2} {puzzle}",
+                eastern_time(timestamp)
+            )?;
+        }
+    }
+    Ok(report)
+}
+
+/// Writes the times since release and the deltas of the members that got a star on the day, if
+/// any did.
+fn write_day(
+    report: &mut String,
+    members: &[&Member],
+    timings: &BTreeMap<(Day, Puzzle), Duration>,
+    name_width: usize,
+    year: i64,
+    day: Day,
+) -> anyhow::Result<()> {
+    let solved: Vec<_> = members
+        .iter()
+        .filter(|member| member.completion_day_level.contains_key(&day.to_string()))
+        .collect();
+    if solved.is_empty() {
+        return Ok(());
+    }
+    let release = release(year, day);
+    writeln!(report)?;
+    writeln!(
+        report,
+        "{:name_width$}  {:>12}  {:>12}  {:>12}",
+        format!("day {day}"),
+        "first",
+        "second",
+        "delta"
+    )?;
+    for member in solved {
+        let timeline = member.timeline()?;
+        let since_release = |puzzle| {
+            timeline
+                .iter()
+                .find(|star| star.day == day && star.puzzle == puzzle)
+                .map_or_else(
+                    || "-".to_owned(),
+                    |star| clock(seconds(star.timestamp - release)),
+                )
+        };
+        let delta = member
+            .deltas()?
+            .get(&day)
+            .map_or_else(|| "-".to_owned(), |&delta| clock(delta));
+        writeln!(
+            report,
+            "{:name_width$}  {:>12}  {:>12}  {delta:>12}",
+            member.display_name(),
+            since_release(Puzzle::First),
+            since_release(Puzzle::Second),
+        )?;
+    }
+    let timing = |puzzle| {
+        timings.get(&(day, puzzle)).map(|duration| {
+            let milliseconds = duration.as_secs_f64() * 1000.0;
+            format!("{milliseconds:.3} ms")
+        })
+    };
+    let (first, second) = (timing(Puzzle::First), timing(Puzzle::Second));
+    if first.is_some() || second.is_some() {
+        writeln!(
+            report,
+            "{:name_width$}  {:>12}  {:>12}",
+            "solutions",
+            first.unwrap_or_else(|| "-".to_owned()),
+            second.unwrap_or_else(|| "-".to_owned()),
+        )?;
+    }
+    Ok(())
+}
+
+/// Advent of Code runs on US Eastern Standard Time, five hours behind UTC.
+const EASTERN_OFFSET: i64 = -5 * 60 * 60;
+
+/// The Unix time at which the puzzles of the day were released, at midnight Eastern time.
+///
+/// # Panics
+///
+/// Panics if the day does not fit in an `i64`.
+#[must_use]
+pub fn release(year: i64, day: Day) -> i64 {
+    let day = i64::try_from(day).expect("day should be small");
+    days_from_civil(year, 12, day) * 24 * 60 * 60 - EASTERN_OFFSET
+}
+
+/// The Unix time as a date and time in Eastern time, like `2022-12-01 00:05:12`.
+#[must_use]
+pub fn eastern_time(timestamp: i64) -> String {
+    let local = timestamp + EASTERN_OFFSET;
+    let (days, seconds) = (
+        local.div_euclid(24 * 60 * 60),
+        local.rem_euclid(24 * 60 * 60),
+    );
+    let (year, month, day) = civil_from_days(days);
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    format!("{year:04}-{month:02}-{day:02} {hours:02}:{minutes:02}:{seconds:02}")
+}
+
+/// The duration like `26:03:09` for 26 hours, 3 minutes and 9 seconds.
+#[must_use]
+pub fn clock(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    format!(
+        "{}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+fn seconds(seconds: i64) -> Duration {
+    Duration::from_secs(u64::try_from(seconds).unwrap_or_default())
+}
+
+/// The number of days since 1970-01-01 of the date in the proleptic Gregorian calendar.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// The date in the proleptic Gregorian calendar that is the number of days after 1970-01-01.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_from_march + 2) / 5 + 1;
+    let month = if month_from_march < 10 {
+        month_from_march + 3
+    } else {
+        month_from_march - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEADERBOARD: &str = r#"{
+        "event": "2022",
+        "owner_id": 1,
+        "members": {
+            "1": {
+                "id": 1, "name": "Alice", "stars": 3, "local_score": 8, "global_score": 0,
+                "last_star_ts": 1669958000,
+                "completion_day_level": {
+                    "1": {
+                        "1": { "get_star_ts": 1669871100, "star_index": 10 },
+                        "2": { "get_star_ts": 1669871400, "star_index": 12 }
+                    },
+                    "2": { "1": { "get_star_ts": 1669958000, "star_index": 40 } }
+                }
+            },
+            "2": {
+                "id": 2, "name": null, "stars": 2, "local_score": 5, "global_score": 0,
+                "last_star_ts": 1669872000,
+                "completion_day_level": {
+                    "1": {
+                        "1": { "get_star_ts": 1669871000, "star_index": 8 },
+                        "2": { "get_star_ts": 1669872000, "star_index": 14 }
+                    }
+                }
+            }
+        }
+    }"#;
+
+    #[test]
+    fn local_scores() {
+        let leaderboard = Leaderboard::parse(LEADERBOARD).expect("leaderboard should parse");
+        let scores = leaderboard
+            .local_scores()
+            .expect("stars should be within range");
+        assert_eq!(scores, BTreeMap::from([(1, 1 + 2 + 2), (2, 2 + 1)]));
+    }
+
+    #[test]
+    fn deltas() {
+        let leaderboard = Leaderboard::parse(LEADERBOARD).expect("leaderboard should parse");
+        let deltas = leaderboard.members["1"]
+            .deltas()
+            .expect("stars should be within range");
+        assert_eq!(deltas, BTreeMap::from([(1, Duration::from_mins(5))]));
+    }
+
+    #[test]
+    fn release() {
+        assert_eq!(super::release(2022, 1), 1_669_870_800);
+        assert_eq!(eastern_time(1_669_871_100), "2022-12-01 00:05:00");
+        assert_eq!(
+            clock(Duration::from_secs(26 * 3600 + 3 * 60 + 9)),
+            "26:03:09"
+        );
+    }
+
+    #[test]
+    fn report() {
+        let leaderboard = Leaderboard::parse(LEADERBOARD).expect("leaderboard should parse");
+        let timings = BTreeMap::from([((1, Puzzle::First), Duration::from_millis(2))]);
+        let report = super::report(&leaderboard, &timings).expect("report should succeed");
+        let expected = [
+            "Leaderboard 2022, 2 members",
+            "",
+            "member               stars  local score  recomputed",
+            "Alice                    3            8           5",
+            "(anonymous user #2)      2            5           3",
+            "",
+            "day 1                       first        second         delta",
+            "Alice                     0:05:00       0:10:00       0:05:00",
+            "(anonymous user #2)       0:03:20       0:20:00       0:16:40",
+            "solutions                2.000 ms             -",
+            "",
+            "day 2                       first        second         delta",
+            "Alice                     0:13:20             -             -",
+            "",
+            "Alice",
+            "  2022-12-01 00:05:00  day  1 first",
+            "  2022-12-01 00:10:00  day  1 second",
+            "  2022-12-02 00:13:20  day  2 first",
+            "",
+            "(anonymous user #2)",
+            "  2022-12-01 00:03:20  day  1 first",
+            "  2022-12-01 00:20:00  day  1 second",
+        ];
+        assert_eq!(report.lines().collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn non_ascii_names_aligned() {
+        let json = LEADERBOARD.replace("\"Alice\"", "\"Ålice Ørsted-Ünderwood\"");
+        let leaderboard = Leaderboard::parse(&json).expect("leaderboard should parse");
+        let report = super::report(&leaderboard, &BTreeMap::new()).expect("report should succeed");
+        let widths: Vec<_> = report
+            .lines()
+            .skip(2)
+            .take(3)
+            .map(|line| line.chars().count())
+            .collect();
+        assert!(
+            widths.iter().all(|&width| width == widths[0]),
+            "rows of table should be equally wide, but are {widths:?}"
+        );
+    }
+}
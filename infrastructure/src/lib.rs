@@ -1,14 +1,28 @@
+pub mod answers;
 pub mod cli;
+pub mod generate;
+pub mod leaderboard;
+pub mod memory;
+pub mod parameters;
+pub mod serve;
+pub mod submit;
+pub mod suite;
 pub mod test;
+pub mod trace;
+pub mod variants;
+pub mod year;
 
 use std::{fmt::Debug, fs, ops::Index};
 
-use anyhow::{Context, anyhow};
-use strum::EnumString;
+use anyhow::anyhow;
+use strum::{Display, EnumString};
+
+use crate::parameters::Parameters;
 
 pub type Day = usize;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Display, EnumString)]
+#[strum(serialize_all = "lowercase")]
 pub enum Puzzle {
     #[strum(ascii_case_insensitive)]
     First,
@@ -48,18 +62,17 @@ pub fn input(manifest_directory: &str, day: Day, input: Input) -> anyhow::Result
 pub type Solution = fn(&str) -> Answer;
 pub type Answer = String;
 
+/// Solves the input that `parameters` are for with `solution`.
+///
 /// # Errors
 ///
-/// Errors if the wanted puzzle input cannot be read or the wanted solution does not exist.
+/// Errors if the wanted input cannot be read.
 pub fn answer(
     manifest_directory: &str,
     day: Day,
-    puzzle: Puzzle,
-    solutions: &[[Solution; 2]],
+    solution: Solution,
+    parameters: &Parameters,
 ) -> anyhow::Result<Answer> {
-    let input = input(manifest_directory, day, Input::PuzzleInput)?;
-    let solution = solutions
-        .get(day - 1)
-        .context("solution to day should exist")?[puzzle];
-    Ok(solution(&input))
+    let input = input(manifest_directory, day, parameters.input())?;
+    Ok(parameters.scope(|| solution(&input)))
 }
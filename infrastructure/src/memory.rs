@@ -0,0 +1,127 @@
+use std::fmt::{self, Display};
+
+use allocator::Counters;
+use serde::Serialize;
+
+/// Counts what each thread allocates, so that [`measure`] can tell how much heap memory a solution
+/// uses, in every program that links this crate with the `count-allocations` feature.
+#[cfg(any(test, feature = "count-allocations"))]
+#[global_allocator]
+static ALLOCATOR: allocator::Counting = allocator::Counting;
+
+/// The heap memory that a run used. A reallocation counts as an allocation of the new size.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct Usage {
+    /// The most bytes allocated at once on top of what was allocated before the run.
+    pub peak: usize,
+
+    /// The bytes allocated in total.
+    pub total: usize,
+
+    /// The number of allocations.
+    pub allocations: usize,
+}
+
+impl Display for Usage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            peak,
+            total,
+            allocations,
+        } = *self;
+        write!(
+            f,
+            "{:>10} peak  {:>10} total  {allocations:>10} allocations",
+            bytes(peak),
+            bytes(total),
+        )
+    }
+}
+
+#[expect(
+    clippy::cast_precision_loss,
+    reason = "one decimal is shown, so the loss cannot be seen"
+)]
+fn bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut amount = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while amount >= 1024.0 && unit < UNITS.len() - 1 {
+        amount /= 1024.0;
+        unit += 1;
+    }
+    format!("{amount:.1} {}", UNITS[unit])
+}
+
+/// Runs `f` and returns its result along with the heap memory that the current thread used
+/// meanwhile, or `None` if the counting allocator is not the global allocator. Memory that other
+/// threads allocate is not included.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<Usage>) {
+    let start = allocator::counters();
+    allocator::set_counters(Counters {
+        peak: start.live,
+        ..start
+    });
+    let result = f();
+    let end = allocator::counters();
+    allocator::set_counters(Counters {
+        peak: end.peak.max(start.peak),
+        ..end
+    });
+    let usage = Usage {
+        peak: (end.peak - start.live).cast_unsigned(),
+        total: end.total - start.total,
+        allocations: end.allocations - start.allocations,
+    };
+    (result, allocator::counting().then_some(usage))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::hint::black_box;
+
+    use super::*;
+
+    #[test]
+    fn measure() {
+        let (length, usage) = super::measure(|| {
+            let first = black_box(vec![0_u8; 1000]);
+            drop(first);
+            let second = black_box(vec![0_u8; 3000]);
+            let third = black_box(vec![0_u8; 2000]);
+            second.len() + third.len()
+        });
+        assert_eq!(length, 5000);
+        let expected = Usage {
+            peak: 5000,
+            total: 6000,
+            allocations: 3,
+        };
+        assert_eq!(usage, Some(expected));
+    }
+
+    #[test]
+    fn nested() {
+        let (inner, outer) = super::measure(|| {
+            let vec = black_box(vec![0_u8; 100]);
+            let (_, inner) = super::measure(|| black_box(vec![0_u8; 400]).len());
+            drop(vec);
+            inner
+        });
+        let inner = inner.expect("counting allocator should be global allocator");
+        let outer = outer.expect("counting allocator should be global allocator");
+        assert_eq!(inner.peak, 400);
+        assert_eq!(outer.peak, 500);
+        assert_eq!(outer.allocations, 2);
+    }
+
+    #[test]
+    fn bytes() {
+        assert_eq!(super::bytes(1023), "1023 B");
+        assert_eq!(super::bytes(1536), "1.5 KiB");
+        assert_eq!(super::bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+}
@@ -0,0 +1,190 @@
+use std::{cell::RefCell, collections::BTreeMap, str::FromStr};
+
+use anyhow::bail;
+
+use crate::{Day, Input};
+
+thread_local! {
+    static CURRENT: RefCell<Parameters> = const { RefCell::new(Parameters::new(Input::PuzzleInput)) };
+}
+
+/// A value that a solution depends on besides its input, such as the size of a space that a
+/// puzzle description states separately for its examples.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Parameter<T> {
+    key: &'static str,
+    puzzle_input: T,
+    example: T,
+}
+
+impl<T> Parameter<T> {
+    #[must_use]
+    pub const fn new(key: &'static str, puzzle_input: T, example: T) -> Self {
+        Self {
+            key,
+            puzzle_input,
+            example,
+        }
+    }
+
+    #[must_use]
+    pub const fn key(&self) -> &'static str {
+        self.key
+    }
+}
+
+impl<T: Clone + FromStr> Parameter<T> {
+    /// The value overridden for the running solution, or else the value for the kind of input it
+    /// solves.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the overriding value cannot be parsed.
+    #[must_use]
+    pub fn get(&self) -> T {
+        CURRENT.with_borrow(|parameters| match parameters.overrides.get(self.key) {
+            Some(value) => value.parse().unwrap_or_else(|_| {
+                panic!(
+                    "value '{value}' of parameter '{}' should be valid",
+                    self.key
+                )
+            }),
+            None => match parameters.input {
+                Input::Example(_) => self.example.clone(),
+                Input::PuzzleInput => self.puzzle_input.clone(),
+            },
+        })
+    }
+}
+
+/// The keys of the parameters that each day declares. Days without parameters are left out.
+pub type Keys = [(Day, &'static [&'static str])];
+
+/// The kind of input that a solution runs on and the parameter values that override the ones
+/// for that kind of input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Parameters {
+    input: Input,
+    overrides: BTreeMap<String, String>,
+}
+
+impl Parameters {
+    #[must_use]
+    pub const fn new(input: Input) -> Self {
+        Self {
+            input,
+            overrides: BTreeMap::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn with(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.overrides.insert(key.into(), value.into());
+        self
+    }
+
+    #[must_use]
+    pub fn input(&self) -> Input {
+        self.input
+    }
+
+    /// Checks that the overridden parameters are ones that the day declares, so that a misspelled
+    /// key is not silently ignored.
+    ///
+    /// # Errors
+    ///
+    /// Errors if a parameter is overridden that the day does not declare.
+    pub fn check(&self, keys: &Keys, day: Day) -> anyhow::Result<()> {
+        let declared = keys
+            .iter()
+            .find_map(|&(keys_day, keys)| (keys_day == day).then_some(keys))
+            .unwrap_or_default();
+        if let Some(key) = self
+            .overrides
+            .keys()
+            .find(|key| !declared.contains(&key.as_str()))
+        {
+            let declared = if declared.is_empty() {
+                "none".to_owned()
+            } else {
+                declared.join(", ")
+            };
+            bail!("parameter '{key}' should be declared by day {day}, which declares {declared}");
+        }
+        Ok(())
+    }
+
+    /// Runs `f` with these parameters in effect for [`Parameter::get`] on the current thread, and
+    /// restores the previous ones afterwards, even if `f` panics.
+    pub fn scope<R>(&self, f: impl FnOnce() -> R) -> R {
+        struct Restore(Option<Parameters>);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                if let Some(previous) = self.0.take() {
+                    CURRENT.set(previous);
+                }
+            }
+        }
+
+        let _restore = Restore(Some(CURRENT.replace(self.clone())));
+        f()
+    }
+}
+
+impl Default for Parameters {
+    fn default() -> Self {
+        Self::new(Input::PuzzleInput)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::panic;
+
+    use super::*;
+
+    const SIZE: Parameter<usize> = Parameter::new("size", 71, 7);
+
+    #[test]
+    fn values_for_input() {
+        assert_eq!(SIZE.get(), 71);
+        let example = Parameters::new(Input::Example(0));
+        assert_eq!(example.scope(|| SIZE.get()), 7);
+        let overridden = example.with("size", "12");
+        assert_eq!(overridden.scope(|| SIZE.get()), 12);
+        assert_eq!(SIZE.get(), 71);
+    }
+
+    #[test]
+    fn check() {
+        const KEYS: &Keys = &[(1, &[SIZE.key()])];
+        let overridden = |key| Parameters::default().with(key, "12");
+        assert!(overridden("size").check(KEYS, 1).is_ok());
+        assert!(Parameters::default().check(KEYS, 2).is_ok());
+        let error = overridden("sise")
+            .check(KEYS, 1)
+            .expect_err("misspelled key should be rejected");
+        assert_eq!(
+            error.to_string(),
+            "parameter 'sise' should be declared by day 1, which declares size"
+        );
+        assert!(overridden("size").check(KEYS, 2).is_err());
+    }
+
+    #[test]
+    fn restored_after_panic() {
+        let example = Parameters::new(Input::Example(0));
+        let result = panic::catch_unwind(|| example.scope(|| panic!("solution should panic")));
+        assert!(result.is_err(), "scope should propagate the panic");
+        assert_eq!(SIZE.get(), 71);
+    }
+
+    #[test]
+    #[should_panic(expected = "value 'seven' of parameter 'size' should be valid")]
+    fn invalid_override() {
+        let _ = Parameters::default()
+            .with("size", "seven")
+            .scope(|| SIZE.get());
+    }
+}
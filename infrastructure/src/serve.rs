@@ -0,0 +1,627 @@
+use std::{
+    fmt::{self, Display},
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    num::NonZero,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{self, RecvTimeoutError},
+    },
+    thread,
+    time::Duration,
+};
+
+use anyhow::{anyhow, bail};
+use serde_json::{Value, json};
+
+use crate::{
+    Day, Input, Puzzle, cli,
+    parameters::Parameters,
+    suite::{self, Job, Outcome},
+    variants,
+    year::Year,
+};
+
+/// The years that a service answers puzzles of.
+pub type Years = &'static [Year];
+
+/// How many connections are handled at once. Further ones wait to be accepted.
+const CONNECTIONS: usize = 16;
+
+/// How long reading a request or writing a response may stall before the connection is dropped.
+const STALL_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Answers the HTTP requests that arrive at the listener, on a fixed number of connections at a
+/// time, with JSON:
+///
+/// - `GET /<year>/<day>` tells whether the puzzles of the day are implemented and which variants
+///   their solutions have.
+/// - `POST /<year>/<day>/<puzzle>` solves the input in the body of the request and tells the
+///   answer, how long it took and, if allocations are counted, how much heap memory it used. The
+///   query `?variant=<name>` selects a variant, `?example=<number>` the parameter values of an
+///   example, and any other `?<key>=<value>` overrides a parameter that the day declares. Keys
+///   and values are percent-decoded.
+///
+/// Failed requests are answered with an `error` and a fitting status. Solutions run on `workers`
+/// threads of their own and are answered with a timeout after `timeout`, but keep their thread
+/// until they finish, and a request that finds every thread busy is turned away. A panicking
+/// solution does not bring the service down.
+///
+/// # Errors
+///
+/// Errors if the listener fails to accept a connection.
+#[expect(
+    clippy::missing_panics_doc,
+    reason = "connection handlers keep their senders until they stop with an error"
+)]
+pub fn serve(
+    listener: TcpListener,
+    years: Years,
+    workers: NonZero<usize>,
+    timeout: Duration,
+) -> anyhow::Result<()> {
+    let listener = Arc::new(listener);
+    let solvers = Arc::new(Solvers::new(workers, timeout));
+    let (sender, receiver) = mpsc::channel();
+    for _ in 0..CONNECTIONS {
+        let (listener, solvers, sender) =
+            (Arc::clone(&listener), Arc::clone(&solvers), sender.clone());
+        thread::spawn(move || {
+            let error = loop {
+                match listener.accept() {
+                    Ok((stream, _)) => {
+                        if let Err(error) = handle(&stream, years, &solvers) {
+                            eprintln!("connection should be answered: {error}");
+                        }
+                    }
+                    Err(error) => break error,
+                }
+            };
+            // Only the first error is reported, after which nobody listens anymore.
+            sender.send(error).ok();
+        });
+    }
+    let error = receiver
+        .recv()
+        .expect("connection handlers should only stop with an error");
+    Err(anyhow::Error::new(error).context("connection should be accepted"))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Ok,
+    BadRequest,
+    NotFound,
+    MethodNotAllowed,
+    PayloadTooLarge,
+    InternalServerError,
+    ServiceUnavailable,
+    GatewayTimeout,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = match self {
+            Self::Ok => "200 OK",
+            Self::BadRequest => "400 Bad Request",
+            Self::NotFound => "404 Not Found",
+            Self::MethodNotAllowed => "405 Method Not Allowed",
+            Self::PayloadTooLarge => "413 Payload Too Large",
+            Self::InternalServerError => "500 Internal Server Error",
+            Self::ServiceUnavailable => "503 Service Unavailable",
+            Self::GatewayTimeout => "504 Gateway Timeout",
+        };
+        write!(f, "{status}")
+    }
+}
+
+type Failure = (Status, String);
+
+/// A job for a solver, with where to send its outcome.
+type Assignment = (Job, mpsc::Sender<Outcome>);
+
+/// The fixed number of threads that run solutions for requests. Solutions are never queued: a
+/// request either finds an idle thread or is turned away, so that neither a burst of requests nor
+/// solutions that outlive their timeout pile up work.
+struct Solvers {
+    assignments: mpsc::Sender<Assignment>,
+    idle: Arc<AtomicUsize>,
+    timeout: Duration,
+}
+
+impl Solvers {
+    fn new(number: NonZero<usize>, timeout: Duration) -> Self {
+        let (assignments, receiver) = mpsc::channel::<Assignment>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        let idle = Arc::new(AtomicUsize::new(number.get()));
+        for _ in 0..number.get() {
+            let (receiver, idle) = (Arc::clone(&receiver), Arc::clone(&idle));
+            thread::spawn(move || {
+                loop {
+                    let assignment = receiver
+                        .lock()
+                        .expect("assignments should not be poisoned")
+                        .recv();
+                    let Ok((job, sender)) = assignment else {
+                        return;
+                    };
+                    let outcome = suite::outcome(job);
+                    idle.fetch_add(1, Ordering::SeqCst);
+                    // Nobody waits for the outcome anymore if the solution has timed out.
+                    sender.send(outcome).ok();
+                }
+            });
+        }
+        Self {
+            assignments,
+            idle,
+            timeout,
+        }
+    }
+
+    /// Runs the job on an idle solver and waits for its outcome until the timeout.
+    fn solve(&self, job: Job) -> Result<Outcome, Failure> {
+        self.idle
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |idle| {
+                idle.checked_sub(1)
+            })
+            .map_err(|_| {
+                (
+                    Status::ServiceUnavailable,
+                    "solver should be idle, but all are busy".to_owned(),
+                )
+            })?;
+        let (sender, receiver) = mpsc::channel();
+        let failed = || {
+            (
+                Status::InternalServerError,
+                "solver should report outcome".to_owned(),
+            )
+        };
+        self.assignments.send((job, sender)).map_err(|_| failed())?;
+        match receiver.recv_timeout(self.timeout) {
+            Ok(outcome) => Ok(outcome),
+            Err(RecvTimeoutError::Timeout) => Ok(Outcome::Timeout(self.timeout)),
+            Err(RecvTimeoutError::Disconnected) => Err(failed()),
+        }
+    }
+}
+
+/// The most bytes that the body of a request may have, which is far more than any puzzle input.
+const MAXIMUM_BODY_LENGTH: usize = 1 << 20;
+
+/// The most bytes that the request line and each header may have.
+const MAXIMUM_LINE_LENGTH: u64 = 8 << 10;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Request {
+    method: String,
+    path: String,
+    query: Vec<(String, String)>,
+    body: String,
+}
+
+fn handle(stream: &TcpStream, years: Years, solvers: &Solvers) -> io::Result<()> {
+    stream.set_read_timeout(Some(STALL_TIMEOUT))?;
+    stream.set_write_timeout(Some(STALL_TIMEOUT))?;
+    let response = request(stream).and_then(|request| respond(&request, years, solvers));
+    let (status, body) = match response {
+        Ok(body) => (Status::Ok, body),
+        Err((status, error)) => (status, json!({ "error": error })),
+    };
+    let body = body.to_string();
+    let mut stream = stream;
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+    stream.flush()
+}
+
+fn request(stream: &TcpStream) -> Result<Request, Failure> {
+    let bad_request = |error: anyhow::Error| (Status::BadRequest, error.to_string());
+    let mut reader = BufReader::new(stream);
+    let request_line = line(&mut reader).map_err(bad_request)?;
+    let [method, target, _] = request_line
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .try_into()
+        .map_err(|_| {
+            bad_request(anyhow!(
+                "request line should consist of method, target and version"
+            ))
+        })?;
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let query = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            Ok((decode(key)?, decode(value)?))
+        })
+        .collect::<anyhow::Result<_>>()
+        .map_err(bad_request)?;
+
+    let mut content_length = 0;
+    loop {
+        let header = line(&mut reader).map_err(bad_request)?;
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':')
+            && name.eq_ignore_ascii_case("content-length")
+        {
+            content_length = value
+                .trim()
+                .parse()
+                .map_err(|_| bad_request(anyhow!("content length should be a number")))?;
+        }
+    }
+    if content_length > MAXIMUM_BODY_LENGTH {
+        return Err((
+            Status::PayloadTooLarge,
+            format!("body should have at most {MAXIMUM_BODY_LENGTH} bytes"),
+        ));
+    }
+    let mut body = vec![0; content_length];
+    reader
+        .read_exact(&mut body)
+        .map_err(|error| bad_request(error.into()))?;
+    let body = String::from_utf8(body).map_err(|_| bad_request(anyhow!("body should be UTF-8")))?;
+
+    Ok(Request {
+        method: method.to_owned(),
+        path: path.to_owned(),
+        query,
+        body,
+    })
+}
+
+/// Decodes a key or value of the query, in which `+` stands for a space and `%` followed by two
+/// hexadecimal digits for the byte that they spell.
+fn decode(str: &str) -> anyhow::Result<String> {
+    let mut bytes = Vec::with_capacity(str.len());
+    let mut rest = str.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        rest = tail;
+        match byte {
+            b'+' => bytes.push(b' '),
+            b'%' => {
+                let (hex, tail) = rest
+                    .split_at_checked(2)
+                    .filter(|(hex, _)| hex.iter().all(u8::is_ascii_hexdigit))
+                    .ok_or_else(|| {
+                        anyhow!("'%' in query '{str}' should be followed by two hexadecimal digits")
+                    })?;
+                let hex = String::from_utf8_lossy(hex);
+                bytes.push(u8::from_str_radix(&hex, 16)?);
+                rest = tail;
+            }
+            byte => bytes.push(byte),
+        }
+    }
+    String::from_utf8(bytes).map_err(|_| anyhow!("query '{str}' should decode to UTF-8"))
+}
+
+/// The next line of the head of the request, without its line break.
+fn line(reader: &mut impl BufRead) -> anyhow::Result<String> {
+    let mut line = String::new();
+    reader.take(MAXIMUM_LINE_LENGTH).read_line(&mut line)?;
+    if !line.ends_with('\n') {
+        bail!("line of request should end within {MAXIMUM_LINE_LENGTH} bytes");
+    }
+    Ok(line.trim_end().to_owned())
+}
+
+fn respond(request: &Request, years: Years, solvers: &Solvers) -> Result<Value, Failure> {
+    let segments: Vec<&str> = request.path.trim_matches('/').split('/').collect();
+    match (request.method.as_str(), segments.as_slice()) {
+        ("GET", &[year, day]) => Ok(metadata(self::year(years, year)?, self::day(day)?)),
+        ("POST", &[year, day, puzzle]) => {
+            let year = self::year(years, year)?;
+            let puzzle = puzzle
+                .parse()
+                .map_err(|_| not_found(format!("puzzle '{puzzle}' should be first or second")))?;
+            let (variant, parameters) =
+                options(&request.query).map_err(|error| (Status::BadRequest, error.to_string()))?;
+            let day = self::day(day)?;
+            parameters
+                .check(year.parameters, day)
+                .map_err(|error| (Status::BadRequest, error.to_string()))?;
+            let solution = year
+                .solutions(day, puzzle)
+                .map_err(|error| not_found(error.to_string()))?
+                .into_iter()
+                .find_map(|(name, solution)| (name == variant).then_some(solution))
+                .ok_or_else(|| not_found(format!("variant '{variant}' of puzzle should exist")))?;
+            let input = request.body.clone();
+            let job: Job = Box::new(move || Ok(parameters.scope(|| solution(&input))));
+            let answer = json!({
+                "year": year.year,
+                "day": day,
+                "puzzle": puzzle.to_string(),
+                "variant": variant,
+            });
+            outcome(answer, solvers.solve(job)?)
+        }
+        (method, &([_, _] | [_, _, _])) => Err((
+            Status::MethodNotAllowed,
+            format!("method '{method}' should be GET for a day or POST for a puzzle"),
+        )),
+        _ => Err(not_found(format!(
+            "path '{}' should be /<year>/<day> or /<year>/<day>/<puzzle>",
+            request.path
+        ))),
+    }
+}
+
+fn year(years: Years, year: &str) -> Result<&'static Year, Failure> {
+    years
+        .iter()
+        .find(|served| served.year.to_string() == year)
+        .ok_or_else(|| not_found(format!("year '{year}' should be served")))
+}
+
+fn day(day: &str) -> Result<Day, Failure> {
+    cli::day(day).map_err(|_| not_found(format!("day '{day}' should be between 1 and 25")))
+}
+
+/// The variant selected by the query and the parameters that it sets.
+fn options(query: &[(String, String)]) -> anyhow::Result<(String, Parameters)> {
+    let mut variant = variants::DEFAULT.to_owned();
+    let mut input = Input::PuzzleInput;
+    let mut overrides = vec![];
+    for (key, value) in query {
+        match key.as_str() {
+            "variant" => variant.clone_from(value),
+            "example" => {
+                let example = value
+                    .parse()
+                    .map_err(|_| anyhow!("example '{value}' should be a number"))?;
+                input = Input::Example(example);
+            }
+            "" => bail!("query should not contain an empty key"),
+            key => overrides.push((key.to_owned(), value.clone())),
+        }
+    }
+    let parameters = overrides
+        .into_iter()
+        .fold(Parameters::new(input), |parameters, (key, value)| {
+            parameters.with(key, value)
+        });
+    Ok((variant, parameters))
+}
+
+fn metadata(year: &Year, day: Day) -> Value {
+    let puzzles: Vec<Value> = [Puzzle::First, Puzzle::Second]
+        .into_iter()
+        .map(|puzzle| {
+            let variants: Vec<&str> = year
+                .solutions(day, puzzle)
+                .map(|solutions| solutions.into_iter().map(|(name, _)| name).collect())
+                .unwrap_or_default();
+            json!({
+                "puzzle": puzzle.to_string(),
+                "implemented": year.solved(day),
+                "variants": variants,
+            })
+        })
+        .collect();
+    json!({ "year": year.year, "day": day, "puzzles": puzzles })
+}
+
+fn outcome(mut answer: Value, outcome: Outcome) -> Result<Value, Failure> {
+    match outcome {
+        Outcome::Answer(value, duration, memory) => {
+            answer["answer"] = json!(value);
+            answer["milliseconds"] = json!(duration.as_secs_f64() * 1000.0);
+            answer["memory"] = json!(memory);
+            Ok(answer)
+        }
+        Outcome::Error(error) => Err((Status::InternalServerError, error)),
+        Outcome::Panic(message) => Err((
+            Status::InternalServerError,
+            format!("solution should not panic, but did: {message}"),
+        )),
+        Outcome::Timeout(timeout) => Err((
+            Status::GatewayTimeout,
+            format!("solution should finish within {} s", timeout.as_secs_f64()),
+        )),
+    }
+}
+
+fn not_found(error: String) -> Failure {
+    (Status::NotFound, error)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{thread, time::Duration};
+
+    use super::*;
+    use crate::{parameters::Parameter, variants::Variant};
+
+    const SIZE: Parameter<usize> = Parameter::new("size", 10, 2);
+
+    static YEARS: [Year; 1] = [Year {
+        year: 2022,
+        manifest_directory: "",
+        solutions: &[
+            Some([|input| input.len().to_string(), |_| panic!("second puzzle")]),
+            Some([
+                |_| {
+                    thread::sleep(Duration::from_secs(10));
+                    String::new()
+                },
+                |_| String::new(),
+            ]),
+            None,
+        ],
+        variants: &[Variant::new(1, Puzzle::First, "sized", |input| {
+            (input.len() * SIZE.get()).to_string()
+        })],
+        parameters: &[(1, &[SIZE.key()])],
+        generators: &[],
+    }];
+
+    /// Serves the years with the given number of solvers on a port of its own and returns its
+    /// base URL.
+    fn service(workers: usize) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("port should be available");
+        let address = listener.local_addr().expect("listener should have address");
+        let workers = NonZero::new(workers).expect("number of workers should not be zero");
+        thread::spawn(move || serve(listener, &YEARS, workers, Duration::from_millis(500)));
+        format!("http://{address}")
+    }
+
+    fn call(method: &str, url: &str, body: &str) -> (u16, Value) {
+        let agent = ureq::Agent::config_builder()
+            .http_status_as_error(false)
+            .build()
+            .new_agent();
+        let mut response = match method {
+            "GET" => agent.get(url).call(),
+            "PUT" => agent.put(url).send(body),
+            _ => agent.post(url).send(body),
+        }
+        .expect("request should succeed");
+        let body = response
+            .body_mut()
+            .read_to_string()
+            .expect("body should be readable");
+        let body = serde_json::from_str(&body).expect("body should be JSON");
+        (response.status().as_u16(), body)
+    }
+
+    #[test]
+    fn metadata() {
+        let base = service(4);
+        let (status, body) = call("GET", &format!("{base}/2022/1"), "");
+        assert_eq!(status, 200);
+        let expected = json!({
+            "year": 2022,
+            "day": 1,
+            "puzzles": [
+                { "puzzle": "first", "implemented": true, "variants": ["default", "sized"] },
+                { "puzzle": "second", "implemented": true, "variants": ["default"] },
+            ],
+        });
+        assert_eq!(body, expected);
+        for day in [3, 4] {
+            let (status, body) = call("GET", &format!("{base}/2022/{day}"), "");
+            assert_eq!(status, 200);
+            assert_eq!(body["puzzles"][0]["implemented"], json!(false));
+            assert_eq!(body["puzzles"][1]["variants"], json!([]));
+        }
+    }
+
+    #[test]
+    fn answer() {
+        let base = service(4);
+        let (status, body) = call("POST", &format!("{base}/2022/1/first"), "abc");
+        assert_eq!(status, 200);
+        assert_eq!(body["answer"], json!("3"));
+        assert_eq!(body["variant"], json!("default"));
+        assert!(
+            body["milliseconds"].is_f64(),
+            "answer should come with timing"
+        );
+
+        let sized = |query| {
+            let (_, body) = call("POST", &format!("{base}/2022/1/first?{query}"), "abc");
+            body["answer"].clone()
+        };
+        assert_eq!(sized("variant=sized"), json!("30"));
+        assert_eq!(sized("variant=sized&example=1"), json!("6"));
+        assert_eq!(sized("variant=sized&size=7"), json!("21"));
+        assert_eq!(sized("variant=size%64&%73ize=7"), json!("21"));
+    }
+
+    #[test]
+    fn stalled_connection() {
+        let base = service(4);
+        let _stalled = TcpStream::connect(base.trim_start_matches("http://"))
+            .expect("service should accept connection");
+        let (status, _) = call("GET", &format!("{base}/2022/1"), "");
+        assert_eq!(status, 200, "stalled connection should not hold up others");
+    }
+
+    #[test]
+    fn payload_too_large() {
+        let base = service(4);
+        let mut stream = TcpStream::connect(base.trim_start_matches("http://"))
+            .expect("service should accept connection");
+        write!(
+            stream,
+            "POST /2022/1/first HTTP/1.1\r\nContent-Length: {}\r\n\r\n",
+            MAXIMUM_BODY_LENGTH + 1
+        )
+        .expect("head should be sent");
+        let mut response = String::new();
+        stream
+            .read_to_string(&mut response)
+            .expect("response should be readable");
+        assert!(
+            response.starts_with("HTTP/1.1 413 "),
+            "oversized body should be refused: {response}"
+        );
+    }
+
+    #[test]
+    fn busy() {
+        let base = service(1);
+        let (status, _) = call("POST", &format!("{base}/2022/2/first"), "");
+        assert_eq!(status, 504);
+        let (status, body) = call("POST", &format!("{base}/2022/1/first"), "abc");
+        assert_eq!(
+            status, 503,
+            "timed-out solution should keep its solver busy"
+        );
+        assert!(body["error"].is_string(), "failure should come with error");
+        let (status, _) = call("GET", &format!("{base}/2022/1"), "");
+        assert_eq!(status, 200, "metadata should not need a solver");
+    }
+
+    #[test]
+    fn decode() {
+        assert_eq!(
+            super::decode("a+b%2Fc%C3%A9").ok(),
+            Some("a b/cé".to_owned())
+        );
+        for invalid in ["%2", "%zz", "%+1", "%FF"] {
+            assert!(
+                super::decode(invalid).is_err(),
+                "'{invalid}' should be invalid"
+            );
+        }
+    }
+
+    #[test]
+    fn failures() {
+        let base = service(4);
+        let cases = [
+            ("POST", "/2022/1/second", 500),
+            ("POST", "/2022/2/first", 504),
+            ("POST", "/2022/1/first?variant=fast", 404),
+            ("POST", "/2022/1/third", 404),
+            ("POST", "/2022/3/first", 404),
+            ("POST", "/2022/1/first?example=x", 400),
+            ("POST", "/2022/1/first?sise=7", 400),
+            ("POST", "/2022/1/first?variant=%zz", 400),
+            ("GET", "/2021/1", 404),
+            ("GET", "/2022/26", 404),
+            ("GET", "/2022", 404),
+            ("PUT", "/2022/1/first", 405),
+        ];
+        for (method, path, expected) in cases {
+            let (status, body) = call(method, &format!("{base}{path}"), "abc");
+            assert_eq!(
+                status, expected,
+                "{method} {path} should fail with {expected}"
+            );
+            assert!(body["error"].is_string(), "failure should come with error");
+        }
+    }
+}
@@ -0,0 +1,319 @@
+use std::{
+    fmt::{self, Display},
+    time::Duration,
+};
+
+use anyhow::{Context, anyhow, bail};
+use strum::{Display, EnumString};
+
+use crate::{Day, Puzzle, answers};
+
+/// The address of Advent of Code, to which answers are submitted unless another one is given.
+pub const DEFAULT_URL: &str = "https://adventofcode.com";
+
+/// The environment variable that holds the session cookie of the logged-in user.
+pub const SESSION_VARIABLE: &str = "AOC_SESSION";
+
+/// What Advent of Code says about a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong(Option<Hint>),
+    RateLimited(Duration),
+    AlreadySolved,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Correct => write!(f, "correct"),
+            Self::Wrong(None) => write!(f, "wrong"),
+            Self::Wrong(Some(hint)) => write!(f, "wrong, {hint}"),
+            Self::RateLimited(wait) => {
+                write!(f, "rate limited, wait {} s", wait.as_secs())
+            }
+            Self::AlreadySolved => write!(f, "already solved"),
+        }
+    }
+}
+
+/// Which way a wrong answer is off.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, EnumString)]
+#[strum(serialize_all = "kebab-case")]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+/// Where and as whom answers are submitted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Client {
+    base_url: String,
+    session: String,
+}
+
+impl Client {
+    #[must_use]
+    pub fn new(base_url: impl Into<String>, session: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            session: session.into(),
+        }
+    }
+
+    /// A client for `base_url` with the session from the environment.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the session variable is not set.
+    pub fn from_environment(base_url: impl Into<String>) -> anyhow::Result<Self> {
+        let session = std::env::var(SESSION_VARIABLE)
+            .with_context(|| format!("environment variable '{SESSION_VARIABLE}' should be set"))?;
+        Ok(Self::new(base_url, session))
+    }
+
+    fn post(&self, year: usize, day: Day, puzzle: Puzzle, answer: &str) -> anyhow::Result<String> {
+        let Self { base_url, session } = self;
+        let level = match puzzle {
+            Puzzle::First => "1",
+            Puzzle::Second => "2",
+        };
+        let page = ureq::post(format!("{base_url}/{year}/day/{day}/answer"))
+            .header("Cookie", format!("session={session}"))
+            .header(
+                "User-Agent",
+                "github.com/PatrickKasting/advent-of-code by Patrick Kasting",
+            )
+            .send_form([("level", level), ("answer", answer)])?
+            .body_mut()
+            .read_to_string()?;
+        Ok(page)
+    }
+}
+
+/// Submits the answer to the puzzle unless the registry of answers already tells what Advent of
+/// Code would say, and records accepted and rejected answers in the registry.
+///
+/// # Errors
+///
+/// Errors if the answer is known to be wrong, the request fails, the response cannot be
+/// understood or the registry cannot be read or written.
+pub fn submit(
+    manifest_directory: &str,
+    year: usize,
+    day: Day,
+    puzzle: Puzzle,
+    answer: &str,
+    client: &Client,
+) -> anyhow::Result<Verdict> {
+    if let Some(accepted) = answers::registered(manifest_directory, day, puzzle)? {
+        if accepted == answer {
+            return Ok(Verdict::AlreadySolved);
+        }
+        bail!("answer '{answer}' should equal accepted answer '{accepted}'");
+    }
+    let rejected = answers::rejected(manifest_directory, day, puzzle)?;
+    if let Some((rejected, hint)) = answers::known_wrong(&rejected, answer) {
+        let verdict = Verdict::Wrong(*hint);
+        bail!("answer '{answer}' should not be known to be wrong, but '{rejected}' was {verdict}");
+    }
+    let verdict = verdict(&client.post(year, day, puzzle, answer)?)?;
+    match verdict {
+        Verdict::Correct => answers::register(manifest_directory, day, puzzle, answer)?,
+        Verdict::Wrong(hint) => answers::reject(manifest_directory, day, puzzle, answer, hint)?,
+        Verdict::RateLimited(_) | Verdict::AlreadySolved => (),
+    }
+    Ok(verdict)
+}
+
+/// The verdict that the response page to a submission states.
+///
+/// # Errors
+///
+/// Errors if the page states none of the known verdicts.
+pub fn verdict(page: &str) -> anyhow::Result<Verdict> {
+    if page.contains("That's the right answer") {
+        Ok(Verdict::Correct)
+    } else if page.contains("That's not the right answer") {
+        let hint = if page.contains("your answer is too high") {
+            Some(Hint::TooHigh)
+        } else if page.contains("your answer is too low") {
+            Some(Hint::TooLow)
+        } else {
+            None
+        };
+        Ok(Verdict::Wrong(hint))
+    } else if page.contains("You gave an answer too recently") {
+        let wait = page
+            .split_once("You have ")
+            .and_then(|(_, rest)| rest.split_once(" left to wait"))
+            .context("response should state how long to wait")?
+            .0;
+        Ok(Verdict::RateLimited(duration(wait)?))
+    } else if page.contains("You don't seem to be solving the right level") {
+        Ok(Verdict::AlreadySolved)
+    } else {
+        Err(anyhow!("response should state a verdict"))
+    }
+}
+
+/// Parses a duration like `1m 5s`.
+fn duration(str: &str) -> anyhow::Result<Duration> {
+    let mut seconds = 0;
+    for part in str.split_whitespace() {
+        let (amount, unit) = [("h", 60 * 60), ("m", 60), ("s", 1)]
+            .into_iter()
+            .find_map(|(suffix, unit)| Some((part.strip_suffix(suffix)?, unit)))
+            .with_context(|| format!("unit of duration '{str}' should be 'h', 'm' or 's'"))?;
+        let amount: u64 = amount
+            .parse()
+            .map_err(|_| anyhow!("duration '{str}' should consist of numbers with units"))?;
+        seconds += amount * unit;
+    }
+    Ok(Duration::from_secs(seconds))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        env, fs,
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    use super::*;
+
+    const CORRECT: &str =
+        "That's the right answer! You are one gold star closer to saving Christmas.";
+    const TOO_HIGH: &str = "That's not the right answer; your answer is too high. \
+        Please wait one minute before trying again.";
+    const TOO_LOW: &str = "That's not the right answer; your answer is too low.";
+    const RATE_LIMITED: &str = "You gave an answer too recently; you have to wait after \
+        submitting an answer before trying again.  You have 1m 5s left to wait.";
+    const ALREADY_SOLVED: &str =
+        "You don't seem to be solving the right level.  Did you already complete it?";
+
+    /// Answers the submissions of the given number of requests like Advent of Code would if the
+    /// correct answer were 42, and returns its base URL.
+    fn stub_server(number_of_requests: usize) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("port should be available");
+        let address = listener.local_addr().expect("listener should have address");
+        thread::spawn(move || {
+            for stream in listener.incoming().take(number_of_requests) {
+                let stream = stream.expect("connection should succeed");
+                let mut reader = BufReader::new(&stream);
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader
+                        .read_line(&mut line)
+                        .expect("header should be readable");
+                    let line = line.trim_end().to_ascii_lowercase();
+                    if line.is_empty() {
+                        break;
+                    }
+                    if let Some(length) = line.strip_prefix("content-length: ") {
+                        content_length = length.parse().expect("length should be a number");
+                    }
+                }
+                let mut body = vec![0; content_length];
+                reader
+                    .read_exact(&mut body)
+                    .expect("body should be readable");
+                let body = String::from_utf8(body).expect("body should be text");
+                let answer: i32 = body
+                    .split('&')
+                    .find_map(|field| field.strip_prefix("answer="))
+                    .expect("body should contain answer")
+                    .parse()
+                    .expect("answer should be a number");
+                let message = match answer.cmp(&42) {
+                    std::cmp::Ordering::Less => TOO_LOW,
+                    std::cmp::Ordering::Equal => CORRECT,
+                    std::cmp::Ordering::Greater => TOO_HIGH,
+                };
+                let page = format!(
+                    "<html><body><main><article><p>{message}</p></article></main></body></html>"
+                );
+                write!(
+                    &stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{page}",
+                    page.len()
+                )
+                .expect("response should be writable");
+            }
+        });
+        format!("http://{address}")
+    }
+
+    fn manifest_directory(name: &str) -> String {
+        let directory = env::temp_dir().join(format!("submit-{name}-{}", std::process::id()));
+        fs::remove_dir_all(&directory).ok();
+        directory.to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn verdict() {
+        let cases = [
+            (CORRECT, Verdict::Correct),
+            (TOO_HIGH, Verdict::Wrong(Some(Hint::TooHigh))),
+            (TOO_LOW, Verdict::Wrong(Some(Hint::TooLow))),
+            ("That's not the right answer.", Verdict::Wrong(None)),
+            (RATE_LIMITED, Verdict::RateLimited(Duration::from_secs(65))),
+            (ALREADY_SOLVED, Verdict::AlreadySolved),
+        ];
+        crate::test::cases(
+            |page| super::verdict(page).expect("verdict should be stated"),
+            cases,
+        );
+        assert!(super::verdict("<html></html>").is_err());
+    }
+
+    #[test]
+    fn duration() {
+        let duration = super::duration("1h 2m 5s").expect("duration should be valid");
+        assert_eq!(duration, Duration::from_secs(3725));
+        for invalid in ["5", "5x", "5é", "é", "m"] {
+            assert!(
+                super::duration(invalid).is_err(),
+                "duration '{invalid}' should be invalid"
+            );
+        }
+    }
+
+    #[test]
+    fn submit() {
+        let manifest_directory = manifest_directory("submit");
+        let client = Client::new(stub_server(3), "session");
+        let submit =
+            |answer| super::submit(&manifest_directory, 2022, 1, Puzzle::First, answer, &client);
+
+        let too_high = submit("50").expect("submission should succeed");
+        assert_eq!(too_high, Verdict::Wrong(Some(Hint::TooHigh)));
+        assert!(
+            submit("60").is_err(),
+            "answer above too-high answer should not be submitted"
+        );
+        let too_low = submit("7").expect("submission should succeed");
+        assert_eq!(too_low, Verdict::Wrong(Some(Hint::TooLow)));
+        assert!(
+            submit("7").is_err(),
+            "rejected answer should not be submitted again"
+        );
+        let correct = submit("42").expect("submission should succeed");
+        assert_eq!(correct, Verdict::Correct);
+
+        let registered = answers::registered(&manifest_directory, 1, Puzzle::First)
+            .expect("registry should be readable");
+        assert_eq!(registered.as_deref(), Some("42"));
+        let resubmitted = submit("42").expect("accepted answer should not be submitted again");
+        assert_eq!(resubmitted, Verdict::AlreadySolved);
+        assert!(
+            submit("43").is_err(),
+            "answer other than accepted one should be wrong"
+        );
+
+        fs::remove_dir_all(&manifest_directory).ok();
+    }
+}
@@ -0,0 +1,259 @@
+use std::{
+    any::Any,
+    collections::BTreeMap,
+    fmt::{self, Display},
+    iter::Enumerate,
+    num::NonZero,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        Arc, Mutex,
+        mpsc::{self, RecvTimeoutError, Sender},
+    },
+    thread,
+    time::{Duration, Instant},
+    vec,
+};
+
+use crate::{
+    Answer,
+    memory::{self, Usage},
+};
+
+/// A solution applied to its input, ready to run on another thread.
+pub type Job = Box<dyn FnOnce() -> anyhow::Result<Answer> + Send>;
+
+/// What became of running a job. An answer comes with how long the job took and, if allocations
+/// are counted, how much heap memory it used.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Answer(Answer, Duration, Option<Usage>),
+    Error(String),
+    Panic(String),
+    Timeout(Duration),
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Answer(answer, duration, memory) => {
+                let milliseconds = duration.as_secs_f64() * 1000.0;
+                write!(f, "{milliseconds:>12.3} ms  ")?;
+                if let Some(memory) = memory {
+                    write!(f, "{memory}  ")?;
+                }
+                write!(f, "{answer}")
+            }
+            Self::Error(error) => write!(f, "error: {error}"),
+            Self::Panic(message) => write!(f, "panic: {message}"),
+            Self::Timeout(timeout) => write!(f, "timeout after {} s", timeout.as_secs_f64()),
+        }
+    }
+}
+
+/// Runs the jobs on a pool of `number_of_workers` threads and returns their outcomes in the order
+/// of the jobs. A job that runs for longer than `timeout` is reported as timed out, but it cannot
+/// be stopped: it keeps its thread until it finishes and may still be running when this returns,
+/// while a new worker takes its place in the pool. Panics are caught and reported with their
+/// messages, which the panic hook prints as usual.
+#[must_use]
+#[expect(
+    clippy::missing_panics_doc,
+    reason = "workers catch the panics of jobs and report every job they take"
+)]
+pub fn run<Key>(
+    jobs: Vec<(Key, Job)>,
+    number_of_workers: NonZero<usize>,
+    timeout: Duration,
+) -> Vec<(Key, Outcome)> {
+    let (keys, jobs): (Vec<_>, Vec<_>) = jobs.into_iter().unzip();
+    let mut outcomes: Vec<Option<Outcome>> = vec![None; jobs.len()];
+    let queue = Arc::new(Mutex::new(jobs.into_iter().enumerate()));
+    let (sender, receiver) = mpsc::channel();
+    let spawn_worker = || {
+        let (queue, sender) = (Arc::clone(&queue), sender.clone());
+        thread::spawn(move || work(&queue, &sender));
+    };
+    for _ in 0..number_of_workers.get().min(outcomes.len()) {
+        spawn_worker();
+    }
+
+    let mut running: BTreeMap<usize, Instant> = BTreeMap::new();
+    while outcomes.iter().any(Option::is_none) {
+        let message = match running.values().min() {
+            Some(&start) => {
+                receiver.recv_timeout((start + timeout).saturating_duration_since(Instant::now()))
+            }
+            None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+        match message {
+            Ok(Message::Started(index, start)) => {
+                running.insert(index, start);
+            }
+            Ok(Message::Finished(index, outcome)) => {
+                // A job that has timed out is no longer running, and its outcome comes too late.
+                if running.remove(&index).is_some() {
+                    outcomes[index] = Some(outcome);
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                let now = Instant::now();
+                running.retain(|&index, &mut start| {
+                    let timed_out = now.duration_since(start) >= timeout;
+                    if timed_out {
+                        outcomes[index] = Some(Outcome::Timeout(timeout));
+                        spawn_worker();
+                    }
+                    !timed_out
+                });
+            }
+            Err(RecvTimeoutError::Disconnected) => panic!("workers should report every job"),
+        }
+    }
+    keys.into_iter()
+        .zip(outcomes.into_iter().flatten())
+        .collect()
+}
+
+/// The number of threads that the machine can run in parallel, or one if it is unknown.
+#[must_use]
+pub fn default_number_of_workers() -> NonZero<usize> {
+    thread::available_parallelism().unwrap_or(NonZero::<usize>::MIN)
+}
+
+type Queue = Mutex<Enumerate<vec::IntoIter<Job>>>;
+
+enum Message {
+    Started(usize, Instant),
+    Finished(usize, Outcome),
+}
+
+/// Runs jobs from the queue until it is empty or nobody listens for their outcomes anymore.
+fn work(queue: &Queue, sender: &Sender<Message>) {
+    loop {
+        let next = queue.lock().expect("queue should not be poisoned").next();
+        let Some((index, job)) = next else {
+            return;
+        };
+        if sender
+            .send(Message::Started(index, Instant::now()))
+            .is_err()
+        {
+            return;
+        }
+        if sender.send(Message::Finished(index, outcome(job))).is_err() {
+            return;
+        }
+    }
+}
+
+/// Runs the job on the current thread and tells what became of it. A panic is caught, so that it
+/// becomes an outcome like any other.
+#[must_use]
+pub fn outcome(job: Job) -> Outcome {
+    let start = Instant::now();
+    match panic::catch_unwind(AssertUnwindSafe(|| memory::measure(job))) {
+        Ok((Ok(answer), memory)) => Outcome::Answer(answer, start.elapsed(), memory),
+        Ok((Err(error), _)) => Outcome::Error(format!("{error:#}")),
+        Err(payload) => Outcome::Panic(panic_message(payload.as_ref())),
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(&message) = payload.downcast_ref::<&str>() {
+        message.to_owned()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "panic payload should be a string".to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::anyhow;
+
+    use super::*;
+
+    #[test]
+    fn run() {
+        let jobs: Vec<(usize, Job)> = vec![
+            (1, Box::new(|| Ok("answer".to_owned()))),
+            (2, Box::new(|| Err(anyhow!("input should exist")))),
+            (3, Box::new(|| unimplemented!())),
+            (
+                4,
+                Box::new(|| {
+                    thread::sleep(Duration::from_secs(2));
+                    Ok("late answer".to_owned())
+                }),
+            ),
+            (5, Box::new(|| panic!("{} should be even", 3))),
+        ];
+        let timeout = Duration::from_millis(200);
+        let outcomes = super::run(
+            jobs,
+            NonZero::new(2).expect("two should not be zero"),
+            timeout,
+        );
+        let keys: Vec<_> = outcomes.iter().map(|&(key, _)| key).collect();
+        assert_eq!(keys, [1, 2, 3, 4, 5]);
+        assert!(
+            matches!(&outcomes[0].1, Outcome::Answer(answer, ..) if answer == "answer"),
+            "first job should answer"
+        );
+        let expected = [
+            Outcome::Error("input should exist".to_owned()),
+            Outcome::Panic("not implemented".to_owned()),
+            Outcome::Timeout(timeout),
+            Outcome::Panic("3 should be even".to_owned()),
+        ];
+        let actual: Vec<_> = outcomes
+            .into_iter()
+            .skip(1)
+            .map(|(_, outcome)| outcome)
+            .collect();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn timed_out_jobs_are_replaced() {
+        let hanging = || -> Job {
+            Box::new(|| {
+                thread::sleep(Duration::from_mins(1));
+                Ok("late answer".to_owned())
+            })
+        };
+        let answering = || -> Job { Box::new(|| Ok("answer".to_owned())) };
+        let jobs: Vec<(usize, Job)> = vec![
+            (1, hanging()),
+            (2, hanging()),
+            (3, hanging()),
+            (4, answering()),
+            (5, answering()),
+        ];
+        let timeout = Duration::from_millis(200);
+        let start = Instant::now();
+        let outcomes = super::run(
+            jobs,
+            NonZero::new(2).expect("two should not be zero"),
+            timeout,
+        );
+        assert!(
+            start.elapsed() < Duration::from_secs(5),
+            "remaining jobs should not wait for the hanging ones"
+        );
+        for (key, outcome) in &outcomes[..3] {
+            assert_eq!(
+                *outcome,
+                Outcome::Timeout(timeout),
+                "job {key} should time out"
+            );
+        }
+        for (key, outcome) in &outcomes[3..] {
+            assert!(
+                matches!(outcome, Outcome::Answer(answer, ..) if answer == "answer"),
+                "job {key} should answer"
+            );
+        }
+    }
+}
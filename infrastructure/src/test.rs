@@ -1,25 +1,43 @@
 use std::fmt::{Debug, Display};
 
-use crate::{Day, Input, Puzzle, Solution};
+use crate::{
+    Day, Input, Puzzle, Solution,
+    generate::{Generator, generated},
+    parameters::Parameters,
+    year::Year,
+};
 
+/// Runs the solution and all its variants with the parameters for the kind of input.
+///
 /// # Panics
 ///
-/// Panics if the return value of the solution applied to the input does not equal
-/// `expected.to_string()`.
-pub fn on_input(
-    inputs: fn(Day, Input) -> String,
-    solutions: &[[Solution; 2]],
-    day: Day,
-    puzzle: Puzzle,
-    input: Input,
-    expected: impl Display,
-) {
-    let actual = solutions[day - 1][puzzle](&inputs(day, input));
-    assert_eq!(
-        actual,
-        expected.to_string(),
-        "actual answer '{actual}' should equal expected answer '{expected}'"
-    );
+/// Panics if the return value of a solution applied to the input does not equal
+/// `expected.to_string()`, or if the input is the puzzle input and `expected` differs from the
+/// answer in the registry of accepted answers.
+pub fn on_input(year: &Year, day: Day, puzzle: Puzzle, input: Input, expected: impl Display) {
+    if input == Input::PuzzleInput
+        && let Some(registered) = year
+            .registered(day, puzzle)
+            .expect("registry of accepted answers should be readable")
+    {
+        assert_eq!(
+            expected.to_string(),
+            registered,
+            "expected answer '{expected}' should equal accepted answer '{registered}'"
+        );
+    }
+    let named_solutions = year
+        .solutions(day, puzzle)
+        .expect("solution to day should exist");
+    let input_text = year.input(day, input).expect("input should exist");
+    for (name, solution) in named_solutions {
+        let actual = Parameters::new(input).scope(|| solution(&input_text));
+        assert_eq!(
+            actual,
+            expected.to_string(),
+            "actual answer '{actual}' of variant '{name}' should equal expected answer '{expected}'"
+        );
+    }
 }
 
 /// # Panics
@@ -39,6 +57,34 @@ pub fn cases<Input: Debug + Clone, Answer: Debug + Eq>(
     }
 }
 
+/// Runs all `solutions`, such as a fast one and a brute-force one, on the inputs that
+/// `generator` generates from the seeds `0..number_of_inputs`.
+///
+/// # Panics
+///
+/// Panics if the solutions disagree on one of the inputs, with its seed and the input itself.
+pub fn agree_on_generated_inputs(
+    generator: &dyn Generator,
+    size: usize,
+    number_of_inputs: u64,
+    solutions: &[Solution],
+) {
+    for seed in 0..number_of_inputs {
+        let input = generated(generator, seed, size);
+        let answers: Vec<_> = solutions.iter().map(|solution| solution(&input)).collect();
+        if let Some(different) = answers.iter().find(|&answer| *answer != answers[0]) {
+            panic_left_right(
+                &format!(
+                    "answers to input generated from seed {seed} should agree
+{input}"
+                ),
+                &answers[0],
+                different,
+            );
+        }
+    }
+}
+
 /// # Panics
 ///
 /// Panics with the message `msg` followed by `left` and `right`.
@@ -0,0 +1,194 @@
+use std::{
+    cell::RefCell,
+    fmt::{self, Display},
+    rc::Rc,
+};
+
+type Sink = Box<dyn FnMut(&Event)>;
+
+thread_local! {
+    static SINK: RefCell<Option<Sink>> = const { RefCell::new(None) };
+}
+
+/// Something noteworthy that happened while solving, such as a cycle being detected, with named
+/// values that describe it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    name: &'static str,
+    fields: Vec<(&'static str, String)>,
+}
+
+impl Event {
+    #[must_use]
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            fields: vec![],
+        }
+    }
+
+    #[must_use]
+    pub fn field(mut self, key: &'static str, value: impl Display) -> Self {
+        self.fields.push((key, value.to_string()));
+        self
+    }
+
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The value of the field with the given key, if the event has one.
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find_map(|(field_key, value)| (*field_key == key).then_some(value.as_str()))
+    }
+}
+
+/// Shows the event on one line, like `cycle detected: start=3 length=7`, except for values that
+/// span several lines, such as grid snapshots, which follow on lines of their own.
+impl Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:", self.name)?;
+        let (multiline, inline): (Vec<_>, Vec<_>) = self
+            .fields
+            .iter()
+            .partition(|(_, value)| value.trim_end().contains('\n'));
+        for (key, value) in inline {
+            write!(f, " {key}={value}")?;
+        }
+        for (key, value) in multiline {
+            write!(f, "\n  {key}:")?;
+            for line in value.trim_end().lines() {
+                write!(f, "\n    {line}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Whether events are traced on the current thread.
+#[must_use]
+pub fn enabled() -> bool {
+    SINK.with_borrow(Option::is_some)
+}
+
+/// Traces the event named `name` with the fields that `fields` adds to it. Unless tracing is
+/// enabled, `fields` is not called, so that tracing costs nothing but the check. The sink is taken
+/// out while it handles the event, so events traced meanwhile, such as by the sink itself, are
+/// dropped.
+pub fn event(name: &'static str, fields: impl FnOnce(Event) -> Event) {
+    struct PutBack(Option<Sink>);
+
+    impl Drop for PutBack {
+        fn drop(&mut self) {
+            SINK.set(self.0.take());
+        }
+    }
+
+    if enabled() {
+        let event = fields(Event::new(name));
+        let mut sink = PutBack(SINK.take());
+        if let Some(sink) = &mut sink.0 {
+            sink(&event);
+        }
+    }
+}
+
+/// Runs `f` with the events that it traces on the current thread passed to `sink`, and restores
+/// the previous sink afterwards, even if `f` panics.
+pub fn scope<R>(sink: impl FnMut(&Event) + 'static, f: impl FnOnce() -> R) -> R {
+    struct Restore(Option<Sink>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            SINK.set(self.0.take());
+        }
+    }
+
+    let _restore = Restore(SINK.replace(Some(Box::new(sink))));
+    f()
+}
+
+/// Runs `f` and returns its result along with the events that it traced on the current thread.
+pub fn capture<R>(f: impl FnOnce() -> R) -> (R, Vec<Event>) {
+    let events = Rc::new(RefCell::new(vec![]));
+    let sink = {
+        let events = Rc::clone(&events);
+        move |event: &Event| events.borrow_mut().push(event.clone())
+    };
+    let result = scope(sink, f);
+    (result, events.take())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cycle_length() -> usize {
+        event("cycle detected", |event| {
+            event
+                .field("start", 3)
+                .field("length", 7)
+                .field("snapshot", "#.\n.#\n")
+        });
+        7
+    }
+
+    #[test]
+    fn capture() {
+        assert!(!enabled(), "tracing should be disabled by default");
+        let (length, events) = super::capture(cycle_length);
+        assert_eq!(length, 7);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].name(), "cycle detected");
+        assert_eq!(events[0].get("length"), Some("7"));
+        assert_eq!(events[0].get("end"), None);
+        let expected = "cycle detected: start=3 length=7\n  snapshot:\n    #.\n    .#";
+        assert_eq!(events[0].to_string(), expected);
+        assert!(!enabled(), "tracing should be disabled after capture");
+    }
+
+    #[test]
+    fn disabled() {
+        let mut called = false;
+        event("cycle detected", |event| {
+            called = true;
+            event
+        });
+        assert!(
+            !called,
+            "fields of event should not be computed while tracing is disabled"
+        );
+    }
+
+    #[test]
+    fn reentrant() {
+        let ((), events) = super::capture(|| {
+            scope(
+                |event| super::event("handled", |handled| handled.field("event", event.name())),
+                || event("traced", |event| event),
+            );
+            event("after", |event| event);
+        });
+        let names = events.iter().map(Event::name).collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            ["after"],
+            "events traced by the sink should be dropped"
+        );
+    }
+
+    #[test]
+    fn nested() {
+        let (((), inner), outer) = super::capture(|| {
+            event("outer", |event| event);
+            super::capture(|| event("inner", |event| event))
+        });
+        let names = |events: &[Event]| events.iter().map(Event::name).collect::<Vec<_>>();
+        assert_eq!(names(&inner), ["inner"]);
+        assert_eq!(names(&outer), ["outer"]);
+    }
+}
@@ -0,0 +1,163 @@
+use std::time::{Duration, Instant};
+
+use anyhow::Context;
+
+use crate::{
+    Answer, Day, Puzzle, Solution, input,
+    memory::{self, Usage},
+    parameters::Parameters,
+};
+
+/// The name of the solution to a puzzle in the table of solutions, as opposed to the ones in the
+/// table of variants.
+pub const DEFAULT: &str = "default";
+
+/// An alternative solution to a puzzle, such as a brute-force one, that is selected by its name.
+#[derive(Debug, Clone, Copy)]
+pub struct Variant {
+    pub day: Day,
+    pub puzzle: Puzzle,
+    pub name: &'static str,
+    pub solution: Solution,
+}
+
+impl Variant {
+    #[must_use]
+    pub const fn new(day: Day, puzzle: Puzzle, name: &'static str, solution: Solution) -> Self {
+        Self {
+            day,
+            puzzle,
+            name,
+            solution,
+        }
+    }
+}
+
+/// A solution with the name that selects it.
+pub type NamedSolution = (&'static str, Solution);
+
+/// The default solution to the puzzle followed by its variants, each with its name.
+///
+/// # Errors
+///
+/// Errors if the default solution does not exist or the day is not solved yet.
+pub fn named_solutions(
+    solutions: &[Option<[Solution; 2]>],
+    variants: &[Variant],
+    day: Day,
+    puzzle: Puzzle,
+) -> anyhow::Result<Vec<NamedSolution>> {
+    let default = solutions
+        .get(day - 1)
+        .context("solution to day should exist")?
+        .with_context(|| format!("day {day} should be solved"))?[puzzle];
+    let variants = variants
+        .iter()
+        .filter(|variant| variant.day == day && variant.puzzle == puzzle)
+        .map(|variant| (variant.name, variant.solution));
+    Ok([(DEFAULT, default)].into_iter().chain(variants).collect())
+}
+
+/// # Errors
+///
+/// Errors if the default solution does not exist or the puzzle has no variant called `name`.
+pub fn solution(
+    solutions: &[Option<[Solution; 2]>],
+    variants: &[Variant],
+    day: Day,
+    puzzle: Puzzle,
+    name: &str,
+) -> anyhow::Result<Solution> {
+    named_solutions(solutions, variants, day, puzzle)?
+        .into_iter()
+        .find_map(|(variant_name, solution)| (variant_name == name).then_some(solution))
+        .with_context(|| format!("variant '{name}' of puzzle should exist"))
+}
+
+/// The answer that a named solution gave, how long it took and, if allocations are counted, how
+/// much heap memory it used.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Timing {
+    pub name: &'static str,
+    pub answer: Answer,
+    pub duration: Duration,
+    pub memory: Option<Usage>,
+}
+
+/// Runs the default solution and all variants of the puzzle on the input that `parameters` are
+/// for, one after the other.
+///
+/// # Errors
+///
+/// Errors if the wanted input cannot be read or the default solution does not exist.
+pub fn compare(
+    manifest_directory: &str,
+    day: Day,
+    puzzle: Puzzle,
+    solutions: &[Option<[Solution; 2]>],
+    variants: &[Variant],
+    parameters: &Parameters,
+) -> anyhow::Result<Vec<Timing>> {
+    let named_solutions = named_solutions(solutions, variants, day, puzzle)?;
+    let input = input(manifest_directory, day, parameters.input())?;
+    Ok(named_solutions
+        .into_iter()
+        .map(|(name, solution)| {
+            let start = Instant::now();
+            let (answer, memory) = memory::measure(|| parameters.scope(|| solution(&input)));
+            Timing {
+                name,
+                answer,
+                duration: start.elapsed(),
+                memory,
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn first(_input: &str) -> Answer {
+        "first".to_owned()
+    }
+
+    fn second(_input: &str) -> Answer {
+        "second".to_owned()
+    }
+
+    fn brute_force(_input: &str) -> Answer {
+        "brute force".to_owned()
+    }
+
+    const SOLUTIONS: &[Option<[Solution; 2]>] = &[Some([first, second]), None];
+    const VARIANTS: &[Variant] = &[Variant::new(1, Puzzle::Second, "brute-force", brute_force)];
+
+    #[test]
+    fn named_solutions() {
+        let names = |puzzle| {
+            super::named_solutions(SOLUTIONS, VARIANTS, 1, puzzle)
+                .expect("solutions should exist")
+                .into_iter()
+                .map(|(name, _)| name)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(Puzzle::First), [DEFAULT]);
+        assert_eq!(names(Puzzle::Second), [DEFAULT, "brute-force"]);
+        assert!(super::named_solutions(SOLUTIONS, VARIANTS, 2, Puzzle::First).is_err());
+        assert!(super::named_solutions(SOLUTIONS, VARIANTS, 3, Puzzle::First).is_err());
+    }
+
+    #[test]
+    fn solution() {
+        let answer = |name| {
+            super::solution(SOLUTIONS, VARIANTS, 1, Puzzle::Second, name)
+                .map(|solution| solution(""))
+                .ok()
+        };
+        assert_eq!(answer(DEFAULT), Some("second".to_owned()));
+        assert_eq!(answer("brute-force"), Some("brute force".to_owned()));
+        assert_eq!(answer("fast"), None);
+    }
+}
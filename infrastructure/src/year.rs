@@ -0,0 +1,124 @@
+use crate::{
+    Answer, Day, Input, Puzzle, Solution, answers,
+    generate::{self, Generators},
+    parameters::{Keys, Parameters},
+    submit::{self, Client, Verdict},
+    variants::{self, NamedSolution, Timing, Variant},
+};
+
+/// The solutions of a year, their variants, the keys of their parameters and the generators of
+/// inputs, along with the directory of the year's crate, where its inputs and answers are kept.
+/// Each year's crate declares one, which the command line and the tests work through. Days that
+/// are not solved yet have `None` in place of their solutions.
+#[derive(Clone, Copy)]
+pub struct Year {
+    pub year: usize,
+    pub manifest_directory: &'static str,
+    pub solutions: &'static [Option<[Solution; 2]>],
+    pub variants: &'static [Variant],
+    pub parameters: &'static Keys,
+    pub generators: &'static Generators,
+}
+
+impl Year {
+    /// # Errors
+    ///
+    /// Errors if the wanted input cannot be read, the wanted solution or variant does not exist
+    /// or a parameter is overridden that the day does not declare.
+    pub fn answer(
+        &self,
+        day: Day,
+        puzzle: Puzzle,
+        variant: &str,
+        parameters: &Parameters,
+    ) -> anyhow::Result<Answer> {
+        parameters.check(self.parameters, day)?;
+        let solution = variants::solution(self.solutions, self.variants, day, puzzle, variant)?;
+        crate::answer(self.manifest_directory, day, solution, parameters)
+    }
+
+    /// Runs the wanted solution and all its variants on the input that `parameters` are for.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the wanted input cannot be read, the wanted solution does not exist or a
+    /// parameter is overridden that the day does not declare.
+    pub fn compare(
+        &self,
+        day: Day,
+        puzzle: Puzzle,
+        parameters: &Parameters,
+    ) -> anyhow::Result<Vec<Timing>> {
+        parameters.check(self.parameters, day)?;
+        variants::compare(
+            self.manifest_directory,
+            day,
+            puzzle,
+            self.solutions,
+            self.variants,
+            parameters,
+        )
+    }
+
+    /// # Errors
+    ///
+    /// Errors if there is no generator for the wanted day.
+    pub fn generate(&self, day: Day, seed: u64, size: usize) -> anyhow::Result<String> {
+        generate::generate(self.generators, day, seed, size)
+    }
+
+    /// Whether the puzzles of the day are solved, as opposed to missing or not solved yet.
+    #[must_use]
+    pub fn solved(&self, day: Day) -> bool {
+        day.checked_sub(1)
+            .and_then(|index| self.solutions.get(index))
+            .is_some_and(Option::is_some)
+    }
+
+    /// The default solution to the puzzle followed by its variants, each with its name.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the default solution does not exist or the day is not solved yet.
+    pub fn solutions(&self, day: Day, puzzle: Puzzle) -> anyhow::Result<Vec<NamedSolution>> {
+        variants::named_solutions(self.solutions, self.variants, day, puzzle)
+    }
+
+    /// Submits the answer to the puzzle and records the verdict in the registry of answers.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the answer is known to be wrong or the submission fails.
+    pub fn submit(
+        &self,
+        day: Day,
+        puzzle: Puzzle,
+        answer: &str,
+        client: &Client,
+    ) -> anyhow::Result<Verdict> {
+        submit::submit(
+            self.manifest_directory,
+            self.year,
+            day,
+            puzzle,
+            answer,
+            client,
+        )
+    }
+
+    /// # Errors
+    ///
+    /// Errors if the wanted input cannot be read.
+    pub fn input(&self, day: Day, input: Input) -> anyhow::Result<String> {
+        crate::input(self.manifest_directory, day, input)
+    }
+
+    /// The answer to the puzzle that Advent of Code has accepted, if any.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the registry of the day cannot be read or is malformed.
+    pub fn registered(&self, day: Day, puzzle: Puzzle) -> anyhow::Result<Option<Answer>> {
+        answers::registered(self.manifest_directory, day, puzzle)
+    }
+}
@@ -0,0 +1,396 @@
+use std::hash::Hash;
+
+use ahash::{AHashMap, AHashSet};
+use itertools::Itertools;
+
+use crate::grid::{Coordinate, Grid, Position};
+
+/// The cells of a cellular automaton and the topology they are arranged in.
+pub trait Space: Clone + PartialEq {
+    type Position: Copy;
+    type State: Clone + PartialEq;
+
+    /// The state of the cell at `position` or `None` if `position` is outside the space.
+    fn state(&self, position: Self::Position) -> Option<&Self::State>;
+
+    /// The state of every cell that is not stored explicitly, if the space is infinite.
+    fn background(&self) -> Option<&Self::State>;
+
+    /// The space after one generation, where `next_state` gives the next state of the cell at
+    /// a position and `next_background` gives the next state of a background cell at a position.
+    #[must_use]
+    fn next<Neighbors: IntoIterator<Item = Self::Position>>(
+        &self,
+        neighbors: impl Fn(Self::Position) -> Neighbors,
+        next_state: impl FnMut(Self::Position) -> Self::State,
+        next_background: impl FnOnce(Self::Position) -> Self::State,
+    ) -> Self;
+}
+
+impl<T: Clone + PartialEq> Space for Grid<T> {
+    type Position = Position;
+    type State = T;
+
+    fn state(&self, position: Self::Position) -> Option<&Self::State> {
+        self.get(position)
+    }
+
+    fn background(&self) -> Option<&Self::State> {
+        None
+    }
+
+    fn next<Neighbors: IntoIterator<Item = Self::Position>>(
+        &self,
+        _: impl Fn(Self::Position) -> Neighbors,
+        mut next_state: impl FnMut(Self::Position) -> Self::State,
+        _: impl FnOnce(Self::Position) -> Self::State,
+    ) -> Self {
+        self.map(|position, _| next_state(position))
+    }
+}
+
+/// An infinite space where every cell that is not stored explicitly is in the background state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Infinite<P: Eq + Hash, S> {
+    cells: AHashMap<P, S>,
+    background: S,
+}
+
+impl<P: Copy + Eq + Hash, S: PartialEq> Infinite<P, S> {
+    pub fn new(background: S, cells: impl IntoIterator<Item = (P, S)>) -> Self {
+        let cells = cells
+            .into_iter()
+            .filter(|(_, state)| *state != background)
+            .collect();
+        Self { cells, background }
+    }
+
+    /// The cells that are not in the background state.
+    pub fn cells(&self) -> impl Iterator<Item = (P, &S)> {
+        self.cells
+            .iter()
+            .map(|(&position, state)| (position, state))
+    }
+
+    #[must_use]
+    pub fn number_of_cells(&self) -> usize {
+        self.cells.len()
+    }
+}
+
+impl<P: Copy + Eq + Hash> Infinite<P, bool> {
+    /// An infinite space of dead cells except for the live cells at `positions`.
+    pub fn from_live(positions: impl IntoIterator<Item = P>) -> Self {
+        Self::new(
+            false,
+            positions.into_iter().map(|position| (position, true)),
+        )
+    }
+}
+
+impl<P, S> Space for Infinite<P, S>
+where
+    P: Copy + Default + Eq + Hash,
+    S: Clone + PartialEq,
+{
+    type Position = P;
+    type State = S;
+
+    fn state(&self, position: Self::Position) -> Option<&Self::State> {
+        Some(self.cells.get(&position).unwrap_or(&self.background))
+    }
+
+    fn background(&self) -> Option<&Self::State> {
+        Some(&self.background)
+    }
+
+    fn next<Neighbors: IntoIterator<Item = Self::Position>>(
+        &self,
+        neighbors: impl Fn(Self::Position) -> Neighbors,
+        mut next_state: impl FnMut(Self::Position) -> Self::State,
+        next_background: impl FnOnce(Self::Position) -> Self::State,
+    ) -> Self {
+        let background = next_background(P::default());
+        let candidates: AHashSet<P> = self
+            .cells
+            .keys()
+            .flat_map(|&position| neighbors(position).into_iter().chain([position]))
+            .collect();
+        let cells = candidates
+            .into_iter()
+            .map(|position| (position, next_state(position)))
+            .filter(|(_, state)| *state != background)
+            .collect();
+        Self { cells, background }
+    }
+}
+
+/// Number of generations before a repeated state and the length of the repeating cycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Repetition {
+    pub start: usize,
+    pub period: usize,
+}
+
+pub struct Automaton<S, Neighbors, Rule> {
+    space: S,
+    neighbors: Neighbors,
+    rule: Rule,
+    generation: usize,
+    history: Option<Vec<S>>,
+}
+
+impl<S, NeighborsFn, Neighbors, Rule> Automaton<S, NeighborsFn, Rule>
+where
+    S: Space,
+    NeighborsFn: Fn(S::Position) -> Neighbors,
+    Neighbors: IntoIterator<Item = S::Position>,
+    Rule: Fn(&S::State, &[S::State]) -> S::State,
+{
+    /// The `rule` receives the state of a cell and the states of its neighbors within the space
+    /// in the order given by `neighbors`.
+    pub fn new(space: S, neighbors: NeighborsFn, rule: Rule) -> Self {
+        Self {
+            space,
+            neighbors,
+            rule,
+            generation: 0,
+            history: None,
+        }
+    }
+
+    /// Keeps every past generation, which enables [`Automaton::run_until_repetition`].
+    #[must_use]
+    pub fn with_history(mut self) -> Self {
+        self.history = Some(vec![self.space.clone()]);
+        self
+    }
+
+    pub fn space(&self) -> &S {
+        &self.space
+    }
+
+    #[must_use]
+    pub fn into_space(self) -> S {
+        self.space
+    }
+
+    #[must_use]
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Every generation from the first up to and including the current one, if history is kept.
+    #[must_use]
+    pub fn history(&self) -> Option<&[S]> {
+        self.history.as_deref()
+    }
+
+    /// Advances one generation and returns whether any cell changed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the space asks for the next state of a position outside of it or for the next
+    /// background state without having a background.
+    pub fn step(&mut self) -> bool {
+        let Self {
+            space,
+            neighbors,
+            rule,
+            ..
+        } = self;
+        let mut neighbor_states = vec![];
+        let mut next_state = |position| {
+            neighbor_states.clear();
+            neighbor_states.extend(
+                neighbors(position)
+                    .into_iter()
+                    .filter_map(|neighbor| space.state(neighbor).cloned()),
+            );
+            let state = space.state(position).expect("cell should be within space");
+            rule(state, &neighbor_states)
+        };
+        let next = space.next(&*neighbors, &mut next_state, |position| {
+            let background = space.background().expect("space should have a background");
+            let number_of_neighbors = neighbors(position).into_iter().count();
+            rule(background, &vec![background.clone(); number_of_neighbors])
+        });
+        let changed = next != self.space;
+        self.space = next;
+        self.generation += 1;
+        if let Some(history) = &mut self.history {
+            history.push(self.space.clone());
+        }
+        changed
+    }
+
+    pub fn run(&mut self, number_of_generations: usize) {
+        for _ in 0..number_of_generations {
+            self.step();
+        }
+    }
+
+    /// Advances until a generation is equal to the one before it and returns the number of that
+    /// generation.
+    pub fn run_until_fixed_point(&mut self) -> usize {
+        while self.step() {}
+        self.generation
+    }
+
+    /// Advances until a generation is equal to an earlier one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if history is not kept.
+    pub fn run_until_repetition(&mut self) -> Repetition {
+        loop {
+            self.step();
+            let history = self
+                .history
+                .as_ref()
+                .expect("history should be kept to detect repetitions");
+            let (current, earlier) = history.split_last().expect("history should not be empty");
+            if let Some(start) = earlier.iter().position(|earlier| earlier == current) {
+                return Repetition {
+                    start,
+                    period: self.generation - start,
+                };
+            }
+        }
+    }
+}
+
+/// The `3^N - 1` positions that differ from `position` by at most one in every coordinate.
+#[expect(
+    clippy::missing_panics_doc,
+    reason = "every neighbor has as many coordinates as position"
+)]
+pub fn moore_neighbors<const N: usize>(
+    position: [Coordinate; N],
+) -> impl Iterator<Item = [Coordinate; N]> {
+    position
+        .into_iter()
+        .map(|coordinate| coordinate - 1..=coordinate + 1)
+        .multi_cartesian_product()
+        .map(|neighbor| {
+            neighbor
+                .try_into()
+                .expect("neighbor should have the same number of coordinates as position")
+        })
+        .filter(move |&neighbor| neighbor != position)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{grid, hex};
+
+    use super::*;
+
+    #[expect(
+        clippy::trivially_copy_pass_by_ref,
+        reason = "rules receive states by reference"
+    )]
+    fn game_of_life(alive: &bool, neighbors: &[bool]) -> bool {
+        let number_of_live_neighbors = neighbors.iter().filter(|&&alive| alive).count();
+        number_of_live_neighbors == 3 || *alive && number_of_live_neighbors == 2
+    }
+
+    #[test]
+    fn blinker_on_bounded_grid() {
+        let grid = Grid::<char>::from(".....\n..#..\n..#..\n..#..\n.....\n").map(|_, &c| c == '#');
+        let mut automaton = Automaton::new(
+            grid.clone(),
+            grid::orthogonal_and_diagonal_neighbors,
+            game_of_life,
+        )
+        .with_history();
+        let actual = automaton.run_until_repetition();
+        assert_eq!(
+            actual,
+            Repetition {
+                start: 0,
+                period: 2
+            }
+        );
+        let horizontal = Grid::<char>::from(".....\n.....\n.###.\n.....\n.....\n");
+        assert_eq!(
+            automaton.history().map(<[_]>::len),
+            Some(3),
+            "history should contain every generation"
+        );
+        assert_eq!(
+            automaton.history().expect("history should be kept")[1],
+            horizontal.map(|_, &c| c == '#')
+        );
+        assert_eq!(automaton.space(), &grid);
+    }
+
+    #[test]
+    fn glider_on_infinite_grid() {
+        let glider = [[0, 1], [1, 2], [2, 0], [2, 1], [2, 2]];
+        let mut automaton = Automaton::new(
+            Infinite::from_live(glider),
+            grid::orthogonal_and_diagonal_neighbors,
+            game_of_life,
+        );
+        automaton.run(8);
+        let actual: AHashSet<Position> = automaton.space().cells().map(|(p, _)| p).collect();
+        let expected = glider.map(|[row, column]| [row + 2, column + 2]);
+        assert_eq!(actual, AHashSet::from(expected));
+    }
+
+    #[test]
+    fn still_life_is_fixed_point() {
+        let block = [[0, 0], [0, 1], [1, 0], [1, 1]];
+        let mut automaton = Automaton::new(
+            Infinite::from_live(block),
+            grid::orthogonal_and_diagonal_neighbors,
+            game_of_life,
+        );
+        assert_eq!(automaton.run_until_fixed_point(), 1);
+    }
+
+    #[test]
+    fn alternating_background() {
+        let flip = |&state: &bool, _: &[bool]| !state;
+        let mut automaton = Automaton::new(
+            Infinite::from_live([[0, 0]]),
+            grid::orthogonal_neighbors,
+            flip,
+        );
+        automaton.step();
+        assert_eq!(automaton.space().background(), Some(&true));
+        assert_eq!(automaton.space().state([0, 0]), Some(&false));
+        assert_eq!(automaton.space().state([5, -3]), Some(&true));
+        automaton.step();
+        assert_eq!(automaton.space(), &Infinite::from_live([[0, 0]]));
+    }
+
+    #[test]
+    fn hexagonal_topology() {
+        let rule = |&alive: &bool, neighbors: &[bool]| {
+            let number_of_live_neighbors = neighbors.iter().filter(|&&alive| alive).count();
+            number_of_live_neighbors == 2 || alive && number_of_live_neighbors == 1
+        };
+        let mut automaton = Automaton::new(
+            Infinite::from_live([[0, 0], hex::EAST]),
+            hex::neighbors,
+            rule,
+        );
+        automaton.step();
+        let actual: AHashSet<hex::Position> = automaton.space().cells().map(|(p, _)| p).collect();
+        let expected = [[0, 0], hex::EAST, hex::NORTH_EAST, hex::SOUTH_EAST];
+        assert_eq!(actual, AHashSet::from(expected));
+    }
+
+    #[test]
+    fn moore_neighbors() {
+        assert_eq!(super::moore_neighbors([0, 0]).count(), 8);
+        assert_eq!(super::moore_neighbors([0, 0, 0]).count(), 26);
+        assert_eq!(super::moore_neighbors([4, -2, 0, 7]).count(), 80);
+        assert!(
+            super::moore_neighbors([1, 2, 3]).all(|neighbor| neighbor != [1, 2, 3]),
+            "position should not be its own neighbor"
+        );
+    }
+}
@@ -0,0 +1,384 @@
+use ahash::AHashMap;
+use easy_cast::Cast;
+use itertools::Itertools;
+
+use crate::{
+    grid::{self, Coordinate, Direction, Grid, Position, Topology},
+    vector::{Negation, Vector},
+};
+
+type Vector3 = [Coordinate; 3];
+
+/// A net of six square faces on a grid folded into a cube. Stepping off the net continues on
+/// the face that is glued to the crossed edge when folded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Net {
+    face_size: Coordinate,
+    faces: AHashMap<Position, Face>,
+}
+
+/// The orientation of a face on the folded cube: its outward normal and the directions that
+/// south and east on the net point in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Face {
+    normal: Vector3,
+    south: Vector3,
+    east: Vector3,
+}
+
+impl Net {
+    /// Folds a net from the positions of its faces, where a face at `[row, column]` covers the
+    /// positions from `[row, column]` times `face_size` and `face_size` positions onwards in
+    /// both directions.
+    ///
+    /// Returns `None` if the faces are not a net of a cube.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `face_size` is zero.
+    #[must_use]
+    pub fn new(faces: impl IntoIterator<Item = Position>, face_size: usize) -> Option<Self> {
+        assert!(face_size > 0, "face size should be positive");
+        let mut unfolded = faces.into_iter().sorted_unstable().dedup().collect_vec();
+        if unfolded.len() != 6 {
+            return None;
+        }
+
+        let first = unfolded.remove(0);
+        let mut folded = AHashMap::from([(
+            first,
+            Face {
+                normal: [0, 0, 1],
+                south: [1, 0, 0],
+                east: [0, 1, 0],
+            },
+        )]);
+        let mut frontier = vec![first];
+        while let Some(plane_face) = frontier.pop() {
+            let face = folded[&plane_face];
+            for direction in grid::ORTHOGONAL_DIRECTIONS {
+                let neighbor = plane_face.add(direction);
+                if let Some(index) = unfolded.iter().position(|&face| face == neighbor) {
+                    unfolded.swap_remove(index);
+                    folded.insert(neighbor, face.folded(direction));
+                    frontier.push(neighbor);
+                }
+            }
+        }
+
+        let are_normals_distinct = folded.values().map(|face| face.normal).all_unique();
+        (unfolded.is_empty() && are_normals_distinct).then(|| Self {
+            face_size: face_size.cast(),
+            faces: folded,
+        })
+    }
+
+    /// Folds the net formed by the elements of `grid` for which `is_on_net` holds. The face
+    /// size is derived from the number of such elements.
+    ///
+    /// Returns `None` if these elements do not form a net of a cube.
+    #[must_use]
+    pub fn from_grid<T>(grid: &Grid<T>, mut is_on_net: impl FnMut(&T) -> bool) -> Option<Self> {
+        let area = grid
+            .iter_row_major()
+            .filter(|(_, element)| is_on_net(element))
+            .count();
+        let face_size = (area / 6).isqrt();
+        if face_size == 0 || 6 * face_size * face_size != area {
+            return None;
+        }
+
+        let face_rows = (0..grid.height()).step_by(face_size);
+        let face_columns = (0..grid.width()).step_by(face_size);
+        let faces = face_rows
+            .cartesian_product(face_columns)
+            .filter(|&(row, column)| is_on_net(&grid[[row, column].cast()]))
+            .map(|(row, column)| [row / face_size, column / face_size].cast());
+        Self::new(faces, face_size)
+    }
+
+    #[must_use]
+    pub fn face_size(&self) -> usize {
+        self.face_size.cast()
+    }
+
+    #[must_use]
+    pub fn contains(&self, position: Position) -> bool {
+        self.faces.contains_key(&self.plane_face(position))
+    }
+
+    fn plane_face(&self, position: Position) -> Position {
+        position.map(|coordinate| coordinate.div_euclid(self.face_size))
+    }
+
+    /// The center of the position on the folded cube in coordinates doubled to stay integral.
+    /// The cube spans from `-face_size` to `face_size` in every dimension.
+    fn cube_position(&self, face: Face, position: Position) -> Vector3 {
+        let [row, column] = position
+            .map(|coordinate| 2 * coordinate.rem_euclid(self.face_size) - self.face_size + 1);
+        face.normal
+            .mul(self.face_size)
+            .add(face.south.mul(row))
+            .add(face.east.mul(column))
+    }
+
+    fn plane_position(&self, plane_face: Position, face: Face, cube_position: Vector3) -> Position {
+        let within_face =
+            [face.south, face.east].map(|axis| (cube_position.dot(axis) + self.face_size - 1) / 2);
+        plane_face.mul(self.face_size).add(within_face)
+    }
+}
+
+impl Face {
+    fn folded(self, direction: Direction) -> Self {
+        let Self {
+            normal,
+            south,
+            east,
+        } = self;
+        match direction {
+            grid::NORTH => Self {
+                normal: south.neg(),
+                south: normal,
+                east,
+            },
+            grid::EAST => Self {
+                normal: east,
+                south,
+                east: normal.neg(),
+            },
+            grid::SOUTH => Self {
+                normal: south,
+                south: normal.neg(),
+                east,
+            },
+            grid::WEST => Self {
+                normal: east.neg(),
+                south,
+                east: normal,
+            },
+            _ => panic!("direction should be one of four unit vectors"),
+        }
+    }
+
+    fn plane_direction(self, cube_direction: Vector3) -> Direction {
+        if cube_direction == self.south {
+            grid::SOUTH
+        } else if cube_direction == self.south.neg() {
+            grid::NORTH
+        } else if cube_direction == self.east {
+            grid::EAST
+        } else if cube_direction == self.east.neg() {
+            grid::WEST
+        } else {
+            panic!("direction should be parallel to the face")
+        }
+    }
+}
+
+impl Topology for Net {
+    fn step(&self, position: Position, direction: Direction) -> Option<(Position, Direction)> {
+        let &face = self.faces.get(&self.plane_face(position))?;
+        let next = position.add(direction);
+        if self.contains(next) {
+            return Some((next, direction));
+        }
+
+        let cube_direction = face
+            .south
+            .mul(direction[0])
+            .add(face.east.mul(direction[1]));
+        let (&next_plane_face, &next_face) = self
+            .faces
+            .iter()
+            .find(|(_, next_face)| next_face.normal == cube_direction)
+            .expect("every direction should have a face on a cube");
+        let next_cube_position = self
+            .cube_position(face, position)
+            .add(cube_direction)
+            .sub(face.normal);
+        let next_position = self.plane_position(next_plane_face, next_face, next_cube_position);
+        let next_direction = next_face.plane_direction(face.normal.neg());
+        Some((next_position, next_direction))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::vector::RotationInTwoDimensions;
+
+    use super::*;
+
+    /// The eleven nets of a cube up to rotation and reflection.
+    const NETS: [&str; 11] = [
+        "#...\n####\n#...\n",
+        "#...\n####\n.#..\n",
+        "#...\n####\n..#.\n",
+        "#...\n####\n...#\n",
+        ".#..\n####\n.#..\n",
+        ".#..\n####\n..#.\n",
+        "##..\n.###\n.#..\n",
+        "##..\n.###\n..#.\n",
+        "##..\n.###\n...#\n",
+        "##..\n.##.\n..##\n",
+        "###..\n..###\n",
+    ];
+
+    #[test]
+    fn folds_all_nets_in_all_orientations() {
+        for net in NETS {
+            for faces in symmetries(&faces(net)) {
+                assert!(
+                    Net::new(faces.clone(), 1).is_some(),
+                    "faces {faces:?} should fold into a cube"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_non_nets() {
+        let non_nets = [
+            "######\n",
+            "###\n###\n",
+            "####\n#..#\n",
+            "#...\n####\n",
+            "#...\n####\n#...\n...#\n",
+            "#.#.\n####\n",
+        ];
+        for non_net in non_nets {
+            assert_eq!(Net::new(faces(non_net), 1), None, "{non_net:?}");
+        }
+    }
+
+    #[test]
+    fn stepping_off_an_edge_and_back_is_identity() {
+        for net in NETS {
+            for faces in symmetries(&faces(net)) {
+                for face_size in 1..=3 {
+                    let net = Net::new(faces.clone(), face_size).expect("net should fold");
+                    assert_stepping_back_is_identity(&net, &faces);
+                }
+            }
+        }
+    }
+
+    fn assert_stepping_back_is_identity(net: &Net, faces: &[Position]) {
+        let face_size: Coordinate = net.face_size().cast();
+        let positions = faces.iter().flat_map(|face| {
+            (0..face_size)
+                .cartesian_product(0..face_size)
+                .map(move |(row, column)| face.mul(face_size).add([row, column]))
+        });
+        for position in positions {
+            for direction in grid::ORTHOGONAL_DIRECTIONS {
+                let (next_position, next_direction) = net
+                    .step(position, direction)
+                    .expect("cube should have no edges");
+                assert!(net.contains(next_position), "step should stay on the net");
+                let back = net.step(next_position, next_direction.neg());
+                assert_eq!(
+                    back,
+                    Some((position, direction.neg())),
+                    "stepping from {position:?} towards {direction:?} and back should be identity"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn walking_around_the_cube_returns_to_start() {
+        for net in NETS {
+            let faces = faces(net);
+            let net = Net::new(faces.iter().copied(), 3).expect("net should fold");
+            let start = faces[0].mul(3).add([1, 1]);
+            for direction in grid::ORTHOGONAL_DIRECTIONS {
+                let [mut position, mut current] = [start, direction];
+                for _ in 0..4 * 3 {
+                    (position, current) = net.step(position, current).expect("cube has no edges");
+                }
+                assert_eq!((position, current), (start, direction));
+            }
+        }
+    }
+
+    #[test]
+    fn advent_of_code_example() {
+        let net = "\
+            ..#.\n\
+            ###.\n\
+            ..##\n\
+        ";
+        let net = Net::new(faces(net), 4).expect("net should fold");
+        let cases = [
+            (([5, 11], grid::EAST), ([8, 14], grid::SOUTH)),
+            (([11, 10], grid::SOUTH), ([7, 1], grid::NORTH)),
+            (([4, 6], grid::NORTH), ([2, 8], grid::EAST)),
+        ];
+        for ((position, direction), expected) in cases {
+            assert_eq!(net.step(position, direction), Some(expected));
+        }
+    }
+
+    #[test]
+    fn from_grid() {
+        let grid = Grid::<char>::from(
+            "\
+            ..##..\n\
+            ..##..\n\
+            ######\n\
+            ######\n\
+            ..##..\n\
+            ..##..\n\
+            ..##..\n\
+            ..##..\n\
+            ",
+        );
+        let net = Net::from_grid(&grid, |&tile| tile == '#').expect("grid should contain a net");
+        assert_eq!(net.face_size(), 2);
+        assert!(net.contains([7, 2]), "bottom face should be on the net");
+        assert!(
+            !net.contains([0, 0]),
+            "top left corner should not be on the net"
+        );
+        assert_eq!(Net::from_grid(&grid, |&tile| tile == '.'), None);
+    }
+
+    fn faces(net: &str) -> Vec<Position> {
+        Grid::<char>::from(net)
+            .iter_row_major()
+            .filter(|&(_, &tile)| tile == '#')
+            .map(|(position, _)| position)
+            .collect_vec()
+    }
+
+    fn symmetries(faces: &[Position]) -> Vec<Vec<Position>> {
+        let mut faces = faces.to_vec();
+        let mut symmetries = vec![];
+        for _ in 0..2 {
+            for _ in 0..4 {
+                faces = faces
+                    .into_iter()
+                    .map(RotationInTwoDimensions::left)
+                    .collect();
+                symmetries.push(normalized(&faces));
+            }
+            faces = faces
+                .into_iter()
+                .map(|[row, column]| [row, -column])
+                .collect();
+        }
+        symmetries
+    }
+
+    fn normalized(faces: &[Position]) -> Vec<Position> {
+        let top_left = [0, 1].map(|axis| {
+            faces
+                .iter()
+                .map(|face| face[axis])
+                .min()
+                .expect("faces should not be empty")
+        });
+        faces.iter().map(|face| face.sub(top_left)).collect()
+    }
+}
@@ -0,0 +1,118 @@
+use easy_cast::Conv;
+use itertools::Itertools;
+use rand::{Rng, RngExt, seq::IndexedRandom};
+
+use crate::{
+    grid::{Coordinate, Grid, ORTHOGONAL_DIRECTIONS, Position},
+    vector::Vector,
+};
+
+/// A height and a width between 1 and `size`.
+pub fn dimensions(rng: &mut impl Rng, size: usize) -> [usize; 2] {
+    [(); 2].map(|()| rng.random_range(1..=size.max(1)))
+}
+
+/// A grid whose elements are drawn independently from `elements`, with probabilities proportional
+/// to their weights.
+///
+/// # Panics
+///
+/// Panics if `height` or `width` is zero or if the weights are all zero.
+pub fn weighted_grid<T: Clone>(
+    rng: &mut impl Rng,
+    [height, width]: [usize; 2],
+    elements: &[(T, u32)],
+) -> Grid<T> {
+    Grid::new(height, width, |_| {
+        elements
+            .choose_weighted(rng, |(_, weight)| *weight)
+            .expect("some element should have a positive weight")
+            .0
+            .clone()
+    })
+}
+
+/// A square maze of walls `#` and open tiles `.` with sides `2 * size + 1` and walls all around.
+/// The open tiles at odd rows and columns are cells, which a random spanning tree connects, so
+/// that every open tile can be reached from every other one. Walls between neighboring cells are
+/// then opened with `loop_probability`, to allow for more than one path.
+///
+/// # Panics
+///
+/// Panics if `size` is zero.
+pub fn maze(rng: &mut impl Rng, size: usize, loop_probability: f64) -> Grid<u8> {
+    assert!(size > 0, "maze should have at least one cell");
+    let length = 2 * size + 1;
+    let mut maze = Grid::new(length, length, |_| b'#');
+    let tile = |cell: Position| cell.mul(2).add([1, 1]);
+
+    let mut visited = Grid::new(size, size, |_| false);
+    let mut path = vec![[0, 0]];
+    visited[[0, 0]] = true;
+    maze[tile([0, 0])] = b'.';
+    while let Some(&cell) = path.last() {
+        let unvisited = ORTHOGONAL_DIRECTIONS
+            .iter()
+            .filter(|&&direction| visited.get(cell.add(direction)) == Some(&false))
+            .collect_vec();
+        if let Some(&&direction) = unvisited.choose(rng) {
+            let next_cell = cell.add(direction);
+            visited[next_cell] = true;
+            maze[tile(cell).add(direction)] = b'.';
+            maze[tile(next_cell)] = b'.';
+            path.push(next_cell);
+        } else {
+            path.pop();
+        }
+    }
+
+    let interior = 1..Coordinate::conv(length) - 1;
+    for (row, column) in interior.clone().cartesian_product(interior) {
+        if (row + column) % 2 == 1 && rng.random_bool(loop_probability) {
+            maze[[row, column]] = b'.';
+        }
+    }
+    maze
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{SeedableRng, rngs::SmallRng};
+
+    use super::*;
+    use crate::{grid::orthogonal_neighbors, search};
+
+    #[test]
+    fn weighted_grid() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let grid = super::weighted_grid(&mut rng, [3, 5], &[('O', 1), ('#', 0), ('.', 2)]);
+        assert_eq!([grid.height(), grid.width()], [3, 5]);
+        assert!(
+            grid.iter_row_major()
+                .all(|(_, &element)| ['O', '.'].contains(&element)),
+            "elements should have positive weights"
+        );
+    }
+
+    #[test]
+    fn maze() {
+        for (seed, loop_probability) in (0..20).cartesian_product([0.0, 0.3]) {
+            let maze = super::maze(&mut SmallRng::seed_from_u64(seed), 6, loop_probability);
+            assert_eq!([maze.height(), maze.width()], [13, 13]);
+            let open_tiles = maze
+                .iter_row_major()
+                .filter(|&(_, &element)| element == b'.')
+                .map(|(position, _)| position)
+                .collect_vec();
+            let reachable = search::distances([1, 1], |position| {
+                orthogonal_neighbors(position)
+                    .into_iter()
+                    .filter(|&neighbor| maze.get(neighbor) == Some(&b'.'))
+            });
+            assert_eq!(reachable.len(), open_tiles.len());
+            if loop_probability == 0.0 {
+                assert_eq!(open_tiles.len(), 2 * 6 * 6 - 1, "cells should form a tree");
+            }
+        }
+    }
+}
@@ -0,0 +1,261 @@
+use std::{array, hash::Hash, ops::Neg};
+
+use ahash::{AHashMap, AHashSet};
+use itertools::Itertools;
+use num_traits::{NumCast, One, Signed, Zero};
+
+use crate::{matrix::Matrix, vector::Vector};
+
+/// One of the 24 rotations that map an axis-aligned cube onto itself, stored as a signed
+/// permutation of the coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CubeRotation {
+    axes: [usize; 3],
+    negated: [bool; 3],
+}
+
+impl CubeRotation {
+    pub const IDENTITY: Self = Self {
+        axes: [0, 1, 2],
+        negated: [false; 3],
+    };
+
+    /// All rotations in a fixed order that starts with the identity.
+    #[must_use]
+    #[expect(
+        clippy::missing_panics_doc,
+        reason = "exactly half of the signed permutations are rotations"
+    )]
+    pub fn all() -> [Self; 24] {
+        (0..3)
+            .permutations(3)
+            .cartesian_product(0..8_u8)
+            .map(|(axes, negations)| Self {
+                axes: [axes[0], axes[1], axes[2]],
+                negated: array::from_fn(|axis| negations >> axis & 1 == 1),
+            })
+            .filter(|rotation| rotation.is_proper())
+            .collect_vec()
+            .try_into()
+            .expect("exactly 24 signed permutations should preserve orientation")
+    }
+
+    /// The rotation by a quarter turn around the x, y or z axis, counterclockwise when looking
+    /// down the axis towards the origin.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `axis` is not 0, 1 or 2.
+    #[must_use]
+    pub fn quarter_turn(axis: usize) -> Self {
+        assert!(axis < 3, "axis should be 0, 1 or 2");
+        let [next, after_next] = [(axis + 1) % 3, (axis + 2) % 3];
+        let mut rotation = Self::IDENTITY;
+        rotation.axes[next] = after_next;
+        rotation.axes[after_next] = next;
+        rotation.negated[next] = true;
+        rotation
+    }
+
+    /// The rotation that applies `self` and then `then`.
+    #[must_use]
+    pub fn compose(self, then: Self) -> Self {
+        Self {
+            axes: then.axes.map(|axis| self.axes[axis]),
+            negated: array::from_fn(|axis| then.negated[axis] != self.negated[then.axes[axis]]),
+        }
+    }
+
+    #[must_use]
+    pub fn inverse(self) -> Self {
+        let mut inverse = Self::IDENTITY;
+        for (axis, (&source, &negated)) in self.axes.iter().zip(&self.negated).enumerate() {
+            inverse.axes[source] = axis;
+            inverse.negated[source] = negated;
+        }
+        inverse
+    }
+
+    #[must_use]
+    pub fn apply<T: Copy + Neg<Output = T>>(self, vector: [T; 3]) -> [T; 3] {
+        array::from_fn(|axis| {
+            let coordinate = vector[self.axes[axis]];
+            if self.negated[axis] {
+                -coordinate
+            } else {
+                coordinate
+            }
+        })
+    }
+
+    #[must_use]
+    pub fn matrix<T: Zero + One + Neg<Output = T>>(self) -> Matrix<T, 3, 3> {
+        array::from_fn(|row| {
+            array::from_fn(
+                |column| match (column == self.axes[row], self.negated[row]) {
+                    (false, _) => T::zero(),
+                    (true, false) => T::one(),
+                    (true, true) => -T::one(),
+                },
+            )
+        })
+    }
+
+    /// Whether the signed permutation preserves orientation, that is, whether the parity of the
+    /// permutation matches the parity of the number of negated coordinates.
+    fn is_proper(self) -> bool {
+        let inversions = self
+            .axes
+            .iter()
+            .tuple_combinations()
+            .filter(|(lhs, rhs)| lhs > rhs)
+            .count();
+        let negations = self.negated.iter().filter(|&&negated| negated).count();
+        (inversions + negations) % 2 == 0
+    }
+}
+
+/// The rotation and translation that move at least `min_matches` points of `moving` onto points
+/// of `fixed`, such that `rotation.apply(point).add(translation)` is in `fixed`. Pairs of points
+/// are only tried if they have enough squared distances to the other points of their clouds in
+/// common, which is necessary for them to correspond.
+///
+/// Returns `None` if there is no such alignment.
+#[must_use]
+pub fn register<T>(
+    fixed: &[[T; 3]],
+    moving: &[[T; 3]],
+    min_matches: usize,
+) -> Option<(CubeRotation, [T; 3])>
+where
+    T: Copy + Eq + Hash + Signed + NumCast,
+{
+    if min_matches == 0 {
+        return Some((CubeRotation::IDENTITY, [T::zero(); 3]));
+    }
+    let fixed_fingerprints = fingerprints(fixed);
+    let moving_fingerprints = fingerprints(moving);
+    let fixed_points: AHashSet<_> = fixed.iter().copied().collect();
+
+    let candidates = fixed
+        .iter()
+        .zip(&fixed_fingerprints)
+        .cartesian_product(moving.iter().zip(&moving_fingerprints))
+        .filter(|((_, fixed_fingerprint), (_, moving_fingerprint))| {
+            number_in_common(fixed_fingerprint, moving_fingerprint) + 1 >= min_matches
+        });
+    for ((&fixed_point, _), (&moving_point, _)) in candidates {
+        for rotation in CubeRotation::all() {
+            let translation = fixed_point.sub(rotation.apply(moving_point));
+            let number_of_matches = moving
+                .iter()
+                .filter(|&&point| fixed_points.contains(&rotation.apply(point).add(translation)))
+                .count();
+            if number_of_matches >= min_matches {
+                return Some((rotation, translation));
+            }
+        }
+    }
+    None
+}
+
+/// The squared distances from every point to the other points, with their multiplicities, which
+/// are unchanged by rotations and translations.
+fn fingerprints<T>(points: &[[T; 3]]) -> Vec<AHashMap<T, usize>>
+where
+    T: Copy + Eq + Hash + Signed + NumCast,
+{
+    points
+        .iter()
+        .map(|&point| {
+            points
+                .iter()
+                .filter(|&&other| other != point)
+                .map(|&other| {
+                    let difference = other.sub(point);
+                    difference.dot(difference)
+                })
+                .counts()
+                .into_iter()
+                .collect()
+        })
+        .collect_vec()
+}
+
+fn number_in_common<T: Eq + Hash>(lhs: &AHashMap<T, usize>, rhs: &AHashMap<T, usize>) -> usize {
+    lhs.iter()
+        .map(|(distance, &count)| count.min(rhs.get(distance).copied().unwrap_or(0)))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use infrastructure::test;
+
+    use super::*;
+    use crate::matrix::{
+        self, quarter_rotation_around_x_axis, quarter_rotation_around_y_axis,
+        quarter_rotation_around_z_axis,
+    };
+
+    #[test]
+    fn group() {
+        let all = CubeRotation::all();
+        assert_eq!(all[0], CubeRotation::IDENTITY);
+        assert_eq!(all.iter().unique().count(), 24);
+        for (&lhs, &rhs) in all.iter().cartesian_product(&all) {
+            let composition = lhs.compose(rhs);
+            assert!(all.contains(&composition), "group should be closed");
+            assert_eq!(
+                composition.apply([1, 2, 3]),
+                rhs.apply(lhs.apply([1, 2, 3])),
+            );
+        }
+        for rotation in all {
+            assert_eq!(rotation.compose(rotation.inverse()), CubeRotation::IDENTITY);
+            assert_eq!(rotation.inverse().compose(rotation), CubeRotation::IDENTITY);
+        }
+    }
+
+    #[test]
+    fn quarter_turns() {
+        let function = |axis| CubeRotation::quarter_turn(axis).matrix::<i32>();
+        let cases = [
+            (0, quarter_rotation_around_x_axis()),
+            (1, quarter_rotation_around_y_axis()),
+            (2, quarter_rotation_around_z_axis()),
+        ];
+        test::cases(function, cases);
+        let turn = CubeRotation::quarter_turn(2);
+        let half_turn = turn.compose(turn);
+        assert_eq!(half_turn.compose(half_turn), CubeRotation::IDENTITY);
+    }
+
+    #[test]
+    fn matrices() {
+        for rotation in CubeRotation::all() {
+            let vector = [5, -7, 11];
+            let expected = matrix::vector_mul(rotation.matrix(), vector);
+            assert_eq!(rotation.apply(vector), expected);
+        }
+    }
+
+    #[test]
+    fn register() {
+        let fixed = [[0, 0, 0], [1, 2, 3], [7, -4, 2], [-3, 5, 9], [10, 10, -10]];
+        let rotation = CubeRotation::all()[17];
+        let translation = [100, -50, 3];
+        let inverse = rotation.inverse();
+        let moving = fixed
+            .iter()
+            .skip(1)
+            .map(|&point| inverse.apply(point.sub(translation)))
+            .chain([[1000, 1000, 1000]])
+            .collect_vec();
+        assert_eq!(
+            super::register(&fixed, &moving, 4),
+            Some((rotation, translation))
+        );
+        assert_eq!(super::register(&fixed, &moving, 5), None);
+    }
+}
@@ -42,9 +42,95 @@ where
     (topological_sort.len() == indegrees.len()).then_some(topological_sort)
 }
 
+/// A partition of `0..len` into sets that can only be merged, as used by Kruskal's algorithm.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DisjointSets {
+    parents: Vec<usize>,
+    sizes: Vec<usize>,
+    number_of_sets: usize,
+}
+
+impl DisjointSets {
+    /// The partition of `0..len` into singletons.
+    #[must_use]
+    pub fn new(len: usize) -> Self {
+        Self {
+            parents: (0..len).collect(),
+            sizes: vec![1; len],
+            number_of_sets: len,
+        }
+    }
+
+    /// The representative of the set that contains `element`, which is the same for all its
+    /// elements until the set is merged with another one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `element` is not in `0..len`.
+    pub fn find(&mut self, mut element: usize) -> usize {
+        while self.parents[element] != element {
+            let grandparent = self.parents[self.parents[element]];
+            self.parents[element] = grandparent;
+            element = grandparent;
+        }
+        element
+    }
+
+    /// Merges the sets that contain `lhs` and `rhs`, and returns whether they were different.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lhs` or `rhs` is not in `0..len`.
+    pub fn union(&mut self, lhs: usize, rhs: usize) -> bool {
+        let [lhs, rhs] = [self.find(lhs), self.find(rhs)];
+        if lhs == rhs {
+            return false;
+        }
+        let [smaller, larger] = if self.sizes[lhs] < self.sizes[rhs] {
+            [lhs, rhs]
+        } else {
+            [rhs, lhs]
+        };
+        self.parents[smaller] = larger;
+        self.sizes[larger] += self.sizes[smaller];
+        self.number_of_sets -= 1;
+        true
+    }
+
+    /// The size of the set that contains `element`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `element` is not in `0..len`.
+    pub fn size(&mut self, element: usize) -> usize {
+        let representative = self.find(element);
+        self.sizes[representative]
+    }
+
+    #[must_use]
+    pub fn number_of_sets(&self) -> usize {
+        self.number_of_sets
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
+    use crate::strategies::{self, Graph};
+
+    fn respects_every_edge(graph: &Graph, order: &[u8]) -> bool {
+        let indices: AHashMap<_, _> = order
+            .iter()
+            .enumerate()
+            .map(|(index, &vertex)| (vertex, index))
+            .collect();
+        indices.len() == graph.len()
+            && graph
+                .iter()
+                .all(|(from, tos)| tos.iter().all(|to| indices[from] < indices[to]))
+    }
 
     #[test]
     fn topological_sort_exists() {
@@ -84,4 +170,35 @@ mod tests {
         let expected = None;
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn disjoint_sets() {
+        let mut sets = DisjointSets::new(6);
+        assert!(sets.union(0, 1));
+        assert!(sets.union(2, 3));
+        assert!(sets.union(1, 3));
+        assert!(!sets.union(0, 2), "sets should already be merged");
+        assert_eq!(sets.find(0), sets.find(3));
+        assert_ne!(sets.find(0), sets.find(4));
+        assert_eq!([sets.size(2), sets.size(5)], [4, 1]);
+        assert_eq!(sets.number_of_sets(), 3);
+    }
+
+    proptest! {
+        #[test]
+        fn topological_sort_respects_every_edge(graph in strategies::graph()) {
+            if let Some(order) = topological_sort(&graph) {
+                prop_assert!(respects_every_edge(&graph, &order), "{order:?} should respect edges");
+            }
+        }
+
+        #[test]
+        fn acyclic_graph_has_topological_sort(graph in strategies::acyclic_graph()) {
+            let order = topological_sort(&graph);
+            prop_assert!(
+                order.is_some_and(|order| respects_every_edge(&graph, &order)),
+                "acyclic graph should be sorted",
+            );
+        }
+    }
 }
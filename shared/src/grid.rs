@@ -162,6 +162,14 @@ impl<T> Grid<T> {
         self.width
     }
 
+    #[must_use]
+    pub fn bounds(&self) -> Rectangle {
+        Rectangle {
+            top_left: [0, 0],
+            dimensions: [self.height(), self.width()].cast(),
+        }
+    }
+
     fn is_within_grid(&self, [row, column]: [usize; 2]) -> bool {
         row < self.height() && column < self.width()
     }
@@ -292,3 +300,261 @@ pub fn diagonal_neighbors(position: Position) -> [Position; 4] {
 pub fn orthogonal_and_diagonal_neighbors(position: Position) -> [Position; 8] {
     ORTHOGONAL_AND_DIAGONAL_DIRECTIONS.map(|direction| position.add(direction))
 }
+
+/// An axis-aligned rectangle of positions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(test, derive(proptest_derive::Arbitrary))]
+pub struct Rectangle {
+    #[cfg_attr(test, proptest(strategy = "crate::strategies::position()"))]
+    pub top_left: Position,
+    #[cfg_attr(test, proptest(strategy = "crate::strategies::dimensions()"))]
+    pub dimensions: [Coordinate; 2],
+}
+
+impl Rectangle {
+    /// The smallest rectangle containing all `positions`, or `None` if there are none.
+    pub fn bounding(positions: impl IntoIterator<Item = Position>) -> Option<Self> {
+        let [min, max] = positions.into_iter().fold(None, |bounds, position| {
+            let [min, max]: [Position; 2] = bounds.unwrap_or([position; 2]);
+            Some([
+                [0, 1].map(|axis| min[axis].min(position[axis])),
+                [0, 1].map(|axis| max[axis].max(position[axis])),
+            ])
+        })?;
+        Some(Self {
+            top_left: min,
+            dimensions: max.sub(min).add([1, 1]),
+        })
+    }
+
+    #[must_use]
+    pub fn contains(&self, position: Position) -> bool {
+        (0..2).all(|axis| {
+            let offset = position[axis] - self.top_left[axis];
+            (0..self.dimensions[axis]).contains(&offset)
+        })
+    }
+
+    /// The position within the rectangle reached by wrapping `position` around along `axis`.
+    #[must_use]
+    pub fn wrap_along(&self, mut position: Position, axis: usize) -> Position {
+        let offset = position[axis] - self.top_left[axis];
+        position[axis] = self.top_left[axis] + offset.rem_euclid(self.dimensions[axis]);
+        position
+    }
+}
+
+/// The way positions are connected, such that a step in a direction might cross an edge and
+/// arrive somewhere else, possibly facing another direction.
+pub trait Topology {
+    /// The position and direction after stepping from `position` in `direction`, or `None` if the
+    /// step leaves the space.
+    fn step(&self, position: Position, direction: Direction) -> Option<(Position, Direction)>;
+}
+
+/// A rectangle with edges that cannot be crossed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Bounded(pub Rectangle);
+
+impl Topology for Bounded {
+    fn step(&self, position: Position, direction: Direction) -> Option<(Position, Direction)> {
+        let next = position.add(direction);
+        self.0.contains(next).then_some((next, direction))
+    }
+}
+
+/// A rectangle where crossing any edge continues from the opposite edge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Torus(pub Rectangle);
+
+impl Topology for Torus {
+    fn step(&self, position: Position, direction: Direction) -> Option<(Position, Direction)> {
+        let next = position.add(direction);
+        let next = self.0.wrap_along(self.0.wrap_along(next, 0), 1);
+        Some((next, direction))
+    }
+}
+
+/// A rectangle where crossing the western or eastern edge continues from the opposite edge, while
+/// the northern and southern edges cannot be crossed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cylinder(pub Rectangle);
+
+impl Topology for Cylinder {
+    fn step(&self, position: Position, direction: Direction) -> Option<(Position, Direction)> {
+        let next = self.0.wrap_along(position.add(direction), 1);
+        self.0.contains(next).then_some((next, direction))
+    }
+}
+
+/// A space of positions for which `inside` holds, where `glue` decides where a step that leaves
+/// the space arrives instead. `glue` receives the position before the step.
+#[derive(Debug, Clone, Copy)]
+pub struct Glued<Inside, Glue> {
+    pub inside: Inside,
+    pub glue: Glue,
+}
+
+impl<Inside, Glue> Topology for Glued<Inside, Glue>
+where
+    Inside: Fn(Position) -> bool,
+    Glue: Fn(Position, Direction) -> Option<(Position, Direction)>,
+{
+    fn step(&self, position: Position, direction: Direction) -> Option<(Position, Direction)> {
+        let next = position.add(direction);
+        if (self.inside)(next) {
+            Some((next, direction))
+        } else {
+            (self.glue)(position, direction)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use infrastructure::test;
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::strategies;
+
+    const RECTANGLE: Rectangle = Rectangle {
+        top_left: [1, 1],
+        dimensions: [4, 5],
+    };
+
+    #[test]
+    fn bounded() {
+        let function = |(position, direction)| Bounded(RECTANGLE).step(position, direction);
+        let cases = [
+            (([1, 3], SOUTH), Some(([2, 3], SOUTH))),
+            (([1, 3], NORTH), None),
+            (([4, 5], EAST), None),
+            (([4, 5], WEST), Some(([4, 4], WEST))),
+        ];
+        test::cases(function, cases);
+    }
+
+    #[test]
+    fn torus() {
+        let function = |(position, direction)| Torus(RECTANGLE).step(position, direction);
+        let cases = [
+            (([1, 3], SOUTH), Some(([2, 3], SOUTH))),
+            (([1, 5], NORTH), Some(([4, 5], NORTH))),
+            (([3, 5], EAST), Some(([3, 1], EAST))),
+            (([4, 3], SOUTH), Some(([1, 3], SOUTH))),
+            (([4, 1], WEST), Some(([4, 5], WEST))),
+            (([4, 5], SOUTH_EAST), Some(([1, 1], SOUTH_EAST))),
+            (([2, 2], [-9, 13]), Some(([1, 5], [-9, 13]))),
+        ];
+        test::cases(function, cases);
+    }
+
+    #[test]
+    fn cylinder() {
+        let function = |(position, direction)| Cylinder(RECTANGLE).step(position, direction);
+        let cases = [
+            (([3, 5], EAST), Some(([3, 1], EAST))),
+            (([4, 1], WEST), Some(([4, 5], WEST))),
+            (([1, 5], NORTH), None),
+            (([4, 3], SOUTH_WEST), None),
+            (([2, 1], SOUTH_WEST), Some(([3, 5], SOUTH_WEST))),
+        ];
+        test::cases(function, cases);
+    }
+
+    #[test]
+    fn glued() {
+        let mobius_strip = Glued {
+            inside: |position| RECTANGLE.contains(position),
+            glue: |[row, column]: Position, direction: Direction| match direction {
+                EAST if column == 5 => Some(([5 - row, 1], EAST)),
+                WEST if column == 1 => Some(([5 - row, 5], WEST)),
+                _ => None,
+            },
+        };
+        let function = |(position, direction)| mobius_strip.step(position, direction);
+        let cases = [
+            (([1, 3], SOUTH), Some(([2, 3], SOUTH))),
+            (([1, 5], EAST), Some(([4, 1], EAST))),
+            (([3, 1], WEST), Some(([2, 5], WEST))),
+            (([4, 2], SOUTH), None),
+        ];
+        test::cases(function, cases);
+    }
+
+    #[test]
+    fn grid_bounds() {
+        let grid = Grid::<char>::from("abc\ndef\n");
+        let bounds = grid.bounds();
+        assert_eq!(bounds.dimensions, [2, 3]);
+        assert!(
+            bounds.contains([1, 2]),
+            "bottom right should be within bounds"
+        );
+        assert!(
+            !bounds.contains([2, 0]),
+            "row below grid should not be within bounds"
+        );
+    }
+
+    #[test]
+    fn bounding_rectangle() {
+        let function = |positions: Vec<Position>| Rectangle::bounding(positions);
+        let cases = [
+            (vec![], None),
+            (
+                vec![[2, -1]],
+                Some(Rectangle {
+                    top_left: [2, -1],
+                    dimensions: [1, 1],
+                }),
+            ),
+            (
+                vec![[2, -1], [-3, 4], [0, 0]],
+                Some(Rectangle {
+                    top_left: [-3, -1],
+                    dimensions: [6, 6],
+                }),
+            ),
+        ];
+        test::cases(function, cases);
+    }
+
+    proptest! {
+        #[test]
+        fn grid_positions_are_within_bounds(grid in any::<Grid<u8>>()) {
+            let bounds = grid.bounds();
+            for (position, element) in grid.iter_row_major() {
+                prop_assert!(bounds.contains(position), "{position:?} should be within bounds");
+                prop_assert_eq!(grid.get(position), Some(element));
+            }
+            prop_assert_eq!(grid.iter_column_major().count(), grid.height() * grid.width());
+        }
+
+        #[test]
+        fn positions_within_rectangle_are_contained(
+            (rectangle, position) in any::<Rectangle>()
+                .prop_flat_map(|rectangle| (Just(rectangle), strategies::position_within(rectangle)))
+        ) {
+            prop_assert!(rectangle.contains(position), "{position:?} should be contained");
+            prop_assert_eq!(rectangle.wrap_along(position, 0), position);
+            prop_assert_eq!(rectangle.wrap_along(position, 1), position);
+        }
+
+        #[test]
+        fn torus_steps_stay_within_rectangle(
+            rectangle in any::<Rectangle>(),
+            position in strategies::position(),
+        ) {
+            let start = rectangle.wrap_along(rectangle.wrap_along(position, 0), 1);
+            prop_assert!(rectangle.contains(start), "wrapped {position:?} should be contained");
+            for direction in ORTHOGONAL_DIRECTIONS {
+                let (next, _) = Torus(rectangle)
+                    .step(start, direction)
+                    .expect("torus should have no edges");
+                prop_assert!(rectangle.contains(next), "{next:?} should be contained");
+            }
+        }
+    }
+}
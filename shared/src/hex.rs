@@ -0,0 +1,264 @@
+use std::iter;
+
+use itertools::Either;
+
+use crate::vector::Vector;
+
+/// Axial coordinates `[q, r]` of a pointy-top hexagon, where `q` increases towards the east and
+/// `r` increases towards the south-east. The implicit third cube coordinate is `s = -q - r`.
+pub type Position = [Coordinate; 2];
+pub type Cube = [Coordinate; 3];
+pub type Coordinate = isize;
+pub type Direction = [Coordinate; 2];
+
+pub const EAST: Direction = [1, 0];
+pub const SOUTH_EAST: Direction = [0, 1];
+pub const SOUTH_WEST: Direction = [-1, 1];
+pub const WEST: Direction = [-1, 0];
+pub const NORTH_WEST: Direction = [0, -1];
+pub const NORTH_EAST: Direction = [1, -1];
+
+/// All six directions in clockwise order starting with east.
+pub const DIRECTIONS: [Direction; 6] = [EAST, SOUTH_EAST, SOUTH_WEST, WEST, NORTH_WEST, NORTH_EAST];
+
+#[must_use]
+pub fn direction(str: &str) -> Option<Direction> {
+    match str {
+        "e" => Some(EAST),
+        "se" => Some(SOUTH_EAST),
+        "sw" => Some(SOUTH_WEST),
+        "w" => Some(WEST),
+        "nw" => Some(NORTH_WEST),
+        "ne" => Some(NORTH_EAST),
+        _ => None,
+    }
+}
+
+/// Parses a walk of concatenated directions without delimiters, such as `"esenee"`.
+///
+/// Returns `None` if `str` contains anything but `e`, `se`, `sw`, `w`, `nw`, and `ne`.
+#[must_use]
+pub fn walk(mut str: &str) -> Option<Vec<Direction>> {
+    let mut walk = vec![];
+    while !str.is_empty() {
+        let length = if str.starts_with(['e', 'w']) { 1 } else { 2 };
+        walk.push(direction(str.get(..length)?)?);
+        str = &str[length..];
+    }
+    Some(walk)
+}
+
+#[must_use]
+pub fn cube([q, r]: Position) -> Cube {
+    [q, r, -q - r]
+}
+
+/// # Panics
+///
+/// Panics in debug builds if the cube coordinates do not sum to zero.
+#[must_use]
+pub fn axial([q, r, s]: Cube) -> Position {
+    debug_assert_eq!(q + r + s, 0, "cube coordinates should sum to zero");
+    [q, r]
+}
+
+#[must_use]
+pub fn distance(from: Position, to: Position) -> Coordinate {
+    let [q, r, s] = cube(to.sub(from));
+    (q.abs() + r.abs() + s.abs()) / 2
+}
+
+#[must_use]
+pub fn neighbors(position: Position) -> [Position; 6] {
+    DIRECTIONS.map(|direction| position.add(direction))
+}
+
+/// Rotates a direction or a position around the origin by 60 degrees clockwise.
+#[must_use]
+pub fn rotate_clockwise(vector: Position) -> Position {
+    let [q, r, s] = cube(vector);
+    axial([-r, -s, -q])
+}
+
+/// Rotates a direction or a position around the origin by 60 degrees counterclockwise.
+#[must_use]
+pub fn rotate_counterclockwise(vector: Position) -> Position {
+    let [q, r, s] = cube(vector);
+    axial([-s, -q, -r])
+}
+
+/// Rotates `position` around `center` by `sixths` sixths of a full turn clockwise. A negative
+/// number of sixths rotates counterclockwise.
+#[must_use]
+pub fn rotate_around(position: Position, center: Position, sixths: isize) -> Position {
+    let mut vector = position.sub(center);
+    for _ in 0..sixths.rem_euclid(6) {
+        vector = rotate_clockwise(vector);
+    }
+    center.add(vector)
+}
+
+/// The positions at exactly distance `radius` from `center` in clockwise order.
+pub fn ring(center: Position, radius: Coordinate) -> impl Iterator<Item = Position> {
+    if radius == 0 {
+        return Either::Left(iter::once(center));
+    }
+    let start = center.add(NORTH_WEST.mul(radius));
+    let ring = DIRECTIONS
+        .into_iter()
+        .flat_map(move |direction| iter::repeat_n(direction, radius.unsigned_abs()))
+        .scan(start, |position, direction| {
+            let current = *position;
+            *position = position.add(direction);
+            Some(current)
+        });
+    Either::Right(ring)
+}
+
+/// The positions at distance at most `radius` from `center`, ring by ring from the center and
+/// outwards.
+pub fn spiral(center: Position, radius: Coordinate) -> impl Iterator<Item = Position> {
+    (0..=radius).flat_map(move |radius| ring(center, radius))
+}
+
+#[cfg(test)]
+mod tests {
+    use ahash::AHashSet;
+    use infrastructure::test;
+    use itertools::Itertools;
+
+    use super::*;
+    use crate::search;
+
+    #[test]
+    fn walk() {
+        let function = |str| super::walk(str);
+        let cases = [
+            ("", Some(vec![])),
+            ("esew", Some(vec![EAST, SOUTH_EAST, WEST])),
+            (
+                "nwwswee",
+                Some(vec![NORTH_WEST, WEST, SOUTH_WEST, EAST, EAST]),
+            ),
+            ("nesw", Some(vec![NORTH_EAST, SOUTH_WEST])),
+            ("en", None),
+            ("s", None),
+            ("ex", None),
+        ];
+        test::cases(function, cases);
+    }
+
+    #[test]
+    fn walk_around_hexagon_returns_to_start() {
+        let walk = super::walk("nwwswee").expect("walk should be valid");
+        let destination = walk.into_iter().fold([0, 0], Vector::add);
+        assert_eq!(destination, [0, 0]);
+    }
+
+    #[test]
+    fn cube_coordinates() {
+        for position in super::spiral([3, -7], 3) {
+            let [q, r, s] = cube(position);
+            assert_eq!(q + r + s, 0, "cube coordinates should sum to zero");
+            assert_eq!(axial([q, r, s]), position);
+        }
+    }
+
+    #[test]
+    fn distance() {
+        let function = |(from, to)| super::distance(from, to);
+        let cases = [
+            (([0, 0], [0, 0]), 0),
+            (([0, 0], EAST), 1),
+            (([0, 0], NORTH_WEST), 1),
+            (([0, 0], [2, -1]), 2),
+            (([-2, 3], [2, -1]), 4),
+            (([1, 1], [-2, -2]), 6),
+            (([0, 0], [3, -5]), 5),
+        ];
+        test::cases(function, cases);
+    }
+
+    #[test]
+    fn distance_agrees_with_breadth_first_search() {
+        let distances = search::distances([0, 0], |position| {
+            super::neighbors(position)
+                .into_iter()
+                .filter(|&neighbor| super::distance([0, 0], neighbor) <= 4)
+        });
+        assert_eq!(distances.len(), 61);
+        for (position, distance) in distances {
+            assert_eq!(distance, super::distance([0, 0], position).unsigned_abs());
+        }
+    }
+
+    #[test]
+    fn neighbors() {
+        let function = |position| AHashSet::from(super::neighbors(position));
+        let cases = [
+            (
+                [0, 0],
+                AHashSet::from([[1, 0], [0, 1], [-1, 1], [-1, 0], [0, -1], [1, -1]]),
+            ),
+            (
+                [3, -4],
+                AHashSet::from([[4, -4], [3, -3], [2, -3], [2, -4], [3, -5], [4, -5]]),
+            ),
+        ];
+        test::cases(function, cases);
+    }
+
+    #[test]
+    fn rotations() {
+        for (index, direction) in DIRECTIONS.into_iter().enumerate() {
+            assert_eq!(rotate_clockwise(direction), DIRECTIONS[(index + 1) % 6]);
+            assert_eq!(
+                rotate_counterclockwise(direction),
+                DIRECTIONS[(index + 5) % 6]
+            );
+        }
+    }
+
+    #[test]
+    fn rotate_around() {
+        let function = |(position, center, sixths)| super::rotate_around(position, center, sixths);
+        let cases = [
+            (([2, 0], [1, 0], 0), [2, 0]),
+            (([2, 0], [1, 0], 1), [1, 1]),
+            (([2, 0], [1, 0], -1), [2, -1]),
+            (([2, 0], [1, 0], 3), [0, 0]),
+            (([2, 0], [1, 0], 7), [1, 1]),
+            (([3, -1], [0, 0], 2), [-2, 3]),
+        ];
+        test::cases(function, cases);
+    }
+
+    #[test]
+    fn ring() {
+        let center = [5, -2];
+        assert_eq!(super::ring(center, 0).collect_vec(), vec![center]);
+        for radius in 1..=4 {
+            let ring = super::ring(center, radius).collect_vec();
+            assert_eq!(ring.len().cast_signed(), 6 * radius);
+            assert_eq!(ring.iter().collect::<AHashSet<_>>().len(), ring.len());
+            assert!(
+                ring.iter()
+                    .all(|&position| super::distance(center, position) == radius),
+                "every position in ring should have distance {radius} to center"
+            );
+            assert!(
+                ring.iter()
+                    .circular_tuple_windows()
+                    .all(|(&from, &to)| super::distance(from, to) == 1),
+                "consecutive positions in ring should be neighbors"
+            );
+        }
+    }
+
+    #[test]
+    fn spiral() {
+        let function = |radius| super::spiral([0, 0], radius).count();
+        let cases = [(0, 1), (1, 7), (2, 19), (3, 37)];
+        test::cases(function, cases);
+    }
+}
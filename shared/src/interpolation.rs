@@ -0,0 +1,247 @@
+use std::ops::{Add, Sub};
+
+use itertools::Itertools;
+use num_integer::Integer;
+use num_traits::{Signed, Zero};
+
+use crate::linear_equations::Field;
+
+/// The polynomial of least degree through some points, in Newton form, which is exact if
+/// arithmetic on `F` is exact, such as for [`crate::rational::Rational`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewtonPolynomial<F> {
+    nodes: Vec<F>,
+    coefficients: Vec<F>,
+}
+
+impl<F: Field> NewtonPolynomial<F> {
+    /// Interpolates the points `(x, y)` by divided differences, or returns `None` if two points
+    /// share an `x`.
+    #[must_use]
+    pub fn new(points: &[(F, F)]) -> Option<Self> {
+        let nodes = points.iter().map(|(x, _)| x.clone()).collect_vec();
+        let mut differences = points.iter().map(|(_, y)| y.clone()).collect_vec();
+        let mut coefficients = Vec::with_capacity(points.len());
+        for order in 1..=points.len() {
+            coefficients.push(differences[0].clone());
+            differences = differences
+                .iter()
+                .tuple_windows()
+                .enumerate()
+                .map(|(index, (lower, upper))| {
+                    let distance = nodes[index + order].clone() - nodes[index].clone();
+                    (!distance.is_negligible()).then(|| (upper.clone() - lower.clone()) / distance)
+                })
+                .collect::<Option<_>>()?;
+        }
+        Some(Self {
+            nodes,
+            coefficients,
+        })
+    }
+
+    #[must_use]
+    pub fn evaluate(&self, x: &F) -> F {
+        self.coefficients
+            .iter()
+            .zip(&self.nodes)
+            .rev()
+            .fold(F::zero(), |value, (coefficient, node)| {
+                value * (x.clone() - node.clone()) + coefficient.clone()
+            })
+    }
+}
+
+/// The value at `x` of the polynomial of least degree through the points `(x, y)` by Lagrange's
+/// formula, or `None` if two points share an `x`.
+#[must_use]
+pub fn lagrange<F: Field>(points: &[(F, F)], x: &F) -> Option<F> {
+    points
+        .iter()
+        .enumerate()
+        .try_fold(F::zero(), |sum, (index, (node, value))| {
+            let basis = points
+                .iter()
+                .enumerate()
+                .filter(|&(other_index, _)| other_index != index)
+                .try_fold(F::one(), |product, (_, (other_node, _))| {
+                    let distance = node.clone() - other_node.clone();
+                    (!distance.is_negligible())
+                        .then(|| product * (x.clone() - other_node.clone()) / distance)
+                })?;
+            Some(sum + value.clone() * basis)
+        })
+}
+
+/// The value that follows the sequence if it is given by a polynomial of degree less than its
+/// length, or `None` if the sequence is empty.
+pub fn extrapolate_forward<T>(sequence: &[T]) -> Option<T>
+where
+    T: Clone + Zero + Add<Output = T> + Sub<Output = T>,
+{
+    (!sequence.is_empty()).then(|| {
+        difference_rows(sequence)
+            .map(|row| row[row.len() - 1].clone())
+            .fold(T::zero(), |sum, last| sum + last)
+    })
+}
+
+/// The value that precedes the sequence if it is given by a polynomial of degree less than its
+/// length, or `None` if the sequence is empty.
+pub fn extrapolate_backward<T>(sequence: &[T]) -> Option<T>
+where
+    T: Clone + Zero + Add<Output = T> + Sub<Output = T>,
+{
+    (!sequence.is_empty()).then(|| {
+        difference_rows(sequence)
+            .map(|row| row[0].clone())
+            .collect_vec()
+            .into_iter()
+            .rev()
+            .fold(T::zero(), |preceding, first| first - preceding)
+    })
+}
+
+/// The sequence followed by its differences, their differences, and so on, down to a single value.
+fn difference_rows<T: Clone + Sub<Output = T>>(sequence: &[T]) -> impl Iterator<Item = Vec<T>> {
+    let differences = |row: &Vec<T>| {
+        (row.len() > 1).then(|| {
+            row.iter()
+                .tuple_windows()
+                .map(|(lower, upper)| upper.clone() - lower.clone())
+                .collect_vec()
+        })
+    };
+    std::iter::successors(Some(sequence.to_vec()), differences)
+}
+
+/// An integer sequence given by a polynomial in the index, stored as the differences of every
+/// order at index zero.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolynomialSequence<T> {
+    differences: Vec<T>,
+}
+
+impl<T: Clone + Integer + Signed> PolynomialSequence<T> {
+    #[must_use]
+    pub fn degree(&self) -> usize {
+        self.differences.len() - 1
+    }
+
+    /// The value at `index`, which might lie far beyond the fitted values or be negative.
+    #[must_use]
+    pub fn value(&self, index: &T) -> T {
+        let mut binomial_coefficient = T::one();
+        let mut order = T::zero();
+        let mut value = T::zero();
+        for difference in &self.differences {
+            value = value + difference.clone() * binomial_coefficient.clone();
+            binomial_coefficient = binomial_coefficient * (index.clone() - order.clone());
+            order = order + T::one();
+            binomial_coefficient = binomial_coefficient / order.clone();
+        }
+        value
+    }
+}
+
+/// Fits the values at indices `0, 1, 2, …` with a polynomial of least degree. Returns `None`
+/// unless there is at least one more value than needed to determine the polynomial, so that the
+/// fit is verified by the sequence rather than forced onto it.
+#[must_use]
+pub fn fit_sequence<T: Clone + Integer + Signed>(sequence: &[T]) -> Option<PolynomialSequence<T>> {
+    let mut differences = vec![];
+    for row in difference_rows(sequence) {
+        if row.iter().all(Zero::is_zero) {
+            if differences.is_empty() {
+                differences.push(T::zero());
+            }
+            return Some(PolynomialSequence { differences });
+        }
+        differences.push(row[0].clone());
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use infrastructure::test;
+
+    use super::*;
+    use crate::rational::Rational;
+
+    type Q = Rational<i64>;
+
+    fn rational_points(points: &[(i64, i64)]) -> Vec<(Q, Q)> {
+        points
+            .iter()
+            .map(|&(x, y)| (Q::from(x), Q::from(y)))
+            .collect()
+    }
+
+    #[test]
+    fn newton_polynomial() {
+        let points = rational_points(&[(-1, 2), (1, 0), (2, 5), (4, 57)]);
+        let polynomial = NewtonPolynomial::new(&points).expect("nodes should be distinct");
+        for (x, y) in &points {
+            assert_eq!(polynomial.evaluate(x), *y);
+        }
+        assert_eq!(polynomial.evaluate(&Q::from(3)), Q::from(22));
+        assert_eq!(polynomial.evaluate(&Q::new(1, 2)), Q::new(1, 8));
+        let duplicate = rational_points(&[(1, 1), (1, 2)]);
+        assert_eq!(NewtonPolynomial::new(&duplicate), None);
+    }
+
+    #[test]
+    fn lagrange() {
+        let points = rational_points(&[(-1, 2), (1, 0), (2, 5), (4, 57)]);
+        let function = |x: Q| super::lagrange(&points, &x);
+        let cases = [
+            (Q::from(3), Some(Q::from(22))),
+            (Q::new(1, 2), Some(Q::new(1, 8))),
+            (Q::from(-1), Some(Q::from(2))),
+        ];
+        test::cases(function, cases);
+        let duplicate = rational_points(&[(1, 1), (1, 2)]);
+        assert_eq!(super::lagrange(&duplicate, &Q::zero()), None);
+    }
+
+    #[test]
+    fn extrapolation() {
+        let function = |sequence: &[i64]| {
+            [
+                super::extrapolate_backward(sequence),
+                super::extrapolate_forward(sequence),
+            ]
+        };
+        let cases = [
+            (&[0, 3, 6, 9, 12, 15][..], [Some(-3), Some(18)]),
+            (&[1, 3, 6, 10, 15, 21][..], [Some(0), Some(28)]),
+            (&[10, 13, 16, 21, 30, 45][..], [Some(5), Some(68)]),
+            (&[7][..], [Some(7), Some(7)]),
+            (&[][..], [None, None]),
+        ];
+        test::cases(function, cases);
+    }
+
+    #[test]
+    fn fit_sequence() {
+        let squares = [1, 4, 9, 16].map(i64::from);
+        let fit = super::fit_sequence(&squares).expect("squares should be verified");
+        assert_eq!(fit.degree(), 2);
+        assert_eq!(fit.value(&99), 10_000);
+        assert_eq!(fit.value(&-1), 0);
+        assert_eq!(super::fit_sequence(&squares[..3]), None);
+
+        let cubes = (0..6_i128).map(|n| n.pow(3) - 2 * n).collect_vec();
+        let fit = super::fit_sequence(&cubes).expect("cubes should be verified");
+        assert_eq!(fit.degree(), 3);
+        let large = 1_000_000_000_i128;
+        assert_eq!(fit.value(&large), large.pow(3) - 2 * large);
+
+        let constant = super::fit_sequence(&[5_i64, 5]).expect("constant should be verified");
+        assert_eq!(constant.degree(), 0);
+        assert_eq!(constant.value(&1000), 5);
+        let zero = super::fit_sequence(&[0_i64]).expect("zero should be verified");
+        assert_eq!(zero.value(&7), 0);
+    }
+}
@@ -0,0 +1,607 @@
+use std::{
+    array,
+    collections::BTreeMap,
+    iter::{self, Sum},
+    mem,
+    ops::{Mul, Range, Sub},
+};
+
+use itertools::Itertools;
+use num_traits::{One, Signed};
+
+/// A set of values stored as sorted, disjoint and non-adjacent half-open intervals, such that
+/// equal sets have equal representations.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Range<T>>,
+}
+
+impl<T: Copy + Ord> IntervalSet<T> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self { intervals: vec![] }
+    }
+
+    /// The non-empty intervals in increasing order, separated by at least one value.
+    #[must_use]
+    pub fn intervals(&self) -> &[Range<T>] {
+        &self.intervals
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    #[must_use]
+    pub fn contains(&self, value: &T) -> bool {
+        let index = self
+            .intervals
+            .partition_point(|interval| interval.end <= *value);
+        self.intervals
+            .get(index)
+            .is_some_and(|interval| interval.start <= *value)
+    }
+
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        self.intervals
+            .iter()
+            .chain(&other.intervals)
+            .cloned()
+            .collect()
+    }
+
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intersection = vec![];
+        let mut lhs = self.intervals.iter().peekable();
+        let mut rhs = other.intervals.iter().peekable();
+        while let (Some(left), Some(right)) = (lhs.peek(), rhs.peek()) {
+            let start = left.start.max(right.start);
+            let end = left.end.min(right.end);
+            if start < end {
+                intersection.push(start..end);
+            }
+            if left.end < right.end {
+                lhs.next();
+            } else {
+                rhs.next();
+            }
+        }
+        Self {
+            intervals: intersection,
+        }
+    }
+
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut difference = vec![];
+        let mut subtrahends = other.intervals.iter().peekable();
+        for interval in &self.intervals {
+            let mut start = interval.start;
+            while let Some(subtrahend) = subtrahends.peek() {
+                if interval.end <= subtrahend.start {
+                    break;
+                }
+                if start < subtrahend.start {
+                    difference.push(start..subtrahend.start);
+                }
+                start = start.max(subtrahend.end);
+                if interval.end <= subtrahend.end {
+                    break;
+                }
+                subtrahends.next();
+            }
+            if start < interval.end {
+                difference.push(start..interval.end);
+            }
+        }
+        Self {
+            intervals: difference,
+        }
+    }
+
+    /// The values within `universe` that are not in the set.
+    #[must_use]
+    pub fn complement(&self, universe: Range<T>) -> Self {
+        Self::from(universe).difference(self)
+    }
+}
+
+impl<T: Copy + Ord + Sub<Output = T> + Sum> IntervalSet<T> {
+    /// The number of values in the set, or its total length for continuous values.
+    #[must_use]
+    pub fn measure(&self) -> T {
+        self.intervals
+            .iter()
+            .map(|interval| interval.end - interval.start)
+            .sum()
+    }
+}
+
+impl<T: Copy + Ord> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Copy + Ord> From<Range<T>> for IntervalSet<T> {
+    fn from(interval: Range<T>) -> Self {
+        iter::once(interval).collect()
+    }
+}
+
+impl<T: Copy + Ord> FromIterator<Range<T>> for IntervalSet<T> {
+    /// Normalises possibly empty, overlapping and unsorted intervals.
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(intervals: I) -> Self {
+        let mut sorted_intervals = intervals
+            .into_iter()
+            .filter(|interval| !interval.is_empty())
+            .collect_vec();
+        sorted_intervals.sort_unstable_by_key(|interval| interval.start);
+
+        let mut merged_intervals: Vec<Range<T>> = vec![];
+        for interval in sorted_intervals {
+            match merged_intervals.last_mut() {
+                Some(last) if interval.start <= last.end => last.end = last.end.max(interval.end),
+                _ => merged_intervals.push(interval),
+            }
+        }
+        Self {
+            intervals: merged_intervals,
+        }
+    }
+}
+
+/// A map that adds an offset to every value which is constant between consecutive breakpoints,
+/// such as the almanac maps of 2023 day 5. Values below the first breakpoint are unchanged.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OffsetMap<T> {
+    offsets: BTreeMap<T, T>,
+}
+
+impl<T: Copy + Ord + Signed> OffsetMap<T> {
+    /// The identity map.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            offsets: BTreeMap::new(),
+        }
+    }
+
+    /// Makes the map add `offset` to every value in `interval`, replacing any previous offset.
+    pub fn insert(&mut self, interval: Range<T>, offset: T) {
+        if interval.is_empty() {
+            return;
+        }
+        let offset_after = self.offset(interval.end);
+        let overwritten_breakpoints = self
+            .offsets
+            .range(interval.start..=interval.end)
+            .map(|(&breakpoint, _)| breakpoint)
+            .collect_vec();
+        for breakpoint in overwritten_breakpoints {
+            self.offsets.remove(&breakpoint);
+        }
+        self.offsets.insert(interval.start, offset);
+        self.offsets.insert(interval.end, offset_after);
+        self.remove_redundant_breakpoints();
+    }
+
+    #[must_use]
+    pub fn offset(&self, value: T) -> T {
+        self.offsets
+            .range(..=value)
+            .next_back()
+            .map_or_else(T::zero, |(_, &offset)| offset)
+    }
+
+    #[must_use]
+    pub fn apply(&self, value: T) -> T {
+        value + self.offset(value)
+    }
+
+    /// The values that the map sends the values of `set` to.
+    #[must_use]
+    pub fn image(&self, set: &IntervalSet<T>) -> IntervalSet<T> {
+        set.intervals()
+            .iter()
+            .flat_map(|interval| {
+                let breakpoints = self.offsets.range(interval.clone()).map(|(&key, _)| key);
+                iter::once(interval.start)
+                    .chain(breakpoints)
+                    .chain(iter::once(interval.end))
+                    .dedup()
+                    .tuple_windows()
+                    .map(|(start, end)| {
+                        let offset = self.offset(start);
+                        start + offset..end + offset
+                    })
+            })
+            .collect()
+    }
+
+    /// The map that applies `self` and then `then`.
+    #[must_use]
+    pub fn compose(&self, then: &Self) -> Self {
+        let pieces = self.pieces();
+        let preimages_of_breakpoints = then.offsets.keys().flat_map(|&breakpoint| {
+            pieces.iter().filter_map(move |&(start, end, offset)| {
+                let preimage = breakpoint - offset;
+                let within_piece = start.is_none_or(|start| start <= preimage)
+                    && end.is_none_or(|end| preimage < end);
+                within_piece.then_some(preimage)
+            })
+        });
+        let offsets = self
+            .offsets
+            .keys()
+            .copied()
+            .chain(preimages_of_breakpoints)
+            .map(|breakpoint| {
+                let offset = self.offset(breakpoint);
+                (breakpoint, offset + then.offset(breakpoint + offset))
+            })
+            .collect();
+        let mut composition = Self { offsets };
+        composition.remove_redundant_breakpoints();
+        composition
+    }
+
+    /// The intervals between consecutive breakpoints with their offsets, where `None` is unbounded.
+    fn pieces(&self) -> Vec<(Option<T>, Option<T>, T)> {
+        let starts = iter::once((None, T::zero())).chain(
+            self.offsets
+                .iter()
+                .map(|(&breakpoint, &offset)| (Some(breakpoint), offset)),
+        );
+        let ends = self
+            .offsets
+            .keys()
+            .copied()
+            .map(Some)
+            .chain(iter::once(None));
+        starts
+            .zip(ends)
+            .map(|((start, offset), end)| (start, end, offset))
+            .collect_vec()
+    }
+
+    fn remove_redundant_breakpoints(&mut self) {
+        let mut previous_offset = T::zero();
+        self.offsets.retain(|_, &mut offset| {
+            let redundant = offset == previous_offset;
+            previous_offset = offset;
+            !redundant
+        });
+    }
+}
+
+impl<T: Copy + Ord + Signed> Default for OffsetMap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A set of points in `DIMENSION` dimensions stored as disjoint boxes, where a box is a half-open
+/// range along every axis.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BoxSet<T, const DIMENSION: usize> {
+    boxes: Vec<[Range<T>; DIMENSION]>,
+}
+
+impl<T: Copy + Ord, const DIMENSION: usize> BoxSet<T, DIMENSION> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self { boxes: vec![] }
+    }
+
+    /// Disjoint non-empty boxes in no particular order.
+    #[must_use]
+    pub fn boxes(&self) -> &[[Range<T>; DIMENSION]] {
+        &self.boxes
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.boxes.is_empty()
+    }
+
+    #[must_use]
+    pub fn contains(&self, point: &[T; DIMENSION]) -> bool {
+        self.boxes.iter().any(|block| {
+            block
+                .iter()
+                .zip(point)
+                .all(|(range, coordinate)| range.contains(coordinate))
+        })
+    }
+
+    /// Adds the points of `block`, splitting existing boxes that overlap it.
+    pub fn insert(&mut self, block: [Range<T>; DIMENSION]) {
+        if block.iter().any(Range::is_empty) {
+            return;
+        }
+        self.remove(&block);
+        self.boxes.push(block);
+    }
+
+    /// Removes the points of `block`, splitting existing boxes that overlap it.
+    pub fn remove(&mut self, block: &[Range<T>; DIMENSION]) {
+        self.boxes = mem::take(&mut self.boxes)
+            .into_iter()
+            .flat_map(|existing| box_difference(&existing, block))
+            .collect_vec();
+    }
+
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        for block in &other.boxes {
+            union.insert(block.clone());
+        }
+        union
+    }
+
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        let boxes = self
+            .boxes
+            .iter()
+            .cartesian_product(&other.boxes)
+            .filter_map(|(lhs, rhs)| box_intersection(lhs, rhs))
+            .collect_vec();
+        Self { boxes }
+    }
+
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut difference = self.clone();
+        for block in &other.boxes {
+            difference.remove(block);
+        }
+        difference
+    }
+}
+
+impl<T, const DIMENSION: usize> BoxSet<T, DIMENSION>
+where
+    T: Copy + Ord + One + Sub<Output = T> + Mul<Output = T> + Sum,
+{
+    /// The number of points in the set, or its total volume for continuous coordinates.
+    #[must_use]
+    pub fn volume(&self) -> T {
+        self.boxes
+            .iter()
+            .map(|block| {
+                block
+                    .iter()
+                    .map(|range| range.end - range.start)
+                    .fold(T::one(), |volume, length| volume * length)
+            })
+            .sum()
+    }
+}
+
+impl<T: Copy + Ord, const DIMENSION: usize> Default for BoxSet<T, DIMENSION> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Copy + Ord, const DIMENSION: usize> FromIterator<[Range<T>; DIMENSION]>
+    for BoxSet<T, DIMENSION>
+{
+    /// Inserts possibly overlapping boxes one after another.
+    fn from_iter<I: IntoIterator<Item = [Range<T>; DIMENSION]>>(boxes: I) -> Self {
+        let mut set = Self::new();
+        for block in boxes {
+            set.insert(block);
+        }
+        set
+    }
+}
+
+fn box_intersection<T: Copy + Ord, const DIMENSION: usize>(
+    lhs: &[Range<T>; DIMENSION],
+    rhs: &[Range<T>; DIMENSION],
+) -> Option<[Range<T>; DIMENSION]> {
+    let intersection: [Range<T>; DIMENSION] = array::from_fn(|axis| {
+        lhs[axis].start.max(rhs[axis].start)..lhs[axis].end.min(rhs[axis].end)
+    });
+    (!intersection.iter().any(Range::is_empty)).then_some(intersection)
+}
+
+/// The points of `lhs` that are not in `rhs` as at most two boxes per axis, obtained by cutting
+/// off the parts of `lhs` below and above `rhs` along one axis after another.
+fn box_difference<T: Copy + Ord, const DIMENSION: usize>(
+    lhs: &[Range<T>; DIMENSION],
+    rhs: &[Range<T>; DIMENSION],
+) -> Vec<[Range<T>; DIMENSION]> {
+    if box_intersection(lhs, rhs).is_none() {
+        return vec![lhs.clone()];
+    }
+
+    let mut remaining = lhs.clone();
+    let mut difference = vec![];
+    for (axis, subtrahend) in rhs.iter().enumerate() {
+        let range = remaining[axis].clone();
+        let below = range.start..subtrahend.start.min(range.end);
+        let above = subtrahend.end.max(range.start)..range.end;
+        for part in [below, above] {
+            if !part.is_empty() {
+                let mut block = remaining.clone();
+                block[axis] = part;
+                difference.push(block);
+            }
+        }
+        remaining[axis] = range.start.max(subtrahend.start)..range.end.min(subtrahend.end);
+    }
+    difference
+}
+
+#[cfg(test)]
+mod tests {
+    use infrastructure::test;
+
+    use super::*;
+
+    fn set(intervals: &[Range<isize>]) -> IntervalSet<isize> {
+        intervals.iter().cloned().collect()
+    }
+
+    #[test]
+    fn normalisation() {
+        let function = |intervals: Vec<Range<isize>>| set(&intervals).intervals().to_vec();
+        #[expect(
+            clippy::single_range_in_vec_init,
+            clippy::reversed_empty_ranges,
+            reason = "lists of ranges, including a reversed one, are desired"
+        )]
+        let cases = [
+            (vec![7..9, 1..3], vec![1..3, 7..9]),
+            (vec![1..5, 3..8, 8..10], vec![1..10]),
+            (vec![2..4, 1..10, 5..5], vec![1..10]),
+            (vec![4..2], vec![]),
+        ];
+        test::cases(function, cases);
+        assert_eq!(IntervalSet::default(), IntervalSet::from(3..3));
+    }
+
+    #[test]
+    fn set_operations() {
+        let lhs = set(&[0..10, 20..30]);
+        let rhs = set(&[5..25, 28..40]);
+        assert_eq!(lhs.union(&rhs), IntervalSet::from(0..40));
+        assert_eq!(lhs.intersection(&rhs), set(&[5..10, 20..25, 28..30]));
+        assert_eq!(lhs.difference(&rhs), set(&[0..5, 25..28]));
+        assert_eq!(rhs.difference(&lhs), set(&[10..20, 30..40]));
+        assert_eq!(lhs.difference(&lhs), IntervalSet::new());
+        assert_eq!(lhs.measure(), 20);
+        assert!(lhs.contains(&0), "set should contain its first value");
+        assert!(!lhs.contains(&10), "set should not contain an interval end");
+        assert!(!lhs.contains(&-1), "set should not contain values below it");
+    }
+
+    #[test]
+    fn complement() {
+        let universe = 0..21;
+        let function = |intervals: Vec<Range<isize>>| set(&intervals).complement(universe.clone());
+        #[expect(
+            clippy::single_range_in_vec_init,
+            reason = "a list of ranges is desired"
+        )]
+        let cases = [
+            (vec![-10..0], IntervalSet::from(0..21)),
+            (vec![7..9], set(&[0..7, 9..21])),
+            (vec![-3..14, 15..26], IntervalSet::from(14..15)),
+            (vec![-5..30], IntervalSet::new()),
+        ];
+        test::cases(function, cases);
+    }
+
+    #[test]
+    fn offset_map() {
+        let mut map = OffsetMap::new();
+        map.insert(98..100, -48);
+        map.insert(50..98, 2);
+        let function = |value| map.apply(value);
+        let cases = [
+            (0, 0),
+            (49, 49),
+            (50, 52),
+            (97, 99),
+            (98, 50),
+            (99, 51),
+            (100, 100),
+        ];
+        test::cases(function, cases);
+
+        let seeds = set(&[79..93, 55..68]);
+        assert_eq!(map.image(&seeds), set(&[57..70, 81..95]));
+        let edges = IntervalSet::from(96..102);
+        assert_eq!(map.image(&edges), set(&[50..52, 98..102]));
+
+        map.insert(50..100, 0);
+        assert_eq!(map, OffsetMap::new());
+    }
+
+    #[test]
+    fn offset_map_composition() {
+        let mut first = OffsetMap::new();
+        first.insert(0..10, 100);
+        first.insert(20..30, -15);
+        let mut second = OffsetMap::new();
+        second.insert(5..15, 1000);
+        second.insert(105..108, -105);
+        let composition = first.compose(&second);
+        for value in -5..40 {
+            assert_eq!(
+                composition.apply(value),
+                second.apply(first.apply(value)),
+                "composition should agree at {value}",
+            );
+        }
+        let everything = IntervalSet::from(-5..40);
+        assert_eq!(
+            composition.image(&everything),
+            second.image(&first.image(&everything)),
+        );
+        assert_eq!(OffsetMap::new().compose(&first), first);
+    }
+
+    #[test]
+    fn box_difference() {
+        let lhs = [5..15, 5..15, 5..15];
+        let rhs = [0..20, 7..11, 1..8];
+        let difference: BoxSet<_, 3> = super::box_difference(&lhs, &rhs).into_iter().collect();
+        assert_eq!(difference.volume(), 1000 - 10 * 4 * 3);
+        assert!(
+            !difference.contains(&[5, 7, 5]),
+            "subtracted point should be removed"
+        );
+        assert!(
+            difference.contains(&[5, 11, 5]),
+            "remaining point should be kept"
+        );
+    }
+
+    #[test]
+    fn box_intersection() {
+        let function = |[lhs, rhs]: [[Range<isize>; 3]; 2]| super::box_intersection(&lhs, &rhs);
+        let cases = [
+            (
+                [[2..4, 2..4, 2..4], [2..4, 2..4, 2..4]],
+                Some([2..4, 2..4, 2..4]),
+            ),
+            (
+                [[1..3, 1..3, 1..3], [2..4, 2..4, 2..4]],
+                Some([2..3, 2..3, 2..3]),
+            ),
+            ([[2..4, 2..4, 2..4], [4..6, 2..4, 2..4]], None),
+            ([[2..4, 2..4, 2..4], [2..4, 4..6, 2..4]], None),
+            ([[2..4, 2..4, 2..4], [2..4, 2..4, 4..6]], None),
+        ];
+        test::cases(function, cases);
+    }
+
+    #[test]
+    fn box_set_operations() {
+        let mut squares = BoxSet::new();
+        squares.insert([0..4, 0..4]);
+        squares.insert([2..6, 2..6]);
+        assert_eq!(squares.volume(), 28);
+        squares.remove(&[1..3, 1..3]);
+        assert_eq!(squares.volume(), 24);
+
+        let strip: BoxSet<_, 2> = [[-10..10, 3..4]].into_iter().collect();
+        assert_eq!(squares.union(&strip).volume(), 24 + 20 - 6);
+        assert_eq!(squares.intersection(&strip).volume(), 6);
+        assert_eq!(squares.difference(&strip).volume(), 18);
+        assert!(
+            squares.difference(&squares).is_empty(),
+            "difference with itself should be empty"
+        );
+    }
+}
@@ -1,9 +1,23 @@
+pub mod automaton;
+pub mod cube;
 pub mod floating_point;
+pub mod generators;
+pub mod geometry;
 pub mod graph;
 pub mod grid;
+pub mod hex;
+pub mod interpolation;
+pub mod interval;
 pub mod linear_equations;
 pub mod matrix;
+pub mod modular;
 pub mod number_theory;
+pub mod rational;
+pub mod render;
 pub mod search;
+#[cfg(test)]
+mod strategies;
 pub mod string;
+pub mod symbolic;
 pub mod vector;
+pub mod vm;
@@ -1,98 +1,149 @@
+use std::{
+    cmp::Ordering,
+    ops::{Div, Mul, Neg, Sub},
+};
+
 use itertools::Itertools;
+use num_integer::Integer;
+use num_traits::{One, Signed, Zero};
 
-use crate::{
-    floating_point::ApproxEq,
-    matrix::{self, column},
-    vector::Negation,
-};
+use crate::{floating_point::ApproxEq, matrix, rational::Rational};
+
+/// Numbers that systems of linear equations can be solved over.
+pub trait Field:
+    Clone
+    + PartialEq
+    + Zero
+    + One
+    + Neg<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+{
+    /// Whether the number is zero, up to rounding errors if arithmetic is inexact.
+    fn is_negligible(&self) -> bool;
+
+    /// Compares absolute values to pick pivots that keep rounding errors small.
+    fn cmp_magnitude(&self, other: &Self) -> Ordering;
+}
+
+impl Field for f64 {
+    fn is_negligible(&self) -> bool {
+        self.approx_eq(0.0)
+    }
+
+    fn cmp_magnitude(&self, other: &Self) -> Ordering {
+        self.abs().total_cmp(&other.abs())
+    }
+}
+
+impl<T: Clone + Integer + Signed> Field for Rational<T> {
+    fn is_negligible(&self) -> bool {
+        self.is_zero()
+    }
 
-type Matrix<const NUM_ROWS: usize, const NUM_COLUMNS: usize> =
-    matrix::Matrix<Real, NUM_ROWS, NUM_COLUMNS>;
-type SolutionSet = Option<(Origin, Vec<Direction>)>;
-type Origin = Vec<Real>;
-type Direction = Vec<Real>;
-type Real = f64;
+    fn cmp_magnitude(&self, other: &Self) -> Ordering {
+        self.abs().cmp(&other.abs())
+    }
+}
+
+type Matrix<F, const NUM_ROWS: usize, const NUM_COLUMNS: usize> =
+    matrix::Matrix<F, NUM_ROWS, NUM_COLUMNS>;
+
+/// The solutions of a system of linear equations as an origin and the directions that any linear
+/// combination of can be added to it, or `None` if there are no solutions.
+pub type SolutionSet<F> = Option<(Origin<F>, Vec<Direction<F>>)>;
+pub type Origin<F> = Vec<F>;
+pub type Direction<F> = Vec<F>;
 
+/// Solves the system of linear equations given by its augmented matrix, exactly if arithmetic on
+/// `F` is exact, such as for [`Rational`].
 #[must_use]
-pub fn solution_set<const NUM_ROWS: usize, const NUM_COLUMNS: usize>(
-    augmented_matrix: Matrix<NUM_ROWS, NUM_COLUMNS>,
-) -> SolutionSet {
+pub fn solution_set<F: Field, const NUM_ROWS: usize, const NUM_COLUMNS: usize>(
+    augmented_matrix: Matrix<F, NUM_ROWS, NUM_COLUMNS>,
+) -> SolutionSet<F> {
     let reduced_row_echelon_form = reduced_row_echelon_form(augmented_matrix);
-    solution_set_from_reduced_row_echelon_form(reduced_row_echelon_form)
+    solution_set_from_reduced_row_echelon_form(&reduced_row_echelon_form)
 }
 
-fn reduced_row_echelon_form<const NUM_ROWS: usize, const NUM_COLUMNS: usize>(
-    mut matrix: Matrix<NUM_ROWS, NUM_COLUMNS>,
-) -> Matrix<NUM_ROWS, NUM_COLUMNS> {
+fn reduced_row_echelon_form<F: Field, const NUM_ROWS: usize, const NUM_COLUMNS: usize>(
+    mut matrix: Matrix<F, NUM_ROWS, NUM_COLUMNS>,
+) -> Matrix<F, NUM_ROWS, NUM_COLUMNS> {
     let [mut pivot_row, mut pivot_column] = [0, 0];
     while pivot_row < NUM_ROWS && pivot_column < NUM_COLUMNS {
-        let row_with_max_abs_value = row_with_max_abs_value(matrix, pivot_row, pivot_column);
-        let pivot = matrix[row_with_max_abs_value][pivot_column];
-        if pivot.approx_eq(0.0) {
+        let row_with_max_abs_value = row_with_max_abs_value(&matrix, pivot_row, pivot_column);
+        let pivot = matrix[row_with_max_abs_value][pivot_column].clone();
+        if pivot.is_negligible() {
             pivot_column += 1;
             continue;
         }
 
-        [matrix[row_with_max_abs_value], matrix[pivot_row]] =
-            [matrix[pivot_row], matrix[row_with_max_abs_value]];
-        subtract_pivot_row_from_other_rows(&mut matrix, pivot_row, pivot_column, pivot);
-        divide_pivot_row_by_pivot(&mut matrix, pivot_row, pivot_column, pivot);
+        matrix.swap(row_with_max_abs_value, pivot_row);
+        subtract_pivot_row_from_other_rows(&mut matrix, pivot_row, pivot_column, &pivot);
+        divide_pivot_row_by_pivot(&mut matrix, pivot_row, pivot_column, &pivot);
         [pivot_row, pivot_column] = [pivot_row + 1, pivot_column + 1];
     }
     matrix
 }
 
-fn row_with_max_abs_value<const NUM_ROWS: usize, const NUM_COLUMNS: usize>(
-    matrix: Matrix<NUM_ROWS, NUM_COLUMNS>,
+fn row_with_max_abs_value<F: Field, const NUM_ROWS: usize, const NUM_COLUMNS: usize>(
+    matrix: &Matrix<F, NUM_ROWS, NUM_COLUMNS>,
     first_row: usize,
     column: usize,
 ) -> usize {
     let row_with_max_abs_value = matrix[first_row..]
         .iter()
-        .map(|row| row[column].abs())
-        .position_max_by(f64::total_cmp)
+        .map(|row| &row[column])
+        .position_max_by(|lhs, rhs| lhs.cmp_magnitude(rhs))
         .expect("first row should be within maxtrix");
     first_row + row_with_max_abs_value
 }
 
-fn subtract_pivot_row_from_other_rows<const NUM_ROWS: usize, const NUM_COLUMNS: usize>(
-    matrix: &mut Matrix<NUM_ROWS, NUM_COLUMNS>,
+fn subtract_pivot_row_from_other_rows<F: Field, const NUM_ROWS: usize, const NUM_COLUMNS: usize>(
+    matrix: &mut Matrix<F, NUM_ROWS, NUM_COLUMNS>,
     pivot_row: usize,
     pivot_column: usize,
-    pivot: f64,
+    pivot: &F,
 ) {
+    let pivot_row_elements = matrix[pivot_row].clone();
     for row in (0..NUM_ROWS).filter(|&row| row != pivot_row) {
-        let ratio = matrix[row][pivot_column] / pivot;
-        matrix[row][pivot_column] = 0.0;
-        #[expect(clippy::needless_range_loop, reason = "indices make intention clearer")]
-        for column in pivot_column + 1..NUM_COLUMNS {
-            matrix[row][column] -= matrix[pivot_row][column] * ratio;
+        let ratio = matrix[row][pivot_column].clone() / pivot.clone();
+        if ratio.is_zero() {
+            continue;
+        }
+        matrix[row][pivot_column] = F::zero();
+        let elements = matrix[row][pivot_column + 1..].iter_mut();
+        for (element, pivot_row_element) in elements.zip(&pivot_row_elements[pivot_column + 1..]) {
+            *element = element.clone() - pivot_row_element.clone() * ratio.clone();
         }
     }
 }
 
-fn divide_pivot_row_by_pivot<const NUM_ROWS: usize, const NUM_COLUMNS: usize>(
-    matrix: &mut Matrix<NUM_ROWS, NUM_COLUMNS>,
+fn divide_pivot_row_by_pivot<F: Field, const NUM_ROWS: usize, const NUM_COLUMNS: usize>(
+    matrix: &mut Matrix<F, NUM_ROWS, NUM_COLUMNS>,
     pivot_row: usize,
     pivot_column: usize,
-    pivot: f64,
+    pivot: &F,
 ) {
-    #[expect(clippy::needless_range_loop, reason = "indices make intention clearer")]
-    for column in pivot_column..NUM_COLUMNS {
-        matrix[pivot_row][column] /= pivot;
+    for element in &mut matrix[pivot_row][pivot_column..] {
+        *element = element.clone() / pivot.clone();
     }
 }
 
-fn solution_set_from_reduced_row_echelon_form<const NUM_ROWS: usize, const NUM_COLUMNS: usize>(
-    matrix: Matrix<NUM_ROWS, NUM_COLUMNS>,
-) -> SolutionSet {
+fn solution_set_from_reduced_row_echelon_form<
+    F: Field,
+    const NUM_ROWS: usize,
+    const NUM_COLUMNS: usize,
+>(
+    matrix: &Matrix<F, NUM_ROWS, NUM_COLUMNS>,
+) -> SolutionSet<F> {
     let [mut pivot_row, mut pivot_column] = [0, 0];
     let mut free_parameters = vec![];
     let mut origin = vec![];
     let mut directions = vec![];
     while pivot_row < NUM_ROWS && pivot_column < NUM_COLUMNS - 1 {
-        if matrix[pivot_row][pivot_column].approx_eq(0.0) {
-            origin.push(0.0);
+        if matrix[pivot_row][pivot_column].is_negligible() {
+            origin.push(F::zero());
             directions.push(solution_set_direction(
                 matrix,
                 pivot_row,
@@ -102,38 +153,47 @@ fn solution_set_from_reduced_row_echelon_form<const NUM_ROWS: usize, const NUM_C
             free_parameters.push(pivot_column);
             pivot_column += 1;
         } else {
-            origin.push(matrix[pivot_row][NUM_COLUMNS - 1]);
+            origin.push(matrix[pivot_row][NUM_COLUMNS - 1].clone());
             [pivot_row, pivot_column] = [pivot_row + 1, pivot_column + 1];
         }
     }
-    if pivot_row < NUM_ROWS && matrix[pivot_row][NUM_COLUMNS - 1].approx_eq(1.0) {
-        None
-    } else {
-        Some((origin, directions))
-    }
+    let is_inconsistent = pivot_row < NUM_ROWS
+        && (matrix[pivot_row][NUM_COLUMNS - 1].clone() - F::one()).is_negligible();
+    (!is_inconsistent).then_some((origin, directions))
 }
 
-fn solution_set_direction<const NUM_ROWS: usize, const NUM_COLUMNS: usize>(
-    matrix: Matrix<NUM_ROWS, NUM_COLUMNS>,
+fn solution_set_direction<F: Field, const NUM_ROWS: usize, const NUM_COLUMNS: usize>(
+    matrix: &Matrix<F, NUM_ROWS, NUM_COLUMNS>,
     pivot_row: usize,
     pivot_column: usize,
     free_parameters: &[usize],
-) -> Vec<f64> {
-    let mut direction: Vec<Real> = column(matrix, pivot_column).neg()[..pivot_row].into();
+) -> Direction<F> {
+    let mut direction = matrix[..pivot_row]
+        .iter()
+        .map(|row| -row[pivot_column].clone())
+        .collect_vec();
     for &parameter in free_parameters {
-        direction.insert(parameter, 0.0);
+        direction.insert(parameter, F::zero());
     }
-    direction.push(1.0);
-    direction.resize(NUM_COLUMNS - 1, 0.0);
+    direction.push(F::one());
+    direction.resize(NUM_COLUMNS - 1, F::zero());
     direction
 }
 
+/// The solution as integers, or `None` if any of its values is not an integer.
+pub fn integer_solution<T: Clone + Integer + Signed>(solution: &[Rational<T>]) -> Option<Vec<T>> {
+    solution.iter().map(Rational::to_integer).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use infrastructure::test;
+    use proptest::{array::uniform3, prelude::*};
 
     use super::*;
 
+    type Real = f64;
+
     #[test]
     fn exactly_one_solution() {
         let matrix = [
@@ -192,7 +252,53 @@ mod tests {
         assert_solution_sets_approx_eq(actual, expected);
     }
 
-    fn assert_solution_sets_approx_eq(left: SolutionSet, right: SolutionSet) {
+    #[test]
+    fn exact_solution_over_rationals() {
+        let matrix = [[1, 1, 1], [1, -1, 0]].map(|row| row.map(Rational::<i64>::from));
+        let actual = solution_set(matrix);
+        let half = Rational::new(1, 2);
+        let expected = Some((vec![half, half], vec![]));
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn exact_solution_with_large_coefficients() {
+        let large = 10_i128.pow(17);
+        let matrix = [
+            [large + 1, large, 2 * large + 1],
+            [large, large - 1, 2 * large - 1],
+        ]
+        .map(|row| row.map(Rational::from));
+        let (origin, directions) = solution_set(matrix).expect("system should have a solution");
+        assert!(directions.is_empty(), "solution should be unique");
+        assert_eq!(super::integer_solution(&origin), Some(vec![1, 1]));
+    }
+
+    #[test]
+    fn affine_solution_set_over_rationals() {
+        let matrix =
+            [[1, 3, 1, 9], [1, 1, -1, 1], [3, 11, 5, 35]].map(|row| row.map(Rational::<i64>::from));
+        let actual = solution_set(matrix);
+        let integers = |values: [i64; 3]| values.map(Rational::from).to_vec();
+        let expected = Some((integers([-3, 4, 0]), vec![integers([2, -1, 1])]));
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn integer_solution() {
+        let function = |solution: Vec<Rational<i64>>| super::integer_solution(&solution);
+        let cases = [
+            (
+                vec![Rational::from(3), Rational::new(-8, 4)],
+                Some(vec![3, -2]),
+            ),
+            (vec![Rational::from(3), Rational::new(1, 2)], None),
+            (vec![], Some(vec![])),
+        ];
+        test::cases(function, cases);
+    }
+
+    fn assert_solution_sets_approx_eq(left: SolutionSet<Real>, right: SolutionSet<Real>) {
         match [left, right] {
             [
                 Some((left_origin, left_directions)),
@@ -233,4 +339,61 @@ mod tests {
             .zip_eq(right)
             .all(|(&left, &right)| left.approx_eq(right))
     }
+
+    type Q = Rational<i64>;
+
+    fn linear_combination(coefficients: &[Q], values: &[Q]) -> Q {
+        coefficients
+            .iter()
+            .zip(values)
+            .fold(Q::zero(), |sum, (&coefficient, &value)| {
+                sum + coefficient * value
+            })
+    }
+
+    fn satisfies(
+        augmented_matrix: &[[Q; 4]; 3],
+        (origin, directions): &(Origin<Q>, Vec<Direction<Q>>),
+    ) -> bool {
+        augmented_matrix.iter().all(|row| {
+            let (constant, coefficients) = row.split_last().expect("row should not be empty");
+            linear_combination(coefficients, origin) == *constant
+                && directions
+                    .iter()
+                    .all(|direction| linear_combination(coefficients, direction).is_zero())
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn solutions_satisfy_the_system(
+            augmented_matrix in uniform3(proptest::array::uniform4(-5..=5_i64)),
+        ) {
+            let augmented_matrix = augmented_matrix.map(|row| row.map(Q::from));
+            if let Some(solutions) = solution_set(augmented_matrix) {
+                prop_assert!(
+                    satisfies(&augmented_matrix, &solutions),
+                    "{solutions:?} should satisfy the system",
+                );
+            }
+        }
+
+        #[test]
+        fn systems_with_a_solution_are_solvable(
+            coefficients in uniform3(uniform3(-5..=5_i64)),
+            solution in uniform3(-5..=5_i64),
+        ) {
+            let solution = solution.map(Q::from);
+            let augmented_matrix = coefficients.map(|row| {
+                let row = row.map(Q::from);
+                let constant = linear_combination(&row, &solution);
+                [row[0], row[1], row[2], constant]
+            });
+            let solutions = solution_set(augmented_matrix);
+            prop_assert!(
+                solutions.is_some_and(|solutions| satisfies(&augmented_matrix, &solutions)),
+                "system should be solvable",
+            );
+        }
+    }
 }
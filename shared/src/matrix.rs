@@ -1,8 +1,13 @@
-use std::{array, ops::Neg};
+use std::{
+    array,
+    cmp::Ordering,
+    ops::{Add, Index, IndexMut, Mul, Neg},
+};
 
+use itertools::Itertools;
 use num_traits::{NumCast, NumOps, One, Zero};
 
-use crate::vector::Vector;
+use crate::{linear_equations::Field, vector::Vector};
 
 pub type Matrix<T, const NUM_ROWS: usize, const NUM_COLUMNS: usize> = [[T; NUM_COLUMNS]; NUM_ROWS];
 
@@ -72,10 +77,430 @@ pub fn quarter_rotation_around_z_axis<T: Zero + One + Neg<Output = T>>() -> Matr
     ]
 }
 
+/// A matrix whose dimensions are only known at runtime, stored in row-major order and indexed by
+/// `[row, column]`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DMatrix<T> {
+    elements: Vec<T>,
+    num_rows: usize,
+    num_columns: usize,
+}
+
+impl<T> DMatrix<T> {
+    /// # Panics
+    ///
+    /// Panics if the number of elements is not a multiple of `num_columns`.
+    #[must_use]
+    pub fn from_elements(elements: Vec<T>, num_columns: usize) -> Self {
+        assert!(num_columns > 0, "matrix should have at least one column");
+        assert!(
+            elements.len().is_multiple_of(num_columns),
+            "number of elements should be a multiple of the number of columns"
+        );
+        Self {
+            num_rows: elements.len() / num_columns,
+            elements,
+            num_columns,
+        }
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the rows are empty or of different lengths.
+    pub fn from_rows<Row: IntoIterator<Item = T>>(rows: impl IntoIterator<Item = Row>) -> Self {
+        let mut elements = vec![];
+        let mut num_columns = None;
+        for row in rows {
+            let row_start = elements.len();
+            elements.extend(row);
+            let row_length = elements.len() - row_start;
+            assert_eq!(
+                *num_columns.get_or_insert(row_length),
+                row_length,
+                "rows should have equal lengths"
+            );
+        }
+        let num_columns = num_columns.expect("matrix should have at least one row");
+        Self::from_elements(elements, num_columns)
+    }
+
+    #[must_use]
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    #[must_use]
+    pub fn num_columns(&self) -> usize {
+        self.num_columns
+    }
+
+    #[must_use]
+    pub fn is_square(&self) -> bool {
+        self.num_rows == self.num_columns
+    }
+
+    #[must_use]
+    pub fn row(&self, index: usize) -> &[T] {
+        &self.elements[index * self.num_columns..(index + 1) * self.num_columns]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.elements.chunks(self.num_columns)
+    }
+
+    pub fn swap_rows(&mut self, lhs: usize, rhs: usize) {
+        for column in 0..self.num_columns {
+            self.elements.swap(
+                lhs * self.num_columns + column,
+                rhs * self.num_columns + column,
+            );
+        }
+    }
+
+    /// The matrix with compile-time dimensions, or `None` if the dimensions differ.
+    #[must_use]
+    pub fn into_array<const NUM_ROWS: usize, const NUM_COLUMNS: usize>(
+        self,
+    ) -> Option<Matrix<T, NUM_ROWS, NUM_COLUMNS>> {
+        if [self.num_rows, self.num_columns] != [NUM_ROWS, NUM_COLUMNS] {
+            return None;
+        }
+        let mut elements = self.elements.into_iter();
+        let rows = (0..NUM_ROWS)
+            .map(|_| {
+                elements
+                    .by_ref()
+                    .take(NUM_COLUMNS)
+                    .collect_vec()
+                    .try_into()
+                    .ok()
+            })
+            .collect::<Option<Vec<_>>>()?;
+        rows.try_into().ok()
+    }
+}
+
+impl<T: Clone> DMatrix<T> {
+    #[must_use]
+    pub fn transpose(&self) -> Self {
+        let elements = (0..self.num_columns)
+            .flat_map(|column| self.rows().map(move |row| row[column].clone()))
+            .collect_vec();
+        Self::from_elements(elements, self.num_rows)
+    }
+}
+
+impl<T: Clone + Zero> DMatrix<T> {
+    #[must_use]
+    pub fn zeros(num_rows: usize, num_columns: usize) -> Self {
+        Self::from_elements(vec![T::zero(); num_rows * num_columns], num_columns)
+    }
+}
+
+impl<T: Clone + Zero + One> DMatrix<T> {
+    #[must_use]
+    pub fn identity(size: usize) -> Self {
+        let mut identity = Self::zeros(size, size);
+        for index in 0..size {
+            identity[[index, index]] = T::one();
+        }
+        identity
+    }
+}
+
+impl<T: Clone + Zero + Add<Output = T> + Mul<Output = T>> DMatrix<T> {
+    /// # Panics
+    ///
+    /// Panics if the length of `rhs` differs from the number of columns.
+    #[must_use]
+    pub fn vector_mul(&self, rhs: &[T]) -> Vec<T> {
+        assert_eq!(
+            rhs.len(),
+            self.num_columns,
+            "vector length should equal number of columns"
+        );
+        self.rows().map(|row| dot(row, rhs)).collect()
+    }
+}
+
+impl<T: Clone + Zero + One + Add<Output = T> + Mul<Output = T>> DMatrix<T> {
+    /// Raises the matrix to the power of `exponent` by repeated squaring, which takes a number of
+    /// matrix multiplications logarithmic in `exponent`. Useful for advancing linear recurrences.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matrix is not square.
+    #[must_use]
+    pub fn pow(&self, mut exponent: usize) -> Self {
+        assert!(self.is_square(), "matrix should be square");
+        let mut power = Self::identity(self.num_rows);
+        let mut base = self.clone();
+        while exponent > 0 {
+            if exponent % 2 == 1 {
+                power = &power * &base;
+            }
+            exponent /= 2;
+            if exponent > 0 {
+                base = &base * &base;
+            }
+        }
+        power
+    }
+}
+
+impl<F: Field> DMatrix<F> {
+    /// The LU decomposition with partial pivoting, or `None` if the matrix is singular.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matrix is not square.
+    #[must_use]
+    pub fn lu_decomposition(&self) -> Option<LuDecomposition<F>> {
+        assert!(self.is_square(), "matrix should be square");
+        let size = self.num_rows;
+        let mut factors = self.clone();
+        let mut permutation = (0..size).collect_vec();
+        let mut is_odd_permutation = false;
+        for pivot in 0..size {
+            let pivot_row = max_abs_row(&factors, pivot, pivot);
+            if factors[[pivot_row, pivot]].is_negligible() {
+                return None;
+            }
+            if pivot_row != pivot {
+                factors.swap_rows(pivot_row, pivot);
+                permutation.swap(pivot_row, pivot);
+                is_odd_permutation = !is_odd_permutation;
+            }
+            for row in pivot + 1..size {
+                let ratio = factors[[row, pivot]].clone() / factors[[pivot, pivot]].clone();
+                for column in pivot + 1..size {
+                    let subtrahend = ratio.clone() * factors[[pivot, column]].clone();
+                    factors[[row, column]] = factors[[row, column]].clone() - subtrahend;
+                }
+                factors[[row, pivot]] = ratio;
+            }
+        }
+        Some(LuDecomposition {
+            factors,
+            permutation,
+            is_odd_permutation,
+        })
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the matrix is not square.
+    #[must_use]
+    pub fn determinant(&self) -> F {
+        self.lu_decomposition()
+            .map_or_else(F::zero, |decomposition| decomposition.determinant())
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the matrix is not square.
+    #[must_use]
+    pub fn inverse(&self) -> Option<Self> {
+        let decomposition = self.lu_decomposition()?;
+        let columns = (0..self.num_rows).map(|index| {
+            let mut unit = vec![F::zero(); self.num_rows];
+            unit[index] = F::one();
+            decomposition.solve(&unit)
+        });
+        Some(Self::from_rows(columns).transpose())
+    }
+
+    /// The unique solution `x` of `self . x == rhs`, or `None` if there is none. Use
+    /// [`crate::linear_equations::solution_set`] for systems that might have many solutions.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matrix is not square or the length of `rhs` differs from its size.
+    #[must_use]
+    pub fn solve(&self, rhs: &[F]) -> Option<Vec<F>> {
+        Some(self.lu_decomposition()?.solve(rhs))
+    }
+
+    #[must_use]
+    pub fn rank(&self) -> usize {
+        let mut matrix = self.clone();
+        let mut rank = 0;
+        for column in 0..self.num_columns {
+            if rank == self.num_rows {
+                break;
+            }
+            let pivot_row = max_abs_row(&matrix, rank, column);
+            if matrix[[pivot_row, column]].is_negligible() {
+                continue;
+            }
+            matrix.swap_rows(pivot_row, rank);
+            for row in rank + 1..self.num_rows {
+                let ratio = matrix[[row, column]].clone() / matrix[[rank, column]].clone();
+                for other_column in column..self.num_columns {
+                    let subtrahend = ratio.clone() * matrix[[rank, other_column]].clone();
+                    matrix[[row, other_column]] = matrix[[row, other_column]].clone() - subtrahend;
+                }
+            }
+            rank += 1;
+        }
+        rank
+    }
+}
+
+fn max_abs_row<F: Field>(matrix: &DMatrix<F>, first_row: usize, column: usize) -> usize {
+    (first_row..matrix.num_rows)
+        .max_by(|&lhs, &rhs| matrix[[lhs, column]].cmp_magnitude(&matrix[[rhs, column]]))
+        .expect("first row should be within matrix")
+}
+
+fn dot<T: Clone + Zero + Add<Output = T> + Mul<Output = T>>(lhs: &[T], rhs: &[T]) -> T {
+    lhs.iter()
+        .zip(rhs)
+        .fold(T::zero(), |sum, (lhs, rhs)| sum + lhs.clone() * rhs.clone())
+}
+
+impl<T> Index<[usize; 2]> for DMatrix<T> {
+    type Output = T;
+
+    fn index(&self, [row, column]: [usize; 2]) -> &T {
+        assert!(column < self.num_columns, "column should be within matrix");
+        &self.elements[row * self.num_columns + column]
+    }
+}
+
+impl<T> IndexMut<[usize; 2]> for DMatrix<T> {
+    fn index_mut(&mut self, [row, column]: [usize; 2]) -> &mut T {
+        assert!(column < self.num_columns, "column should be within matrix");
+        &mut self.elements[row * self.num_columns + column]
+    }
+}
+
+impl<T: Clone + Zero + Add<Output = T> + Mul<Output = T>> Mul for &DMatrix<T> {
+    type Output = DMatrix<T>;
+
+    /// # Panics
+    ///
+    /// Panics if the number of columns of `self` differs from the number of rows of `rhs`.
+    fn mul(self, rhs: Self) -> DMatrix<T> {
+        assert_eq!(
+            self.num_columns, rhs.num_rows,
+            "number of columns of lhs should equal number of rows of rhs"
+        );
+        let rhs = rhs.transpose();
+        let elements = self
+            .rows()
+            .flat_map(|row| rhs.rows().map(move |column| dot(row, column)))
+            .collect_vec();
+        DMatrix::from_elements(elements, rhs.num_rows)
+    }
+}
+
+impl<T, const NUM_ROWS: usize, const NUM_COLUMNS: usize> From<Matrix<T, NUM_ROWS, NUM_COLUMNS>>
+    for DMatrix<T>
+{
+    fn from(matrix: Matrix<T, NUM_ROWS, NUM_COLUMNS>) -> Self {
+        Self::from_rows(matrix)
+    }
+}
+
+/// The factors `L` and `U` of `P . A == L . U`, where `P` permutes the rows of `A`, `L` is lower
+/// triangular with ones on its diagonal, and `U` is upper triangular.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LuDecomposition<F> {
+    /// `L` below the diagonal and `U` on and above it.
+    factors: DMatrix<F>,
+    permutation: Vec<usize>,
+    is_odd_permutation: bool,
+}
+
+impl<F: Field> LuDecomposition<F> {
+    #[must_use]
+    pub fn lower(&self) -> DMatrix<F> {
+        let mut lower = self.factors.clone();
+        for [row, column] in self.positions() {
+            lower[[row, column]] = match row.cmp(&column) {
+                Ordering::Less => F::zero(),
+                Ordering::Equal => F::one(),
+                Ordering::Greater => continue,
+            };
+        }
+        lower
+    }
+
+    #[must_use]
+    pub fn upper(&self) -> DMatrix<F> {
+        let mut upper = self.factors.clone();
+        for [row, column] in self.positions() {
+            if row > column {
+                upper[[row, column]] = F::zero();
+            }
+        }
+        upper
+    }
+
+    /// The row of `A` that ends up at each row of `P . A`.
+    #[must_use]
+    pub fn permutation(&self) -> &[usize] {
+        &self.permutation
+    }
+
+    #[must_use]
+    pub fn determinant(&self) -> F {
+        let product = (0..self.permutation.len()).fold(F::one(), |product, index| {
+            product * self.factors[[index, index]].clone()
+        });
+        if self.is_odd_permutation {
+            -product
+        } else {
+            product
+        }
+    }
+
+    /// Solves `A . x == rhs` by forward and back substitution.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `rhs` differs from the size of `A`.
+    #[must_use]
+    pub fn solve(&self, rhs: &[F]) -> Vec<F> {
+        let size = self.permutation.len();
+        assert_eq!(rhs.len(), size, "right-hand side should match matrix size");
+        let mut solution: Vec<F> = Vec::with_capacity(size);
+        for row in 0..size {
+            let sum = dot(&self.factors.row(row)[..row], &solution);
+            solution.push(rhs[self.permutation[row]].clone() - sum);
+        }
+        for row in (0..size).rev() {
+            let sum = dot(&self.factors.row(row)[row + 1..], &solution[row + 1..]);
+            solution[row] = (solution[row].clone() - sum) / self.factors[[row, row]].clone();
+        }
+        solution
+    }
+
+    fn positions(&self) -> impl Iterator<Item = [usize; 2]> + use<F> {
+        let size = self.permutation.len();
+        (0..size)
+            .cartesian_product(0..size)
+            .map(|(row, column)| [row, column])
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use infrastructure::test;
+
+    use crate::{floating_point::ApproxEq, rational::Rational};
+
     use super::*;
 
+    type Q = Rational<i64>;
+
+    fn rational_matrix<const NUM_ROWS: usize, const NUM_COLUMNS: usize>(
+        matrix: Matrix<i64, NUM_ROWS, NUM_COLUMNS>,
+    ) -> DMatrix<Q> {
+        DMatrix::from(matrix.map(|row| row.map(Q::from)))
+    }
+
     #[test]
     fn vector_mul() {
         let matrix = [[1, -1, 2], [0, -3, 1]];
@@ -132,4 +557,119 @@ mod tests {
         let expected = [-2, 1, 3];
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn dynamic_matrix_from_and_into_array() {
+        let array = [[1, 2, 3], [4, 5, 6]];
+        let matrix = DMatrix::from(array);
+        assert_eq!([matrix.num_rows(), matrix.num_columns()], [2, 3]);
+        assert_eq!(matrix[[1, 0]], 4);
+        assert_eq!(matrix.row(1), [4, 5, 6]);
+        assert_eq!(matrix.clone().into_array::<3, 2>(), None);
+        assert_eq!(matrix.into_array(), Some(array));
+    }
+
+    #[test]
+    #[should_panic(expected = "rows should have equal lengths")]
+    fn dynamic_matrix_with_ragged_rows() {
+        let _ = DMatrix::from_rows([vec![1, 2], vec![3]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "rows should have equal lengths")]
+    fn dynamic_matrix_with_ragged_rows_of_divisible_total_length() {
+        let _ = DMatrix::from_rows([vec![1, 2, 3], vec![4]]);
+    }
+
+    #[test]
+    fn transpose() {
+        let matrix = DMatrix::from([[1, 2, 3], [4, 5, 6]]);
+        let expected = DMatrix::from([[1, 4], [2, 5], [3, 6]]);
+        assert_eq!(matrix.transpose(), expected);
+    }
+
+    #[test]
+    fn dynamic_matrix_mul() {
+        let lhs = [[1, 2, 2], [2, 3, 4]];
+        let rhs = [[2, 5], [6, 1], [4, 3]];
+        let actual = &DMatrix::from(lhs) * &DMatrix::from(rhs);
+        assert_eq!(actual.into_array(), Some(super::matrix_mul(lhs, rhs)));
+        let vector = DMatrix::from([[1, -1, 2], [0, -3, 1]]).vector_mul(&[2, 1, 0]);
+        assert_eq!(vector, [1, -3]);
+    }
+
+    #[test]
+    fn pow() {
+        let fibonacci = DMatrix::from([[1_u64, 1], [1, 0]]);
+        assert_eq!(fibonacci.pow(0), DMatrix::identity(2));
+        assert_eq!(fibonacci.pow(1), fibonacci);
+        assert_eq!(fibonacci.pow(90)[[0, 1]], 2_880_067_194_370_816_120);
+    }
+
+    #[test]
+    fn determinant() {
+        let matrix = rational_matrix([[2, -3, 1], [2, 0, -1], [1, 4, 5]]);
+        assert_eq!(matrix.determinant(), Q::from(49));
+        let swapped = rational_matrix([[0, 1], [1, 0]]);
+        assert_eq!(swapped.determinant(), Q::from(-1));
+        let singular = rational_matrix([[1, 2], [2, 4]]);
+        assert_eq!(singular.determinant(), Q::zero());
+        let floating_point = DMatrix::from([[4.0, 3.0], [6.0, 3.0]]);
+        assert!(
+            floating_point.determinant().approx_eq(-6.0),
+            "determinant should be -6"
+        );
+    }
+
+    #[test]
+    fn inverse() {
+        let matrix = rational_matrix([[4, 7], [2, 6]]);
+        let inverse = matrix.inverse().expect("matrix should be invertible");
+        let expected = DMatrix::from([
+            [Q::new(3, 5), Q::new(-7, 10)],
+            [Q::new(-1, 5), Q::new(2, 5)],
+        ]);
+        assert_eq!(inverse, expected);
+        assert_eq!(&matrix * &inverse, DMatrix::identity(2));
+        assert_eq!(rational_matrix([[1, 2], [2, 4]]).inverse(), None);
+    }
+
+    #[test]
+    fn rank() {
+        let function = |matrix: DMatrix<Q>| matrix.rank();
+        let cases = [
+            (rational_matrix([[1, 2, 3], [2, 4, 6]]), 1),
+            (rational_matrix([[1, 2, 3], [0, 1, 1], [1, 3, 4]]), 2),
+            (rational_matrix([[0, 0], [0, 0]]), 0),
+            (rational_matrix([[0, 1], [1, 0], [1, 1]]), 2),
+            (rational_matrix([[2, 1, 0], [0, 1, 1], [1, 0, 1]]), 3),
+        ];
+        test::cases(function, cases);
+    }
+
+    #[test]
+    fn lu_decomposition() {
+        let matrix = rational_matrix([[1, 2, 0], [3, 1, 4], [2, 0, 1]]);
+        let decomposition = matrix
+            .lu_decomposition()
+            .expect("matrix should be invertible");
+        let permuted = DMatrix::from_rows(
+            decomposition
+                .permutation()
+                .iter()
+                .map(|&row| matrix.row(row).to_vec()),
+        );
+        assert_eq!(&decomposition.lower() * &decomposition.upper(), permuted);
+        assert_eq!(decomposition.determinant(), matrix.determinant());
+    }
+
+    #[test]
+    fn solve() {
+        let matrix = rational_matrix([[2, 1, -1], [-3, -1, 2], [-2, 1, 2]]);
+        let rhs = [8, -11, -3].map(Q::from);
+        let expected = [2, 3, -1].map(Q::from);
+        assert_eq!(matrix.solve(&rhs), Some(expected.to_vec()));
+        let singular = rational_matrix([[1, 1], [1, 1]]);
+        assert_eq!(singular.solve(&[Q::one(), Q::one()]), None);
+    }
 }
@@ -0,0 +1,252 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+use ahash::AHashMap;
+use easy_cast::Cast;
+
+use crate::number_theory::extended_euclid;
+
+/// An integer modulo `modulus`, where every operation reduces its result. Products are computed
+/// with 128-bit intermediates so that any modulus up to `u64::MAX` works.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ModInt {
+    value: u64,
+    modulus: u64,
+}
+
+impl ModInt {
+    /// # Panics
+    ///
+    /// Panics if `modulus` is zero.
+    #[must_use]
+    pub fn new(value: u64, modulus: u64) -> Self {
+        assert!(modulus > 0, "modulus should be positive");
+        Self {
+            value: value % modulus,
+            modulus,
+        }
+    }
+
+    /// The residue class of a possibly negative `value`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `modulus` is zero.
+    #[must_use]
+    pub fn from_signed(value: i128, modulus: u64) -> Self {
+        assert!(modulus > 0, "modulus should be positive");
+        Self {
+            value: value.rem_euclid(modulus.into()).cast(),
+            modulus,
+        }
+    }
+
+    /// The representative in `0..modulus`.
+    #[must_use]
+    pub fn value(self) -> u64 {
+        self.value
+    }
+
+    #[must_use]
+    pub fn modulus(self) -> u64 {
+        self.modulus
+    }
+
+    /// Raises the number to the power of `exponent` by repeated squaring.
+    #[must_use]
+    pub fn pow(self, mut exponent: u64) -> Self {
+        let mut power = Self::new(1, self.modulus);
+        let mut base = self;
+        while exponent > 0 {
+            if exponent % 2 == 1 {
+                power = power * base;
+            }
+            base = base * base;
+            exponent /= 2;
+        }
+        power
+    }
+
+    /// The multiplicative inverse, or `None` if the number and the modulus are not coprime.
+    #[must_use]
+    pub fn inverse(self) -> Option<Self> {
+        let (divisor, [coefficient, _]) = extended_euclid(self.value.into(), self.modulus.into());
+        (divisor == 1).then(|| Self::from_signed(coefficient, self.modulus))
+    }
+
+    fn assert_same_modulus(self, rhs: Self) {
+        assert_eq!(
+            self.modulus, rhs.modulus,
+            "operands should have the same modulus"
+        );
+    }
+}
+
+impl Add for ModInt {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        self.assert_same_modulus(rhs);
+        let sum = u128::from(self.value) + u128::from(rhs.value);
+        Self {
+            value: (sum % u128::from(self.modulus)).cast(),
+            modulus: self.modulus,
+        }
+    }
+}
+
+impl Sub for ModInt {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self + -rhs
+    }
+}
+
+impl Mul for ModInt {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        self.assert_same_modulus(rhs);
+        let product = u128::from(self.value) * u128::from(rhs.value);
+        Self {
+            value: (product % u128::from(self.modulus)).cast(),
+            modulus: self.modulus,
+        }
+    }
+}
+
+impl Div for ModInt {
+    type Output = Self;
+
+    /// # Panics
+    ///
+    /// Panics if `rhs` is not invertible.
+    #[expect(
+        clippy::suspicious_arithmetic_impl,
+        reason = "division is multiplication by the inverse"
+    )]
+    fn div(self, rhs: Self) -> Self {
+        self * rhs
+            .inverse()
+            .expect("divisor should be coprime to the modulus")
+    }
+}
+
+impl Neg for ModInt {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            value: (self.modulus - self.value) % self.modulus,
+            modulus: self.modulus,
+        }
+    }
+}
+
+impl Display for ModInt {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (mod {})", self.value, self.modulus)
+    }
+}
+
+/// The smallest exponent `e` such that `base.pow(e) == target`, found with the baby-step
+/// giant-step algorithm in time proportional to the square root of the modulus. Returns `None` if
+/// there is no such exponent or if `base` is not invertible.
+///
+/// # Panics
+///
+/// Panics if `base` and `target` have different moduli.
+#[must_use]
+pub fn discrete_logarithm(base: ModInt, target: ModInt) -> Option<u64> {
+    base.assert_same_modulus(target);
+    let step = base.modulus.isqrt() + 1;
+    let mut baby_steps = AHashMap::new();
+    let mut power = ModInt::new(1, base.modulus);
+    for exponent in 0..step {
+        baby_steps.entry(power.value).or_insert(exponent);
+        power = power * base;
+    }
+    let giant_step = base.inverse()?.pow(step);
+    let mut remainder = target;
+    for giant_steps in 0..step {
+        if let Some(&exponent) = baby_steps.get(&remainder.value) {
+            return Some(giant_steps * step + exponent);
+        }
+        remainder = remainder * giant_step;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use infrastructure::test;
+
+    use super::*;
+
+    const PRIME: u64 = 1_000_000_007;
+
+    fn m(value: u64) -> ModInt {
+        ModInt::new(value, PRIME)
+    }
+
+    #[test]
+    fn arithmetic() {
+        assert_eq!(m(PRIME - 1) + m(5), m(4));
+        assert_eq!(m(3) - m(5), m(PRIME - 2));
+        assert_eq!(-m(0), m(0));
+        assert_eq!(m(PRIME - 1) * m(PRIME - 1), m(1));
+        assert_eq!(m(6) / m(3), m(2));
+        assert_eq!(ModInt::from_signed(-3, 7), ModInt::new(4, 7));
+        let large = ModInt::new(u64::MAX - 1, u64::MAX);
+        assert_eq!(large * large, ModInt::new(1, u64::MAX));
+    }
+
+    #[test]
+    fn pow() {
+        let function = |(base, exponent)| ModInt::new(base, 13).pow(exponent).value();
+        let cases = [((2, 0), 1), ((2, 10), 1024 % 13), ((0, 0), 1), ((5, 12), 1)];
+        test::cases(function, cases);
+        assert_eq!(m(2).pow(PRIME - 1), m(1));
+    }
+
+    #[test]
+    fn inverse() {
+        let function = |(value, modulus)| ModInt::new(value, modulus).inverse();
+        let cases = [
+            ((3, 7), Some(ModInt::new(5, 7))),
+            ((4, 8), None),
+            ((0, 5), None),
+            ((17, 3120), Some(ModInt::new(2753, 3120))),
+        ];
+        test::cases(function, cases);
+    }
+
+    #[test]
+    #[should_panic(expected = "operands should have the same modulus")]
+    fn different_moduli() {
+        let _ = ModInt::new(1, 5) + ModInt::new(1, 7);
+    }
+
+    #[test]
+    fn discrete_logarithm() {
+        let function = |(base, target, modulus)| {
+            super::discrete_logarithm(ModInt::new(base, modulus), ModInt::new(target, modulus))
+        };
+        let cases = [
+            ((7, 5_764_801, 20_201_227), Some(8)),
+            ((7, 17_807_724, 20_201_227), Some(11)),
+            ((3, 1, 7), Some(0)),
+            ((2, 3, 7), None),
+            ((2, 1, 8), None),
+        ];
+        test::cases(function, cases);
+        let exponent = 123_456_789;
+        let target = m(5).pow(exponent);
+        let logarithm = super::discrete_logarithm(m(5), target).expect("logarithm should exist");
+        assert_eq!(m(5).pow(logarithm), target);
+        assert!(logarithm <= exponent, "logarithm should be the smallest");
+    }
+}
@@ -1,68 +1,59 @@
-use easy_cast::Cast;
-use itertools::Itertools;
-
+use easy_cast::Conv;
+
+/// The smallest non-negative `x` such that `x.rem_euclid(modulus) == remainder.rem_euclid(modulus)`
+/// for every `(remainder, modulus)` congruence, or `None` if the congruences contradict each other
+/// or `x` does not fit in an `isize`. The moduli need not be coprime; all solutions are congruent
+/// modulo their least common multiple.
+///
+/// # Panics
+///
+/// Panics if any modulus is not positive.
 #[must_use]
 pub fn chinese_remainder_theorem(
-    congruences: impl Iterator<Item = (isize, isize)> + Clone,
-) -> isize {
-    debug_assert_chinese_remainder_theorem_properties(congruences.clone());
-
-    let n: isize = congruences.clone().map(|(_, modulo)| modulo).product();
-    let ms = congruences.clone().map(|(_, modulo)| n / modulo);
-    congruences
-        .zip_eq(ms)
-        .map(|((remainder, modulo), m)| {
-            let [_, v] = bezouts_identity(modulo, m);
-            remainder * v * m
-        })
-        .sum::<isize>()
-        .rem_euclid(n)
-}
-
-fn debug_assert_chinese_remainder_theorem_properties(
-    congruences: impl Iterator<Item = (isize, isize)> + Clone,
-) {
-    let modulos = congruences.map(|(_, modulo)| modulo);
-
-    let are_modulos_greater_than_one = modulos.clone().all(|modulo| modulo > 1);
-    debug_assert!(
-        are_modulos_greater_than_one,
-        "modulos must be greater than one"
-    );
-
-    let are_modulos_pairwise_coprime = modulos
-        .combinations(2)
-        .all(|pair| greatest_common_divisor(pair[0].cast(), pair[1].cast()) == 1);
-    debug_assert!(
-        are_modulos_pairwise_coprime,
-        "modulos must be pairwise coprime"
-    );
+    congruences: impl IntoIterator<Item = (isize, isize)>,
+) -> Option<isize> {
+    let (remainder, _) = congruences.into_iter().try_fold(
+        (0, 1),
+        |(remainder, modulus): (i128, i128), (other_remainder, other_modulus)| {
+            assert!(other_modulus > 0, "moduli should be positive");
+            let [other_remainder, other_modulus] = [other_remainder, other_modulus].map(i128::conv);
+            let (divisor, [coefficient, _]) = extended_euclid(modulus, other_modulus);
+            let difference = other_remainder - remainder;
+            if difference % divisor != 0 {
+                return None;
+            }
+            let step = other_modulus / divisor;
+            let multiple =
+                ((difference / divisor).rem_euclid(step) * coefficient.rem_euclid(step)) % step;
+            let combined_modulus = modulus.checked_mul(step)?;
+            let remainder = (remainder + modulus * multiple).rem_euclid(combined_modulus);
+            Some((remainder, combined_modulus))
+        },
+    )?;
+    isize::try_from(remainder).ok()
 }
 
-fn bezouts_identity(mut a: isize, mut b: isize) -> [isize; 2] {
-    let mut swapped = false;
-    if a < b {
-        [a, b] = [b, a];
-        swapped = true;
-    }
-
-    let mut quotients = vec![];
-    while b != 0 {
-        quotients.push(a / b);
-        [a, b] = [b, a % b];
+/// Returns the greatest common divisor of `a` and `b` and coefficients `[x, y]` such that
+/// `a * x + b * y` equals it.
+#[must_use]
+pub fn extended_euclid(a: i128, b: i128) -> (i128, [i128; 2]) {
+    let [mut previous, mut current] = [(a, [1, 0]), (b, [0, 1])];
+    while current.0 != 0 {
+        let quotient = previous.0 / current.0;
+        let next = (
+            previous.0 - quotient * current.0,
+            [
+                previous.1[0] - quotient * current.1[0],
+                previous.1[1] - quotient * current.1[1],
+            ],
+        );
+        [previous, current] = [current, next];
     }
-
-    let mut factors = quotients
-        .into_iter()
-        .rev()
-        .fold([1, 0], |[smaller, greater], quotient| {
-            [greater, smaller + greater * -quotient]
-        });
-
-    if swapped {
-        factors.reverse();
+    if previous.0 < 0 {
+        (-previous.0, previous.1.map(|coefficient| -coefficient))
+    } else {
+        previous
     }
-    factors
 }
 
 #[must_use]
@@ -94,46 +85,50 @@ pub fn number_of_decimal_digits(mut number: isize) -> u32 {
 
 #[cfg(test)]
 mod tests {
-    use easy_cast::Cast;
     use infrastructure::test;
+    use proptest::{collection::vec, prelude::*};
 
     #[test]
     fn chinese_remainder_theorem() {
         let function =
             |congruences: &[_]| super::chinese_remainder_theorem(congruences.iter().copied());
         let cases = [
-            (&[(8, 15)][..], 8),
-            (&[(8 + 15, 15)][..], 8),
-            (&[(1, 3), (2, 4)][..], 10),
-            (&[(1, 3), (2 - 4, 4)][..], 10),
-            (&[(4, 17), (1, 15), (14, 28)][..], 4186),
-            (&[(4 + 17, 17), (1 - 15, 15), (14 - 2 * 28, 28)][..], 4186),
-            (&[(201, 289), (54, 143), (9, 18), (7, 53)][..], 15_566_319),
+            (&[(8, 15)][..], Some(8)),
+            (&[(8 + 15, 15)][..], Some(8)),
+            (&[(1, 3), (2, 4)][..], Some(10)),
+            (&[(1, 3), (2 - 4, 4)][..], Some(10)),
+            (&[(4, 17), (1, 15), (14, 28)][..], Some(4186)),
+            (
+                &[(4 + 17, 17), (1 - 15, 15), (14 - 2 * 28, 28)][..],
+                Some(4186),
+            ),
+            (
+                &[(201, 289), (54, 143), (9, 18), (7, 53)][..],
+                Some(15_566_319),
+            ),
+            (&[][..], Some(0)),
+            (&[(5, 1)][..], Some(0)),
+            (&[(2, 4), (4, 6)][..], Some(10)),
+            (&[(3, 6), (1, 4), (7, 10)][..], Some(57)),
+            (&[(1, 4), (2, 6)][..], None),
+            (&[(1, isize::MAX), (0, isize::MAX - 1)][..], None),
         ];
         test::cases(function, cases);
     }
 
     #[test]
-    fn bezouts_identity() {
-        let cases = [
-            [9, 0],
-            [0, 9],
-            [9, 9],
-            [9, 3],
-            [3, 9],
-            [20, 3],
-            [3, 20],
-            [15, 4],
-            [12, 5],
-            [1785, 546],
-            [546, 1785],
-        ];
+    fn extended_euclid() {
+        let cases = [[240, 46], [46, 240], [9, 0], [0, 9], [-12, 18], [1785, 546]];
         for case @ [a, b] in cases {
-            let [k, l] = super::bezouts_identity(a, b);
+            let (divisor, [x, y]) = super::extended_euclid(a, b);
             assert_eq!(
-                a * k + b * l,
-                super::greatest_common_divisor(a.cast(), b.cast()).cast(),
-                "answer to case '{case:?}' should result in the greatest common divisor"
+                a * x + b * y,
+                divisor,
+                "case '{case:?}' should satisfy identity"
+            );
+            assert!(
+                divisor >= 0 && (divisor == 0 || (a % divisor == 0 && b % divisor == 0)),
+                "case '{case:?}' should yield a common divisor"
             );
         }
     }
@@ -182,4 +177,32 @@ mod tests {
         ];
         test::cases(super::number_of_decimal_digits, cases);
     }
+
+    proptest! {
+        #[test]
+        fn chinese_remainder_theorem_satisfies_every_congruence(
+            congruences in vec((-1000..1000_isize, 1..60_isize), 0..5),
+        ) {
+            if let Some(solution) = super::chinese_remainder_theorem(congruences.iter().copied()) {
+                prop_assert!(solution >= 0, "solution should not be negative");
+                for (remainder, modulus) in congruences {
+                    prop_assert_eq!(solution.rem_euclid(modulus), remainder.rem_euclid(modulus));
+                }
+            }
+        }
+
+        #[test]
+        fn chinese_remainder_theorem_solves_consistent_congruences(
+            value in 0..1_000_000_isize,
+            moduli in vec(1..60_isize, 0..5),
+        ) {
+            let congruences = moduli.iter().map(|&modulus| (value % modulus, modulus));
+            let solution = super::chinese_remainder_theorem(congruences);
+            prop_assert!(
+                solution.is_some_and(|solution| solution <= value
+                    && moduli.iter().all(|&modulus| solution % modulus == value % modulus)),
+                "{solution:?} should be the smallest solution",
+            );
+        }
+    }
 }
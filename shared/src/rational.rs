@@ -0,0 +1,261 @@
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display, Formatter},
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+use num_integer::Integer;
+use num_traits::{One, Signed, Zero};
+
+/// An exact fraction of two integers kept in lowest terms with a positive denominator, such that
+/// equal numbers have equal representations. Use a big integer such as `num_bigint::BigInt` if
+/// intermediate results might overflow a primitive integer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational<T> {
+    numerator: T,
+    denominator: T,
+}
+
+impl<T: Clone + Integer + Signed> Rational<T> {
+    /// # Panics
+    ///
+    /// Panics if `denominator` is zero.
+    #[must_use]
+    pub fn new(numerator: T, denominator: T) -> Self {
+        assert!(!denominator.is_zero(), "denominator should not be zero");
+        let divisor = numerator.gcd(&denominator) * denominator.signum();
+        Self {
+            numerator: numerator / divisor.clone(),
+            denominator: denominator / divisor,
+        }
+    }
+
+    #[must_use]
+    pub fn numerator(&self) -> &T {
+        &self.numerator
+    }
+
+    #[must_use]
+    pub fn denominator(&self) -> &T {
+        &self.denominator
+    }
+
+    #[must_use]
+    pub fn is_integer(&self) -> bool {
+        self.denominator.is_one()
+    }
+
+    /// The number as an integer, or `None` if it is not an integer.
+    #[must_use]
+    pub fn to_integer(&self) -> Option<T> {
+        self.is_integer().then(|| self.numerator.clone())
+    }
+
+    #[must_use]
+    pub fn abs(&self) -> Self {
+        Self {
+            numerator: self.numerator.abs(),
+            denominator: self.denominator.clone(),
+        }
+    }
+}
+
+impl<T: Clone + Integer + Signed> From<T> for Rational<T> {
+    fn from(integer: T) -> Self {
+        Self {
+            numerator: integer,
+            denominator: T::one(),
+        }
+    }
+}
+
+impl<T: Clone + Integer + Signed> Add for Rational<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        let divisor = self.denominator.gcd(&rhs.denominator);
+        let lhs_factor = rhs.denominator / divisor.clone();
+        let rhs_factor = self.denominator.clone() / divisor;
+        Self::new(
+            self.numerator * lhs_factor.clone() + rhs.numerator * rhs_factor,
+            self.denominator * lhs_factor,
+        )
+    }
+}
+
+impl<T: Clone + Integer + Signed> Sub for Rational<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self + -rhs
+    }
+}
+
+impl<T: Clone + Integer + Signed> Mul for Rational<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        let lhs_divisor = self.numerator.gcd(&rhs.denominator);
+        let rhs_divisor = rhs.numerator.gcd(&self.denominator);
+        Self::new(
+            (self.numerator / lhs_divisor.clone()) * (rhs.numerator / rhs_divisor.clone()),
+            (self.denominator / rhs_divisor) * (rhs.denominator / lhs_divisor),
+        )
+    }
+}
+
+impl<T: Clone + Integer + Signed> Div for Rational<T> {
+    type Output = Self;
+
+    /// # Panics
+    ///
+    /// Panics if `rhs` is zero.
+    fn div(self, rhs: Self) -> Self {
+        assert!(!rhs.is_zero(), "divisor should not be zero");
+        self * Self::new(rhs.denominator, rhs.numerator)
+    }
+}
+
+impl<T: Clone + Integer + Signed> Neg for Rational<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            numerator: -self.numerator,
+            denominator: self.denominator,
+        }
+    }
+}
+
+impl<T: Clone + Integer + Signed> Zero for Rational<T> {
+    fn zero() -> Self {
+        Self::from(T::zero())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.numerator.is_zero()
+    }
+}
+
+impl<T: Clone + Integer + Signed> One for Rational<T> {
+    fn one() -> Self {
+        Self::from(T::one())
+    }
+}
+
+impl<T: Clone + Integer + Signed> PartialOrd for Rational<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Clone + Integer + Signed> Ord for Rational<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let lhs = self.numerator.clone() * other.denominator.clone();
+        let rhs = other.numerator.clone() * self.denominator.clone();
+        lhs.cmp(&rhs)
+    }
+}
+
+impl<T: Display + One + PartialEq> Display for Rational<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.denominator.is_one() {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use infrastructure::test;
+    use num_bigint::BigInt;
+
+    use super::*;
+
+    type Q = Rational<i64>;
+
+    fn q(numerator: i64, denominator: i64) -> Q {
+        Q::new(numerator, denominator)
+    }
+
+    #[test]
+    fn lowest_terms() {
+        let function = |(numerator, denominator)| {
+            let rational = q(numerator, denominator);
+            [*rational.numerator(), *rational.denominator()]
+        };
+        let cases = [
+            ((6, 4), [3, 2]),
+            ((-6, 4), [-3, 2]),
+            ((6, -4), [-3, 2]),
+            ((-6, -4), [3, 2]),
+            ((0, -5), [0, 1]),
+            ((7, 1), [7, 1]),
+        ];
+        test::cases(function, cases);
+    }
+
+    #[test]
+    #[should_panic(expected = "denominator should not be zero")]
+    fn zero_denominator() {
+        let _ = q(1, 0);
+    }
+
+    #[test]
+    fn arithmetic() {
+        assert_eq!(q(1, 2) + q(1, 3), q(5, 6));
+        assert_eq!(q(1, 6) + q(1, 3), q(1, 2));
+        assert_eq!(q(1, 2) - q(3, 4), q(-1, 4));
+        assert_eq!(q(2, 3) * q(9, 4), q(3, 2));
+        assert_eq!(q(2, 3) * Q::zero(), Q::zero());
+        assert_eq!(q(2, 3) / q(-4, 9), q(-3, 2));
+        assert_eq!(-q(2, 3), q(-2, 3));
+        assert_eq!(q(-2, 3).abs(), q(2, 3));
+    }
+
+    #[test]
+    #[should_panic(expected = "divisor should not be zero")]
+    fn division_by_zero() {
+        let _ = q(1, 2) / Q::zero();
+    }
+
+    #[test]
+    fn exact_where_floating_point_is_not() {
+        let tenth = q(1, 10);
+        let sum = (0..10).fold(Q::zero(), |sum, _| sum + tenth);
+        assert_eq!(sum, Q::one());
+        let large = Rational::<i128>::from(10_i128.pow(17));
+        assert_eq!(large + Rational::one() - large, Rational::one());
+    }
+
+    #[test]
+    fn big_integers() {
+        let large = Rational::from(BigInt::from(10).pow(40));
+        let third = Rational::new(BigInt::from(1), BigInt::from(3));
+        let product = large.clone() * large.clone() * third.clone();
+        assert_eq!(product.clone() / large.clone() / large, third);
+        assert!(!product.is_integer(), "product should not be an integer");
+    }
+
+    #[test]
+    fn ordering() {
+        assert!(q(1, 3) < q(1, 2), "1/3 should be less than 1/2");
+        assert!(q(-1, 2) < q(-1, 3), "-1/2 should be less than -1/3");
+        assert_eq!(q(2, 4).cmp(&q(1, 2)), Ordering::Equal);
+    }
+
+    #[test]
+    fn integers() {
+        let function = |rational: Q| rational.to_integer();
+        let cases = [(q(4, 2), Some(2)), (q(-9, 3), Some(-3)), (q(1, 2), None)];
+        test::cases(function, cases);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(q(-6, 4).to_string(), "-3/2");
+        assert_eq!(q(8, 4).to_string(), "2");
+    }
+}
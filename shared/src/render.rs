@@ -0,0 +1,184 @@
+use std::{fs::File, io::BufWriter, path::Path, time::Duration};
+
+use easy_cast::Cast;
+use image::{
+    Delay, DynamicImage, Frame, ImageResult, RgbImage,
+    codecs::gif::{GifEncoder, Repeat},
+    imageops::{self, FilterType},
+};
+
+use crate::{
+    grid::{Grid, Position, Rectangle},
+    vector::Vector,
+};
+
+pub type Image = RgbImage;
+pub type Color = [u8; 3];
+
+pub const BLACK: Color = [0, 0, 0];
+pub const WHITE: Color = [u8::MAX, u8::MAX, u8::MAX];
+
+/// An image with one pixel per element of `grid` colored by `palette`. Rows of the grid become
+/// rows of pixels.
+#[must_use]
+pub fn grid<T>(grid: &Grid<T>, mut palette: impl FnMut(&T) -> Color) -> Image {
+    let mut image = Image::new(grid.width().cast(), grid.height().cast());
+    for ([row, column], element) in grid.iter_row_major() {
+        image.put_pixel(column.cast(), row.cast(), palette(element).into());
+    }
+    image
+}
+
+/// An image of the positions within `bounds`, where `cells` are colored by `palette` and every
+/// other position has the `background` color. Cells outside `bounds` are left out.
+pub fn sparse<T>(
+    cells: impl IntoIterator<Item = (Position, T)>,
+    bounds: Rectangle,
+    background: Color,
+    mut palette: impl FnMut(T) -> Color,
+) -> Image {
+    let [height, width] = bounds.dimensions;
+    let mut image = Image::from_pixel(width.cast(), height.cast(), background.into());
+    for (position, cell) in cells {
+        if bounds.contains(position) {
+            let [row, column] = position.sub(bounds.top_left);
+            image.put_pixel(column.cast(), row.cast(), palette(cell).into());
+        }
+    }
+    image
+}
+
+/// An image of the positions within `bounds` with `positions` in the `foreground` color and
+/// every other position in the `background` color.
+pub fn positions(
+    positions: impl IntoIterator<Item = Position>,
+    bounds: Rectangle,
+    [background, foreground]: [Color; 2],
+) -> Image {
+    let cells = positions.into_iter().map(|position| (position, ()));
+    sparse(cells, bounds, background, |()| foreground)
+}
+
+/// The image enlarged such that every pixel becomes a square of `factor` times `factor` pixels.
+#[must_use]
+pub fn scaled(image: &Image, factor: u32) -> Image {
+    let [width, height] = [image.width(), image.height()].map(|dimension| dimension * factor);
+    imageops::resize(image, width, height, FilterType::Nearest)
+}
+
+/// Saves the image in the format given by the extension of `path`, for example PNG.
+///
+/// # Errors
+///
+/// Errors if the file cannot be written or the format is unsupported.
+pub fn save(image: &Image, path: impl AsRef<Path>) -> ImageResult<()> {
+    image.save(path)
+}
+
+/// Saves `frames` as a looping animated GIF showing every frame for `frame_duration`.
+///
+/// # Errors
+///
+/// Errors if the file cannot be written or a frame cannot be encoded.
+pub fn save_animation(
+    frames: impl IntoIterator<Item = Image>,
+    frame_duration: Duration,
+    path: impl AsRef<Path>,
+) -> ImageResult<()> {
+    let file = BufWriter::new(File::create(path)?);
+    let mut encoder = GifEncoder::new_with_speed(file, 10);
+    encoder.set_repeat(Repeat::Infinite)?;
+    let delay = Delay::from_saturating_duration(frame_duration);
+    let frames = frames.into_iter().map(|frame| {
+        let frame = DynamicImage::from(frame).into_rgba8();
+        Frame::from_parts(frame, 0, 0, delay)
+    });
+    encoder.encode_frames(frames)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::BufReader;
+
+    use image::{AnimationDecoder, ImageReader, codecs::gif::GifDecoder};
+
+    use super::*;
+
+    const RED: Color = [u8::MAX, 0, 0];
+
+    #[test]
+    fn grid() {
+        let grid = Grid::<char>::from("#.\n.#\n#.\n");
+        let actual = super::grid(&grid, |&char| if char == '#' { BLACK } else { WHITE });
+        assert_eq!(actual.dimensions(), (2, 3));
+        assert_eq!(actual.get_pixel(0, 0).0, BLACK);
+        assert_eq!(actual.get_pixel(1, 0).0, WHITE);
+        assert_eq!(actual.get_pixel(1, 1).0, BLACK);
+        assert_eq!(actual.get_pixel(0, 2).0, BLACK);
+    }
+
+    #[test]
+    fn sparse() {
+        let bounds = Rectangle {
+            top_left: [-1, 3],
+            dimensions: [2, 4],
+        };
+        let cells = [([-1, 3], 'a'), ([0, 6], 'b'), ([1, 3], 'c')];
+        let actual = super::sparse(cells, bounds, WHITE, |cell| match cell {
+            'a' => RED,
+            _ => BLACK,
+        });
+        assert_eq!(actual.dimensions(), (4, 2));
+        assert_eq!(actual.get_pixel(0, 0).0, RED);
+        assert_eq!(actual.get_pixel(3, 1).0, BLACK);
+        assert_eq!(actual.get_pixel(1, 1).0, WHITE);
+    }
+
+    #[test]
+    fn scaled() {
+        let bounds = Rectangle::bounding([[0, 0], [1, 1]]).expect("positions should not be empty");
+        let image = positions([[0, 1]], bounds, [WHITE, BLACK]);
+        let actual = super::scaled(&image, 3);
+        assert_eq!(actual.dimensions(), (6, 6));
+        assert_eq!(actual.get_pixel(3, 0).0, BLACK);
+        assert_eq!(actual.get_pixel(5, 2).0, BLACK);
+        assert_eq!(actual.get_pixel(2, 2).0, WHITE);
+        assert_eq!(actual.get_pixel(3, 3).0, WHITE);
+    }
+
+    #[test]
+    fn png_round_trip() {
+        let image = super::grid(&Grid::<char>::from("#.\n.#\n"), |&char| {
+            if char == '#' { RED } else { WHITE }
+        });
+        let path = std::env::temp_dir().join("shared-render-png-round-trip.png");
+        save(&image, &path).expect("image should be saved");
+        let loaded = ImageReader::open(&path)
+            .expect("image should be opened")
+            .decode()
+            .expect("image should be decoded")
+            .into_rgb8();
+        assert_eq!(loaded, image);
+    }
+
+    #[test]
+    fn animation_has_every_frame() {
+        let bounds = Rectangle {
+            top_left: [0, 0],
+            dimensions: [4, 4],
+        };
+        let frames = (0..4).map(|index| positions([[index, index]], bounds, [WHITE, BLACK]));
+        let path = std::env::temp_dir().join("shared-render-animation.gif");
+        save_animation(frames, Duration::from_millis(100), &path).expect("animation should save");
+        let file = BufReader::new(File::open(&path).expect("animation should be opened"));
+        let decoded = GifDecoder::new(file)
+            .expect("animation should be a gif")
+            .into_frames()
+            .collect_frames()
+            .expect("frames should be decoded");
+        assert_eq!(decoded.len(), 4);
+        let last = decoded[3].buffer();
+        assert_eq!(last.get_pixel(3, 3).0, [0, 0, 0, u8::MAX]);
+        assert_eq!(last.get_pixel(0, 0).0, [u8::MAX; 4]);
+    }
+}
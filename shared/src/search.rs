@@ -215,8 +215,20 @@ where
 #[cfg(test)]
 mod tests {
     use infrastructure::test;
+    use proptest::prelude::*;
 
     use super::*;
+    use crate::{
+        grid::{self, Grid, Position},
+        strategies,
+    };
+
+    fn maze_and_source() -> impl Strategy<Value = (Grid<bool>, Position)> {
+        strategies::grid_with(prop::bool::weighted(0.3)).prop_flat_map(|walls| {
+            let source = strategies::position_within(walls.bounds());
+            (Just(walls), source)
+        })
+    }
 
     #[test]
     fn assignment() {
@@ -252,4 +264,24 @@ mod tests {
         ];
         test::cases(function, cases);
     }
+
+    proptest! {
+        #[test]
+        fn distances_agree_with_unit_cost_minimum_path_costs((walls, source) in maze_and_source()) {
+            let open_neighbors = |position| {
+                grid::orthogonal_neighbors(position)
+                    .into_iter()
+                    .filter(|&neighbor| walls.get(neighbor) == Some(&false))
+            };
+            let distances = distances(source, open_neighbors);
+            for (target, _) in walls.iter_row_major() {
+                let minimum_path_cost = minimum_path_cost(
+                    source,
+                    |position| open_neighbors(position).map(|neighbor| (neighbor, 1)),
+                    |position| position == target,
+                );
+                prop_assert_eq!(distances.get(&target).copied(), minimum_path_cost);
+            }
+        }
+    }
 }
@@ -0,0 +1,102 @@
+use std::fmt::Debug;
+
+use ahash::AHashMap;
+use easy_cast::Conv;
+use itertools::Itertools;
+use proptest::{
+    arbitrary::{Arbitrary, any_with},
+    collection::vec,
+    prelude::{BoxedStrategy, Just, Strategy},
+};
+
+use crate::grid::{Coordinate, Grid, Position, Rectangle};
+
+pub type Graph = AHashMap<Vertex, Vec<Vertex>>;
+pub type Vertex = u8;
+
+pub const MAX_SIDE: usize = 8;
+pub const MAX_VERTICES: usize = 10;
+
+pub fn coordinate() -> impl Strategy<Value = Coordinate> {
+    -100..=100_isize
+}
+
+pub fn position() -> impl Strategy<Value = Position> {
+    [coordinate(), coordinate()]
+}
+
+pub fn dimensions() -> impl Strategy<Value = [Coordinate; 2]> {
+    let side = 1..=Coordinate::conv(MAX_SIDE);
+    [side.clone(), side]
+}
+
+/// A position within `rectangle`.
+pub fn position_within(rectangle: Rectangle) -> impl Strategy<Value = Position> {
+    let [height, width] = rectangle.dimensions;
+    [0..height, 0..width].prop_map(move |[row, column]| {
+        let [top, left] = rectangle.top_left;
+        [top + row, left + column]
+    })
+}
+
+/// A grid with sides of at most `MAX_SIDE` whose elements are drawn from `element`.
+pub fn grid_with<T: Debug>(
+    element: impl Strategy<Value = T> + Clone,
+) -> impl Strategy<Value = Grid<T>> {
+    (1..=MAX_SIDE, 1..=MAX_SIDE).prop_flat_map(move |(height, width)| {
+        vec(element.clone(), height * width)
+            .prop_map(move |elements| Grid::from_elements(elements, width))
+    })
+}
+
+impl<T: Arbitrary + 'static> Arbitrary for Grid<T>
+where
+    T::Strategy: Clone,
+{
+    type Parameters = T::Parameters;
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(parameters: Self::Parameters) -> Self::Strategy {
+        grid_with(any_with::<T>(parameters)).boxed()
+    }
+}
+
+/// A directed graph on at most `MAX_VERTICES` vertices, possibly with cycles, loops and repeated
+/// edges. Every vertex is a key, even if it has no outgoing edges.
+pub fn graph() -> impl Strategy<Value = Graph> {
+    (1..=MAX_VERTICES).prop_flat_map(|number_of_vertices| {
+        let vertex = 0..Vertex::conv(number_of_vertices);
+        vec((vertex.clone(), vertex), 0..=2 * number_of_vertices)
+            .prop_map(move |edges| adjacency_lists(number_of_vertices, edges))
+    })
+}
+
+/// A directed acyclic graph on at most `MAX_VERTICES` vertices, generated with edges from lower to
+/// higher vertices whose labels are then shuffled.
+pub fn acyclic_graph() -> impl Strategy<Value = Graph> {
+    (1..=MAX_VERTICES).prop_flat_map(|number_of_vertices| {
+        let vertex = 0..Vertex::conv(number_of_vertices);
+        let edges = vec((vertex.clone(), vertex), 0..=2 * number_of_vertices);
+        let labels = Just((0..Vertex::conv(number_of_vertices)).collect_vec()).prop_shuffle();
+        (edges, labels).prop_map(move |(edges, labels)| {
+            let edges = edges
+                .into_iter()
+                .filter(|(from, to)| from < to)
+                .map(|(from, to)| (labels[usize::from(from)], labels[usize::from(to)]));
+            adjacency_lists(number_of_vertices, edges)
+        })
+    })
+}
+
+fn adjacency_lists(
+    number_of_vertices: usize,
+    edges: impl IntoIterator<Item = (Vertex, Vertex)>,
+) -> Graph {
+    let mut graph: Graph = (0..Vertex::conv(number_of_vertices))
+        .map(|vertex| (vertex, vec![]))
+        .collect();
+    for (from, to) in edges {
+        graph.entry(from).or_default().push(to);
+    }
+    graph
+}
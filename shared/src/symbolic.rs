@@ -0,0 +1,606 @@
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BTreeSet},
+    fmt::{self, Display, Formatter},
+    mem,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+use easy_cast::Conv;
+use itertools::Itertools;
+use num_bigint::BigInt;
+use num_traits::{One, Zero};
+
+use crate::rational::Rational;
+
+pub type Number = Rational<BigInt>;
+
+/// An expression tree over named variables and exact rational constants.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Expression {
+    Constant(Number),
+    Variable(String),
+    Add(Box<Expression>, Box<Expression>),
+    Sub(Box<Expression>, Box<Expression>),
+    Mul(Box<Expression>, Box<Expression>),
+    Div(Box<Expression>, Box<Expression>),
+    Neg(Box<Expression>),
+}
+
+impl Expression {
+    pub fn constant(integer: impl Into<BigInt>) -> Self {
+        Self::Constant(Number::from(integer.into()))
+    }
+
+    pub fn variable(name: impl Into<String>) -> Self {
+        Self::Variable(name.into())
+    }
+
+    /// The value of the expression, or `None` if it contains variables or divides by zero.
+    #[must_use]
+    pub fn evaluate(&self) -> Option<Number> {
+        match self {
+            Self::Constant(constant) => Some(constant.clone()),
+            Self::Variable(_) => None,
+            Self::Add(lhs, rhs) => Some(lhs.evaluate()? + rhs.evaluate()?),
+            Self::Sub(lhs, rhs) => Some(lhs.evaluate()? - rhs.evaluate()?),
+            Self::Mul(lhs, rhs) => Some(lhs.evaluate()? * rhs.evaluate()?),
+            Self::Div(lhs, rhs) => {
+                let [lhs, rhs] = [lhs.evaluate()?, rhs.evaluate()?];
+                (!rhs.is_zero()).then(|| lhs / rhs)
+            }
+            Self::Neg(expression) => Some(-expression.evaluate()?),
+        }
+    }
+
+    /// Replaces every occurrence of `variable` by `value`.
+    #[must_use]
+    pub fn substitute(&self, variable: &str, value: &Self) -> Self {
+        let substitute = |expression: &Self| Box::new(expression.substitute(variable, value));
+        match self {
+            Self::Variable(name) if name == variable => value.clone(),
+            Self::Constant(_) | Self::Variable(_) => self.clone(),
+            Self::Add(lhs, rhs) => Self::Add(substitute(lhs), substitute(rhs)),
+            Self::Sub(lhs, rhs) => Self::Sub(substitute(lhs), substitute(rhs)),
+            Self::Mul(lhs, rhs) => Self::Mul(substitute(lhs), substitute(rhs)),
+            Self::Div(lhs, rhs) => Self::Div(substitute(lhs), substitute(rhs)),
+            Self::Neg(expression) => Self::Neg(substitute(expression)),
+        }
+    }
+
+    /// Folds constant subexpressions and removes additions of zero, multiplications by one and
+    /// double negations, while keeping the shape of the expression otherwise.
+    #[must_use]
+    pub fn simplify(&self) -> Self {
+        let simplified = match self {
+            Self::Constant(_) | Self::Variable(_) => return self.clone(),
+            Self::Add(lhs, rhs) => match [lhs.simplify(), rhs.simplify()] {
+                [lhs, rhs] if lhs.is_constant(0) => rhs,
+                [lhs, rhs] if rhs.is_constant(0) => lhs,
+                [lhs, rhs] => lhs + rhs,
+            },
+            Self::Sub(lhs, rhs) => match [lhs.simplify(), rhs.simplify()] {
+                [lhs, rhs] if lhs == rhs => Self::constant(0),
+                [lhs, rhs] if lhs.is_constant(0) => -rhs,
+                [lhs, rhs] if rhs.is_constant(0) => lhs,
+                [lhs, rhs] => lhs - rhs,
+            },
+            Self::Mul(lhs, rhs) => match [lhs.simplify(), rhs.simplify()] {
+                [lhs, rhs] if lhs.is_constant(0) || rhs.is_constant(0) => Self::constant(0),
+                [lhs, rhs] if lhs.is_constant(1) => rhs,
+                [lhs, rhs] if rhs.is_constant(1) => lhs,
+                [lhs, rhs] => lhs * rhs,
+            },
+            Self::Div(lhs, rhs) => match [lhs.simplify(), rhs.simplify()] {
+                [lhs, rhs] if rhs.is_constant(1) => lhs,
+                [lhs, rhs] => lhs / rhs,
+            },
+            Self::Neg(expression) => {
+                let expression = expression.simplify();
+                if let Self::Neg(expression) = expression {
+                    *expression
+                } else {
+                    -expression
+                }
+            }
+        };
+        simplified.fold_constants()
+    }
+
+    /// The expression in polynomial normal form, or `None` if it divides by zero or by an
+    /// expression containing variables.
+    #[must_use]
+    pub fn to_polynomial(&self) -> Option<Polynomial> {
+        let (numerator, denominator) = self.fraction()?;
+        denominator.to_constant()?.is_one().then_some(numerator)
+    }
+
+    /// The expression as a numerator and denominator in polynomial normal form, where the
+    /// denominator is one whenever it would be constant.
+    fn fraction(&self) -> Option<(Polynomial, Polynomial)> {
+        let fraction = match self {
+            Self::Constant(constant) => (Polynomial::constant(constant.clone()), Polynomial::one()),
+            Self::Variable(name) => (Polynomial::variable(name), Polynomial::one()),
+            Self::Add(lhs, rhs) | Self::Sub(lhs, rhs) => {
+                let (lhs_numerator, lhs_denominator) = lhs.fraction()?;
+                let (rhs_numerator, rhs_denominator) = rhs.fraction()?;
+                let rhs_numerator = if matches!(self, Self::Sub(..)) {
+                    -rhs_numerator
+                } else {
+                    rhs_numerator
+                };
+                if lhs_denominator == rhs_denominator {
+                    (lhs_numerator + rhs_numerator, lhs_denominator)
+                } else {
+                    (
+                        lhs_numerator * rhs_denominator.clone()
+                            + rhs_numerator * lhs_denominator.clone(),
+                        lhs_denominator * rhs_denominator,
+                    )
+                }
+            }
+            Self::Mul(lhs, rhs) => {
+                let (lhs_numerator, lhs_denominator) = lhs.fraction()?;
+                let (rhs_numerator, rhs_denominator) = rhs.fraction()?;
+                (
+                    lhs_numerator * rhs_numerator,
+                    lhs_denominator * rhs_denominator,
+                )
+            }
+            Self::Div(lhs, rhs) => {
+                let (lhs_numerator, lhs_denominator) = lhs.fraction()?;
+                let (rhs_numerator, rhs_denominator) = rhs.fraction()?;
+                if rhs_numerator.is_zero() {
+                    return None;
+                }
+                (
+                    lhs_numerator * rhs_denominator,
+                    lhs_denominator * rhs_numerator,
+                )
+            }
+            Self::Neg(expression) => {
+                let (numerator, denominator) = expression.fraction()?;
+                (-numerator, denominator)
+            }
+        };
+        Some(normalized(fraction))
+    }
+
+    fn is_constant(&self, integer: i32) -> bool {
+        matches!(self, Self::Constant(constant) if *constant == Number::from(BigInt::from(integer)))
+    }
+
+    fn fold_constants(self) -> Self {
+        let is_constant = |expression: &Self| matches!(expression, Self::Constant(_));
+        let is_foldable = match &self {
+            Self::Constant(_) | Self::Variable(_) => false,
+            Self::Add(lhs, rhs)
+            | Self::Sub(lhs, rhs)
+            | Self::Mul(lhs, rhs)
+            | Self::Div(lhs, rhs) => is_constant(lhs) && is_constant(rhs),
+            Self::Neg(expression) => is_constant(expression),
+        };
+        match is_foldable.then(|| self.evaluate()).flatten() {
+            Some(constant) => Self::Constant(constant),
+            None => self,
+        }
+    }
+}
+
+fn normalized((numerator, denominator): (Polynomial, Polynomial)) -> (Polynomial, Polynomial) {
+    match denominator.to_constant() {
+        Some(constant) => (
+            numerator * Polynomial::constant(Number::one() / constant),
+            Polynomial::one(),
+        ),
+        None => (numerator, denominator),
+    }
+}
+
+impl Add for Expression {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::Add(Box::new(self), Box::new(rhs))
+    }
+}
+
+impl Sub for Expression {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::Sub(Box::new(self), Box::new(rhs))
+    }
+}
+
+impl Mul for Expression {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self::Mul(Box::new(self), Box::new(rhs))
+    }
+}
+
+impl Div for Expression {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self {
+        Self::Div(Box::new(self), Box::new(rhs))
+    }
+}
+
+impl Neg for Expression {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::Neg(Box::new(self))
+    }
+}
+
+impl Display for Expression {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Constant(constant) => write!(f, "{constant}"),
+            Self::Variable(name) => write!(f, "{name}"),
+            Self::Add(lhs, rhs) => write!(f, "({lhs} + {rhs})"),
+            Self::Sub(lhs, rhs) => write!(f, "({lhs} - {rhs})"),
+            Self::Mul(lhs, rhs) => write!(f, "({lhs} * {rhs})"),
+            Self::Div(lhs, rhs) => write!(f, "({lhs} / {rhs})"),
+            Self::Neg(expression) => write!(f, "-{expression}"),
+        }
+    }
+}
+
+/// The variables of a term of a polynomial with their positive exponents.
+pub type Monomial = BTreeMap<String, u32>;
+
+/// A polynomial in named variables in normal form: a sum of distinct monomials with nonzero
+/// coefficients, such that equal polynomials have equal representations.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Polynomial {
+    terms: BTreeMap<Monomial, Number>,
+}
+
+impl Polynomial {
+    #[must_use]
+    pub fn constant(constant: Number) -> Self {
+        let mut polynomial = Self::default();
+        polynomial.add_term(Monomial::new(), constant);
+        polynomial
+    }
+
+    #[must_use]
+    pub fn variable(name: &str) -> Self {
+        let mut polynomial = Self::default();
+        polynomial.add_term(Monomial::from([(name.to_owned(), 1)]), Number::one());
+        polynomial
+    }
+
+    #[must_use]
+    pub fn one() -> Self {
+        Self::constant(Number::one())
+    }
+
+    #[must_use]
+    pub fn is_zero(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// The terms in increasing order of their monomials.
+    pub fn terms(&self) -> impl Iterator<Item = (&Monomial, &Number)> {
+        self.terms.iter()
+    }
+
+    /// The value of the polynomial, or `None` if it contains variables.
+    #[must_use]
+    pub fn to_constant(&self) -> Option<Number> {
+        match self.terms.iter().collect_vec()[..] {
+            [] => Some(Number::zero()),
+            [(monomial, constant)] if monomial.is_empty() => Some(constant.clone()),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn variables(&self) -> BTreeSet<&str> {
+        self.terms
+            .keys()
+            .flat_map(|monomial| monomial.keys().map(String::as_str))
+            .collect()
+    }
+
+    /// The coefficients of the powers of `variable` in increasing order of the exponent, or `None`
+    /// if the polynomial contains other variables. The zero polynomial has no coefficients.
+    #[must_use]
+    pub fn coefficients(&self, variable: &str) -> Option<Vec<Number>> {
+        let mut coefficients = vec![];
+        for (monomial, coefficient) in &self.terms {
+            let exponent = match monomial.iter().collect_vec()[..] {
+                [] => 0,
+                [(name, &exponent)] if name == variable => exponent,
+                _ => return None,
+            };
+            let exponent = usize::conv(exponent);
+            if coefficients.len() <= exponent {
+                coefficients.resize(exponent + 1, Number::zero());
+            }
+            coefficients[exponent] = coefficient.clone();
+        }
+        Some(coefficients)
+    }
+
+    fn add_term(&mut self, monomial: Monomial, coefficient: Number) {
+        let sum = match self.terms.remove(&monomial) {
+            Some(existing) => existing + coefficient,
+            None => coefficient,
+        };
+        if !sum.is_zero() {
+            self.terms.insert(monomial, sum);
+        }
+    }
+}
+
+impl Add for Polynomial {
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self {
+        for (monomial, coefficient) in rhs.terms {
+            self.add_term(monomial, coefficient);
+        }
+        self
+    }
+}
+
+impl Sub for Polynomial {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self + -rhs
+    }
+}
+
+impl Mul for Polynomial {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        let mut product = Self::default();
+        for ((lhs_monomial, lhs_coefficient), (rhs_monomial, rhs_coefficient)) in
+            self.terms.iter().cartesian_product(&rhs.terms)
+        {
+            let mut monomial = lhs_monomial.clone();
+            for (name, exponent) in rhs_monomial {
+                *monomial.entry(name.clone()).or_default() += exponent;
+            }
+            product.add_term(monomial, lhs_coefficient.clone() * rhs_coefficient.clone());
+        }
+        product
+    }
+}
+
+impl Neg for Polynomial {
+    type Output = Self;
+
+    fn neg(mut self) -> Self {
+        for coefficient in self.terms.values_mut() {
+            *coefficient = -mem::replace(coefficient, Number::zero());
+        }
+        self
+    }
+}
+
+impl Display for Polynomial {
+    /// Writes the terms in decreasing order of their degree, such as `3*x^2 + -1/2*x*y + 4`.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+        let degree = |monomial: &Monomial| monomial.values().sum::<u32>();
+        let terms = self.terms.iter().rev();
+        let terms = terms.sorted_by_key(|(monomial, _)| Reverse(degree(monomial)));
+        let terms = terms.map(|(monomial, coefficient)| {
+            let variables = monomial.iter().map(|(name, &exponent)| match exponent {
+                1 => name.clone(),
+                _ => format!("{name}^{exponent}"),
+            });
+            let coefficient =
+                (monomial.is_empty() || !coefficient.is_one()).then(|| coefficient.to_string());
+            coefficient.into_iter().chain(variables).join("*")
+        });
+        write!(f, "{}", terms.format(" + "))
+    }
+}
+
+/// The solutions of an equation in one unknown.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Solutions {
+    /// Finitely many solutions in increasing order, which might be none at all.
+    Finite(Vec<Number>),
+    /// Every number is a solution.
+    All,
+}
+
+impl Solutions {
+    /// The integer solutions, or `None` if there are infinitely many. An empty vector reports that
+    /// no integer solution exists.
+    #[must_use]
+    pub fn integers(&self) -> Option<Vec<BigInt>> {
+        match self {
+            Self::Finite(solutions) => {
+                Some(solutions.iter().filter_map(Number::to_integer).collect())
+            }
+            Self::All => None,
+        }
+    }
+}
+
+/// Solves `lhs == rhs` for `variable` exactly, or returns `None` if the equation contains other
+/// variables, is not linear or quadratic in `variable` once denominators are cleared, or has
+/// irrational solutions.
+#[must_use]
+pub fn solve(lhs: &Expression, rhs: &Expression, variable: &str) -> Option<Solutions> {
+    let (numerator, denominator) = (lhs.clone() - rhs.clone()).fraction()?;
+    let denominator = denominator.coefficients(variable)?;
+    let solutions = match numerator.coefficients(variable)?.as_slice() {
+        [] => return Some(Solutions::All),
+        [_] => vec![],
+        [constant, linear] => vec![-constant.clone() / linear.clone()],
+        [constant, linear, quadratic] => quadratic_roots(constant, linear, quadratic)?,
+        _ => return None,
+    };
+    let solutions = solutions
+        .into_iter()
+        .filter(|solution| !horner(&denominator, solution).is_zero())
+        .sorted()
+        .dedup()
+        .collect();
+    Some(Solutions::Finite(solutions))
+}
+
+fn quadratic_roots(constant: &Number, linear: &Number, quadratic: &Number) -> Option<Vec<Number>> {
+    let four = Number::from(BigInt::from(4));
+    let discriminant =
+        linear.clone() * linear.clone() - four * quadratic.clone() * constant.clone();
+    if discriminant < Number::zero() {
+        return Some(vec![]);
+    }
+    let root = rational_square_root(&discriminant)?;
+    let denominator = Number::from(BigInt::from(2)) * quadratic.clone();
+    Some(
+        [-root.clone(), root]
+            .into_iter()
+            .map(|root| (root - linear.clone()) / denominator.clone())
+            .collect(),
+    )
+}
+
+fn rational_square_root(number: &Number) -> Option<Number> {
+    let [numerator, denominator] = [number.numerator(), number.denominator()].map(|integer| {
+        let root = integer.sqrt();
+        (root.clone() * root.clone() == *integer).then_some(root)
+    });
+    Some(Number::new(numerator?, denominator?))
+}
+
+fn horner(coefficients: &[Number], value: &Number) -> Number {
+    coefficients
+        .iter()
+        .rev()
+        .fold(Number::zero(), |sum, coefficient| {
+            sum * value.clone() + coefficient.clone()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use infrastructure::test;
+
+    use super::*;
+
+    fn x() -> Expression {
+        Expression::variable("x")
+    }
+
+    fn c(integer: i64) -> Expression {
+        Expression::constant(integer)
+    }
+
+    fn q(numerator: i64, denominator: i64) -> Number {
+        Number::new(BigInt::from(numerator), BigInt::from(denominator))
+    }
+
+    fn finite(solutions: &[Number]) -> Solutions {
+        Solutions::Finite(solutions.to_vec())
+    }
+
+    #[test]
+    fn evaluate() {
+        let expression = (c(3) + c(4)) * c(2) / c(4) - -c(1);
+        assert_eq!(expression.evaluate(), Some(q(9, 2)));
+        assert_eq!((c(1) / (c(2) - c(2))).evaluate(), None);
+        assert_eq!((x() + c(1)).evaluate(), None);
+        let substituted = (x() * x()).substitute("x", &c(-3));
+        assert_eq!(substituted.evaluate(), Some(q(9, 1)));
+    }
+
+    #[test]
+    fn simplify() {
+        let function = |expression: Expression| expression.simplify();
+        let cases = [
+            (c(2) * c(3) + x(), c(6) + x()),
+            ((x() + c(0)) * c(1), x()),
+            (c(0) * (x() / c(7)), c(0)),
+            (-(-x()), x()),
+            ((x() - x()) + c(1) / c(2), Expression::Constant(q(1, 2))),
+            (x() / (c(2) - c(1)), x()),
+            (c(1) / (c(1) - c(1)), c(1) / c(0)),
+        ];
+        test::cases(function, cases);
+    }
+
+    #[test]
+    fn polynomial_normal_form() {
+        let y = Expression::variable("y");
+        let expression = (x() + c(1)) * (x() - c(1)) + y.clone() * x() / c(2) - c(3) * y;
+        let polynomial = expression
+            .to_polynomial()
+            .expect("expression should be a polynomial");
+        assert_eq!(polynomial.to_string(), "x^2 + 1/2*x*y + -3*y + -1");
+        assert_eq!(polynomial.variables().into_iter().collect_vec(), ["x", "y"]);
+        assert_eq!(polynomial.coefficients("x"), None);
+        let same = (x() * x() - c(1)) - (c(3) - x() / c(2)) * Expression::variable("y");
+        assert_eq!(same.to_polynomial(), Some(polynomial));
+        assert_eq!((c(1) / x()).to_polynomial(), None);
+        assert_eq!((x() - x()).to_polynomial(), Some(Polynomial::default()));
+    }
+
+    #[test]
+    fn linear_equations() {
+        let function = |(lhs, rhs): (Expression, Expression)| solve(&lhs, &rhs, "x");
+        let cases = [
+            (
+                (c(4) + c(2) * (x() - c(3)), c(150)),
+                Some(finite(&[q(76, 1)])),
+            ),
+            ((x() / c(3), c(5) - x()), Some(finite(&[q(15, 4)]))),
+            ((c(2) / (x() - c(1)), c(4)), Some(finite(&[q(3, 2)]))),
+            ((x() + c(1), x() + c(2)), Some(finite(&[]))),
+            ((c(2) * x(), x() + x()), Some(Solutions::All)),
+            ((x() * Expression::variable("y"), c(1)), None),
+        ];
+        test::cases(function, cases);
+    }
+
+    #[test]
+    fn quadratic_equations() {
+        let function = |(lhs, rhs): (Expression, Expression)| solve(&lhs, &rhs, "x");
+        let cases = [
+            ((x() * x(), c(4)), Some(finite(&[q(-2, 1), q(2, 1)]))),
+            (
+                ((c(2) * x() - c(1)) * (x() + c(3)), c(0)),
+                Some(finite(&[q(-3, 1), q(1, 2)])),
+            ),
+            (
+                ((x() - c(1)) * (x() - c(1)), c(0)),
+                Some(finite(&[q(1, 1)])),
+            ),
+            ((x() * x(), c(-1)), Some(finite(&[]))),
+            ((x() * x(), c(2)), None),
+            (
+                ((x() * x() - c(1)) / (x() - c(1)), c(0)),
+                Some(finite(&[q(-1, 1)])),
+            ),
+            ((x() * x() * x(), c(8)), None),
+        ];
+        test::cases(function, cases);
+    }
+
+    #[test]
+    fn integer_solutions() {
+        let no_integer = solve(&(c(2) * x()), &c(1), "x").expect("equation should be linear");
+        assert_eq!(no_integer.integers(), Some(vec![]));
+        let integer =
+            solve(&(x() * x() * c(2)), &(c(3) - x()), "x").expect("equation should be quadratic");
+        assert_eq!(integer.integers(), Some(vec![BigInt::from(1)]));
+        assert_eq!(Solutions::All.integers(), None);
+    }
+}
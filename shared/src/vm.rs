@@ -0,0 +1,266 @@
+use std::mem;
+
+/// What the machine does after executing an instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flow {
+    /// Continues with the next instruction.
+    Next,
+    /// Continues with the instruction at this offset from the current one.
+    Jump(isize),
+    /// Stops the machine.
+    Halt,
+}
+
+/// An instruction of a machine with registers and any other state of type `State`.
+pub trait Instruction {
+    type State;
+
+    /// The number of cycles it takes to execute the instruction. Its effects take place at the
+    /// end of the last cycle.
+    fn cycles(&self) -> usize {
+        1
+    }
+
+    fn execute(&self, state: &mut Self::State) -> Flow;
+}
+
+/// The reason a machine stopped running.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Termination {
+    /// The program counter left the program or an instruction halted the machine.
+    Halted,
+    /// An instruction was about to be executed a second time.
+    Loop,
+}
+
+/// An executed instruction as recorded in the trace of a machine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    /// The number of cycles completed before the instruction started.
+    pub cycle: usize,
+    pub program_counter: usize,
+}
+
+/// A machine executing a program one instruction at a time.
+pub struct Machine<'program, I: Instruction> {
+    program: &'program [I],
+    state: I::State,
+    program_counter: usize,
+    cycle: usize,
+    is_halted: bool,
+    trace: Option<Vec<Step>>,
+}
+
+impl<'program, I: Instruction> Machine<'program, I> {
+    pub fn new(program: &'program [I], state: I::State) -> Self {
+        Self {
+            program,
+            state,
+            program_counter: 0,
+            cycle: 0,
+            is_halted: false,
+            trace: None,
+        }
+    }
+
+    /// Records every executed instruction, which is available through [`Machine::trace`].
+    #[must_use]
+    pub fn with_trace(mut self) -> Self {
+        self.trace = Some(vec![]);
+        self
+    }
+
+    pub fn state(&self) -> &I::State {
+        &self.state
+    }
+
+    pub fn state_mut(&mut self) -> &mut I::State {
+        &mut self.state
+    }
+
+    #[must_use]
+    pub fn into_state(self) -> I::State {
+        self.state
+    }
+
+    #[must_use]
+    pub fn program_counter(&self) -> usize {
+        self.program_counter
+    }
+
+    /// The number of completed cycles.
+    #[must_use]
+    pub fn cycle(&self) -> usize {
+        self.cycle
+    }
+
+    /// Every executed instruction in order of execution, if the trace is recorded.
+    #[must_use]
+    pub fn trace(&self) -> Option<&[Step]> {
+        self.trace.as_deref()
+    }
+
+    #[must_use]
+    pub fn is_halted(&self) -> bool {
+        self.is_halted || self.program_counter >= self.program.len()
+    }
+
+    /// Executes the next instruction and returns whether there was one to execute.
+    pub fn step(&mut self) -> bool {
+        self.step_with(|_, _| {})
+    }
+
+    /// Executes the next instruction like [`Machine::step`] and calls `on_cycle` at the start of
+    /// every cycle it takes with the number of completed cycles and the state during the cycle.
+    pub fn step_with(&mut self, mut on_cycle: impl FnMut(usize, &I::State)) -> bool {
+        if self.is_halted() {
+            return false;
+        }
+        let instruction = &self.program[self.program_counter];
+        if let Some(trace) = &mut self.trace {
+            trace.push(Step {
+                cycle: self.cycle,
+                program_counter: self.program_counter,
+            });
+        }
+        for _ in 0..instruction.cycles() {
+            on_cycle(self.cycle, &self.state);
+            self.cycle += 1;
+        }
+        match instruction.execute(&mut self.state) {
+            Flow::Next => self.program_counter += 1,
+            Flow::Jump(offset) => match self.program_counter.checked_add_signed(offset) {
+                Some(program_counter) => self.program_counter = program_counter,
+                None => self.is_halted = true,
+            },
+            Flow::Halt => self.is_halted = true,
+        }
+        true
+    }
+
+    pub fn run(&mut self) {
+        while self.step() {}
+    }
+
+    /// Runs until halted, calling `on_cycle` as described for [`Machine::step_with`].
+    pub fn run_with(&mut self, mut on_cycle: impl FnMut(usize, &I::State)) {
+        while self.step_with(&mut on_cycle) {}
+    }
+
+    /// Runs until halted or until an instruction is about to be executed a second time. A
+    /// repeated instruction only implies an infinite loop if the control flow of the program does
+    /// not depend on its state.
+    pub fn run_until_loop(&mut self) -> Termination {
+        let mut is_executed = vec![false; self.program.len()];
+        while !self.is_halted() {
+            if mem::replace(&mut is_executed[self.program_counter], true) {
+                return Termination::Loop;
+            }
+            self.step();
+        }
+        Termination::Halted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A toy instruction set operating on a counter.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Toy {
+        Add(isize),
+        Sleep(usize),
+        JumpIfPositive(isize),
+        Jump(isize),
+        Halt,
+    }
+
+    impl Instruction for Toy {
+        type State = isize;
+
+        fn cycles(&self) -> usize {
+            match *self {
+                Toy::Sleep(cycles) => cycles,
+                Toy::Add(_) | Toy::JumpIfPositive(_) | Toy::Jump(_) | Toy::Halt => 1,
+            }
+        }
+
+        fn execute(&self, counter: &mut isize) -> Flow {
+            match *self {
+                Toy::Add(number) => *counter += number,
+                Toy::JumpIfPositive(offset) if *counter > 0 => return Flow::Jump(offset),
+                Toy::Sleep(_) | Toy::JumpIfPositive(_) => {}
+                Toy::Jump(offset) => return Flow::Jump(offset),
+                Toy::Halt => return Flow::Halt,
+            }
+            Flow::Next
+        }
+    }
+
+    #[test]
+    fn countdown() {
+        let program = [
+            Toy::Add(3),
+            Toy::Add(-1),
+            Toy::JumpIfPositive(-1),
+            Toy::Add(10),
+        ];
+        let mut machine = Machine::new(&program, 0);
+        machine.run();
+        assert_eq!(machine.state(), &10);
+        assert_eq!(machine.program_counter(), program.len());
+        assert_eq!(machine.cycle(), 1 + 3 * 2 + 1);
+        assert!(!machine.step(), "halted machine should not step");
+    }
+
+    #[test]
+    fn halt_instruction_and_jump_before_start() {
+        let program = [Toy::Add(1), Toy::Halt, Toy::Add(1)];
+        let mut machine = Machine::new(&program, 0);
+        machine.run();
+        assert!(
+            machine.is_halted(),
+            "machine should halt at halt instruction"
+        );
+        assert_eq!(machine.program_counter(), 1);
+        assert_eq!(machine.into_state(), 1);
+
+        let program = [Toy::Add(1), Toy::Jump(-2), Toy::Add(1)];
+        let mut machine = Machine::new(&program, 0);
+        assert_eq!(machine.run_until_loop(), Termination::Halted);
+        assert_eq!(machine.program_counter(), 1);
+    }
+
+    #[test]
+    fn loop_detection() {
+        let program = [Toy::Add(1), Toy::Add(2), Toy::Jump(-1)];
+        let mut machine = Machine::new(&program, 0);
+        assert_eq!(machine.run_until_loop(), Termination::Loop);
+        assert_eq!(machine.state(), &3);
+        assert_eq!(machine.program_counter(), 1);
+    }
+
+    #[test]
+    fn cycle_hook_sees_state_during_cycles() {
+        let program = [Toy::Add(2), Toy::Sleep(3), Toy::Add(5)];
+        let mut machine = Machine::new(&program, 0);
+        let mut states = vec![];
+        machine.run_with(|cycle, &counter| states.push((cycle, counter)));
+        assert_eq!(states, [(0, 0), (1, 2), (2, 2), (3, 2), (4, 2)]);
+    }
+
+    #[test]
+    fn trace() {
+        let program = [Toy::Sleep(2), Toy::Jump(2), Toy::Halt, Toy::Add(1)];
+        let mut machine = Machine::new(&program, 0);
+        assert_eq!(machine.trace(), None);
+        machine = machine.with_trace();
+        machine.run();
+        let expected = [(0, 0), (2, 1), (3, 3)].map(|(cycle, program_counter)| Step {
+            cycle,
+            program_counter,
+        });
+        assert_eq!(machine.trace(), Some(expected.as_slice()));
+    }
+}
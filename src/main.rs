@@ -1,47 +1,142 @@
 use std::fmt::Debug;
 
 use anyhow::Context;
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use infrastructure::{
-    Day, Puzzle,
-    cli::{day, usize_within},
+    cli::{
+        self, GenerateArguments, LeaderboardArguments, PuzzleArguments, ServeArguments,
+        SolveArguments, SubmitArguments, SuiteArguments, usize_within,
+    },
+    year::Year,
 };
 
 fn main() -> anyhow::Result<()> {
-    let CommandLineArguments { year, day, puzzle } = CommandLineArguments::parse();
-
-    let &answer = [
-        advent20::answer,
-        advent21::answer,
-        advent22::answer,
-        advent23::answer,
-        advent24::answer,
-    ]
-    .get(year - 2020)
-    .context("year should be 2020, 2021, 2022, 2023, or 2024")?;
-    let answer = answer(day, puzzle)?;
-    println!("{answer}");
-
-    Ok(())
+    let CommandLineArguments { command, solve } = CommandLineArguments::parse();
+    match command {
+        Some(Command::Generate { year, arguments }) => {
+            cli::execute(self::year(year)?, cli::Command::Generate(arguments))
+        }
+        Some(Command::Compare { year, arguments }) => {
+            cli::execute(self::year(year)?, cli::Command::Compare(arguments))
+        }
+        Some(Command::Submit { year, arguments }) => {
+            cli::execute(self::year(year)?, cli::Command::Submit(arguments))
+        }
+        Some(Command::Leaderboard { year, arguments }) => {
+            cli::execute(self::year(year)?, cli::Command::Leaderboard(arguments))
+        }
+        Some(Command::All(arguments)) => {
+            let jobs = YEARS
+                .iter()
+                .flat_map(|year| {
+                    let number = year.year;
+                    cli::puzzle_input_jobs(year)
+                        .into_iter()
+                        .map(move |((day, puzzle), job)| {
+                            (format!("{number} {day:02} {puzzle:6}"), job)
+                        })
+                })
+                .collect();
+            cli::run_suite(jobs, arguments);
+            Ok(())
+        }
+        Some(Command::Serve(arguments)) => cli::serve(&YEARS, arguments),
+        None => {
+            let YearSolveArguments { year, arguments } =
+                solve.expect("puzzle should be given if no command is");
+            cli::solve(self::year(year)?, arguments)
+        }
+    }
 }
 
-type Year = usize;
+const YEARS: [Year; 5] = [
+    advent20::YEAR,
+    advent21::YEAR,
+    advent22::YEAR,
+    advent23::YEAR,
+    advent24::YEAR,
+];
 
-#[derive(Debug, Clone, Copy, Parser)]
+fn year(year: usize) -> anyhow::Result<&'static Year> {
+    YEARS
+        .iter()
+        .find(|served| served.year == year)
+        .context("year should be 2020, 2021, 2022, 2023, or 2024")
+}
+
+#[derive(Debug, Clone, Parser)]
 #[command(about, long_about = None)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct CommandLineArguments {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[command(flatten)]
+    solve: Option<YearSolveArguments>,
+}
+
+#[derive(Debug, Clone, clap::Args)]
+#[group(args = ["year"])]
+struct YearSolveArguments {
     /// Which year?
-    #[clap(value_parser=year)]
-    year: Year,
+    #[clap(value_parser=year_number)]
+    year: usize,
+
+    #[command(flatten)]
+    arguments: SolveArguments,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+enum Command {
+    /// Prints a random input that is valid under the puzzle description.
+    Generate {
+        /// Which year?
+        #[clap(value_parser=year_number)]
+        year: usize,
+
+        #[command(flatten)]
+        arguments: GenerateArguments,
+    },
+
+    /// Runs the solution and all its variants, checks that they agree and prints their timings.
+    Compare {
+        /// Which year?
+        #[clap(value_parser=year_number)]
+        year: usize,
+
+        #[command(flatten)]
+        arguments: PuzzleArguments,
+    },
+
+    /// Solves the puzzle inputs of all years concurrently and prints the answers in order.
+    All(SuiteArguments),
+
+    /// Submits the answer to the puzzle input, unless it is known to be wrong, and prints the
+    /// verdict.
+    Submit {
+        /// Which year?
+        #[clap(value_parser=year_number)]
+        year: usize,
+
+        #[command(flatten)]
+        arguments: SubmitArguments,
+    },
+
+    /// Answers puzzles of all years over HTTP on localhost: `GET /<year>/<day>` tells which
+    /// solutions exist, and `POST /<year>/<day>/<puzzle>` solves the input in the body.
+    Serve(ServeArguments),
 
-    /// Which day?
-    #[clap(value_parser=day)]
-    day: Day,
+    /// Prints scores, star times and timelines of an exported private leaderboard.
+    Leaderboard {
+        /// Which year? Solution timings are taken from it.
+        #[clap(value_parser=year_number)]
+        year: usize,
 
-    /// First or second puzzle?
-    puzzle: Puzzle,
+        #[command(flatten)]
+        arguments: LeaderboardArguments,
+    },
 }
 
-fn year(str: &str) -> anyhow::Result<Year> {
+fn year_number(str: &str) -> anyhow::Result<usize> {
     usize_within(2020..=2024, str)
 }